@@ -3,31 +3,73 @@
 
 //! Influx Measurement implementations
 
-use influxdb::{InfluxDbWriteable, WriteQuery};
+use std::collections::HashMap;
+
+use influxdb::{InfluxDbWriteable, ReadQuery, WriteQuery};
+use serde::Serialize;
 
 use super::{
     ledger::{
-        AddressActivityMeasurement, AddressBalanceMeasurement, BaseTokenActivityMeasurement, LedgerOutputMeasurement,
-        LedgerSizeMeasurement, OutputActivityMeasurement, TransactionSizeMeasurement, UnclaimedTokenMeasurement,
-        UnlockConditionMeasurement,
+        ActiveAddressesByKindMeasurement, AddressActivityMeasurement, AddressBalanceMeasurement,
+        BaseTokenActivityMeasurement, CoinDaysDestroyedMeasurement, DepositViolationMeasurement,
+        FeatureUsageMeasurement, FoundryPerAliasMeasurement, HoldingTimeMeasurement, LedgerOutputMeasurement,
+        LedgerSizeMeasurement, MedianTransactionValueMeasurement, OutputActivityMeasurement,
+        OutputValueHistogramMeasurement, OutputVelocityMeasurement, OutputsPerAddressMeasurement,
+        OutputsPerTransactionMeasurement, SupplyLockStateMeasurement, TransactionSizeMeasurement,
+        UnclaimedTokenMeasurement, UnlockConditionMeasurement,
     },
-    tangle::{BlockActivityMeasurement, MilestoneSizeMeasurement},
+    tangle::{BlockActivityMeasurement, MilestoneSizeMeasurement, MilestoneSizeTotalsMeasurement},
     AnalyticsInterval, PerInterval, PerMilestone,
 };
-use crate::{db::influxdb::InfluxDb, model::ProtocolParameters};
+use crate::{
+    db::influxdb::{AmountFormat, AnalyticsCategory, AnalyticsChoice, InfluxDb, InfluxDbConfig, TimestampResolution},
+    model::{
+        metadata::ConflictReason,
+        tangle::{MilestoneIndex, MilestoneIndexTimestamp},
+        ProtocolParameters,
+    },
+};
 
 /// A trait that defines an InfluxDb measurement.
 trait Measurement {
     const NAME: &'static str;
+    const CATEGORY: AnalyticsCategory;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery;
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F;
 }
 
 impl<M: Measurement + ?Sized> Measurement for &M {
     const NAME: &'static str = M::NAME;
+    const CATEGORY: AnalyticsCategory = M::CATEGORY;
+
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
+        (*self).add_fields(query, amounts)
+    }
+}
+
+/// The destination of the fields of a measurement, which is either a query or the schema of the measurement.
+trait AddField: Sized {
+    fn add_field(self, name: impl Into<String>, value: impl Into<influxdb::Type>) -> Self;
+}
+
+impl AddField for WriteQuery {
+    fn add_field(self, name: impl Into<String>, value: impl Into<influxdb::Type>) -> Self {
+        WriteQuery::add_field(self, name, value.into())
+    }
+}
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
-        (*self).add_fields(query)
+impl AddField for Vec<FieldSchema> {
+    fn add_field(mut self, name: impl Into<String>, value: impl Into<influxdb::Type>) -> Self {
+        self.push(FieldSchema {
+            name: name.into(),
+            kind: match value.into() {
+                influxdb::Type::Boolean(_) => FieldType::Boolean,
+                influxdb::Type::Float(_) => FieldType::Float,
+                influxdb::Type::SignedInteger(_) | influxdb::Type::UnsignedInteger(_) => FieldType::Integer,
+                influxdb::Type::Text(_) => FieldType::String,
+            },
+        });
+        self
     }
 }
 
@@ -37,22 +79,242 @@ trait IntervalMeasurement: Measurement {
 }
 
 trait AddFields<M: Measurement> {
-    fn add_fields(self, measurement: &M) -> Self;
+    fn add_fields(self, measurement: &M, amounts: AmountFields) -> Self;
 }
 
-impl<M: Measurement> AddFields<M> for WriteQuery {
-    fn add_fields(self, measurement: &M) -> Self {
-        measurement.add_fields(self)
+impl<F: AddField, M: Measurement> AddFields<M> for F {
+    fn add_fields(self, measurement: &M, amounts: AmountFields) -> Self {
+        measurement.add_fields(self, amounts)
+    }
+}
+
+/// Determines how amount fields are written, according to the configured [`AmountFormat`].
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct AmountFields {
+    format: AmountFormat,
+    base_token_decimals: u32,
+}
+
+impl AmountFields {
+    /// Creates the amount fields settings from the InfluxDb config.
+    pub(crate) fn from_config(config: &InfluxDbConfig) -> Self {
+        Self {
+            format: config.amount_format,
+            base_token_decimals: config.base_token_decimals,
+        }
+    }
+}
+
+trait AddAmountField {
+    fn add_amount_field(self, name: impl Into<String>, amount: u64, amounts: AmountFields) -> Self;
+}
+
+impl<F: AddField> AddAmountField for F {
+    fn add_amount_field(self, name: impl Into<String>, amount: u64, amounts: AmountFields) -> Self {
+        match amounts.format {
+            AmountFormat::Raw => self.add_field(name, amount),
+            // Precision is lost for amounts above 2^53 of the smallest unit.
+            AmountFormat::Scaled => {
+                self.add_field(name, amount as f64 / 10_f64.powi(amounts.base_token_decimals as i32))
+            }
+        }
+    }
+}
+
+/// Determines how measurements are turned into queries, according to the InfluxDb config.
+#[derive(Copy, Clone, Debug)]
+pub struct QuerySettings {
+    amounts: AmountFields,
+    timestamp_resolution: TimestampResolution,
+}
+
+impl QuerySettings {
+    /// Creates the query settings from the InfluxDb config.
+    pub fn from_config(config: &InfluxDbConfig) -> Self {
+        Self {
+            amounts: AmountFields::from_config(config),
+            timestamp_resolution: config.timestamp_resolution,
+        }
+    }
+
+    /// Snaps the timestamp of an interval measurement. Per-milestone measurements keep the exact milestone timestamp,
+    /// since snapping them would let the measurements of a period overwrite each other.
+    fn interval_timestamp(&self, seconds: u64) -> influxdb::Timestamp {
+        influxdb::Timestamp::Seconds(self.timestamp_resolution.snap(seconds) as _)
+    }
+}
+
+/// The type of a field as written to InfluxDb.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum FieldType {
+    Integer,
+    Float,
+    Boolean,
+    String,
+}
+
+/// A field of a measurement.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FieldSchema {
+    /// The name of the field.
+    pub name: String,
+    /// The type of the field.
+    #[serde(rename = "type")]
+    pub kind: FieldType,
+}
+
+/// Describes the fields of a measurement as it is written to InfluxDb.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MeasurementSchema {
+    /// The name of the measurement.
+    pub name: String,
+    /// The category of the measurement, which determines the database it is written to.
+    pub category: AnalyticsCategory,
+    /// The fields of the measurement, in the order they are written.
+    pub fields: Vec<FieldSchema>,
+}
+
+/// A measurement whose fields can be described from its type, without measuring a ledger.
+trait DescribeMeasurement: Measurement + Sized {
+    /// Creates a measurement that writes every field of its type. The number of fields of some measurements depends on
+    /// the protocol parameters.
+    fn describe(protocol_params: &ProtocolParameters) -> Self;
+
+    /// Describes the measurement as it is written for a milestone.
+    fn schema(protocol_params: &ProtocolParameters, amounts: AmountFields) -> MeasurementSchema {
+        let measurement = PerMilestone {
+            at: MilestoneIndexTimestamp {
+                milestone_index: 0.into(),
+                milestone_timestamp: 0.into(),
+            },
+            inner: Self::describe(protocol_params),
+        };
+        MeasurementSchema {
+            name: Self::NAME.to_string(),
+            category: Self::CATEGORY,
+            fields: measurement.add_fields(Vec::new(), amounts),
+        }
     }
 }
 
+/// Implements [`DescribeMeasurement`] for measurements whose default value writes every field.
+macro_rules! impl_describe_default {
+    ($($measurement:ty),* $(,)?) => {
+        $(
+            impl DescribeMeasurement for $measurement {
+                fn describe(_protocol_params: &ProtocolParameters) -> Self {
+                    Default::default()
+                }
+            }
+        )*
+    };
+}
+
+impl_describe_default!(
+    ActiveAddressesByKindMeasurement,
+    AddressActivityMeasurement,
+    BaseTokenActivityMeasurement,
+    BlockActivityMeasurement,
+    CoinDaysDestroyedMeasurement,
+    DepositViolationMeasurement,
+    FeatureUsageMeasurement,
+    FoundryPerAliasMeasurement,
+    HoldingTimeMeasurement,
+    LedgerOutputMeasurement,
+    LedgerSizeMeasurement,
+    MedianTransactionValueMeasurement,
+    MilestoneSizeMeasurement,
+    MilestoneSizeTotalsMeasurement,
+    OutputActivityMeasurement,
+    OutputVelocityMeasurement,
+    OutputsPerAddressMeasurement,
+    OutputsPerTransactionMeasurement,
+    SupplyLockStateMeasurement,
+    TransactionSizeMeasurement,
+    UnclaimedTokenMeasurement,
+    UnlockConditionMeasurement,
+);
+
+/// The number of logarithmic ranges of amounts up to the token supply, which are written as separate fields.
+fn token_supply_buckets(protocol_params: &ProtocolParameters) -> usize {
+    protocol_params.token_supply.ilog10() as usize + 1
+}
+
+impl DescribeMeasurement for AddressBalanceMeasurement {
+    fn describe(protocol_params: &ProtocolParameters) -> Self {
+        Self {
+            address_with_balance_count: 0,
+            token_distribution: vec![Default::default(); token_supply_buckets(protocol_params)],
+        }
+    }
+}
+
+impl DescribeMeasurement for OutputValueHistogramMeasurement {
+    fn describe(protocol_params: &ProtocolParameters) -> Self {
+        Self {
+            output_counts: vec![0; token_supply_buckets(protocol_params)],
+        }
+    }
+}
+
+impl DescribeMeasurement for ProtocolParameters {
+    fn describe(protocol_params: &ProtocolParameters) -> Self {
+        protocol_params.clone()
+    }
+}
+
+/// Describes the measurement written by the analytic of the given choice.
+pub(crate) fn measurement_schema(
+    choice: &AnalyticsChoice,
+    protocol_params: &ProtocolParameters,
+    amounts: AmountFields,
+) -> MeasurementSchema {
+    match choice {
+        AnalyticsChoice::AddressBalance => AddressBalanceMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::BaseTokenActivity => BaseTokenActivityMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::BlockActivity => BlockActivityMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::ActiveAddresses => AddressActivityMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::ActiveAddressesByKind => ActiveAddressesByKindMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::CoinDaysDestroyed => CoinDaysDestroyedMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::DepositViolations => DepositViolationMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::FeatureUsage => FeatureUsageMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::FoundryPerAlias => FoundryPerAliasMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::HoldingTime => HoldingTimeMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::LedgerOutputs => LedgerOutputMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::LedgerSize => LedgerSizeMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::MedianTransactionValue => MedianTransactionValueMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::MilestoneSize => MilestoneSizeMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::MilestoneSizeTotals => MilestoneSizeTotalsMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::OutputActivity => OutputActivityMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::OutputValueHistogram => OutputValueHistogramMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::OutputVelocity => OutputVelocityMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::OutputsPerAddress => OutputsPerAddressMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::OutputsPerTransaction => OutputsPerTransactionMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::ProtocolParameters => ProtocolParameters::schema(protocol_params, amounts),
+        AnalyticsChoice::SupplyLockState => SupplyLockStateMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::TransactionSizeDistribution => TransactionSizeMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::UnclaimedTokens => UnclaimedTokenMeasurement::schema(protocol_params, amounts),
+        AnalyticsChoice::UnlockConditions => UnlockConditionMeasurement::schema(protocol_params, amounts),
+    }
+}
+
+/// A measurement that can be turned into InfluxDb queries.
 pub trait PrepareQuery: Send + Sync {
-    fn prepare_query(&self) -> Vec<WriteQuery>;
+    /// Creates the write queries of the measurement, along with the category of analytic they belong to.
+    fn prepare_query(&self, settings: QuerySettings) -> Vec<(AnalyticsCategory, WriteQuery)>;
 }
 
 impl<T: PrepareQuery + ?Sized> PrepareQuery for Box<T> {
-    fn prepare_query(&self) -> Vec<WriteQuery> {
-        (**self).prepare_query()
+    fn prepare_query(&self, settings: QuerySettings) -> Vec<(AnalyticsCategory, WriteQuery)> {
+        (**self).prepare_query(settings)
+    }
+}
+
+impl<T: PrepareQuery + ?Sized> PrepareQuery for &T {
+    fn prepare_query(&self, settings: QuerySettings) -> Vec<(AnalyticsCategory, WriteQuery)> {
+        (**self).prepare_query(settings)
     }
 }
 
@@ -60,19 +322,34 @@ impl<M: Send + Sync> PrepareQuery for PerMilestone<M>
 where
     M: Measurement,
 {
-    fn prepare_query(&self) -> Vec<WriteQuery> {
-        vec![
+    fn prepare_query(&self, settings: QuerySettings) -> Vec<(AnalyticsCategory, WriteQuery)> {
+        vec![(
+            M::CATEGORY,
             influxdb::Timestamp::from(self.at.milestone_timestamp)
                 .into_query(M::NAME)
-                .add_field("milestone_index", self.at.milestone_index)
-                .add_fields(&self.inner),
-        ]
+                .add_fields(self, settings.amounts),
+        )]
+    }
+}
+
+/// The measurement of a milestone is written along with the milestone index.
+impl<M: Measurement> Measurement for PerMilestone<M> {
+    const NAME: &'static str = M::NAME;
+    const CATEGORY: AnalyticsCategory = M::CATEGORY;
+
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
+        query
+            .add_field("milestone_index", self.at.milestone_index)
+            .add_fields(&self.inner, amounts)
     }
 }
 
 impl<T: PrepareQuery> PrepareQuery for PerMilestone<Vec<T>> {
-    fn prepare_query(&self) -> Vec<WriteQuery> {
-        self.inner.iter().flat_map(|inner| inner.prepare_query()).collect()
+    fn prepare_query(&self, settings: QuerySettings) -> Vec<(AnalyticsCategory, WriteQuery)> {
+        self.inner
+            .iter()
+            .flat_map(|inner| inner.prepare_query(settings))
+            .collect()
     }
 }
 
@@ -80,10 +357,10 @@ impl<M: Send + Sync> PrepareQuery for PerMilestone<Option<M>>
 where
     M: Measurement,
 {
-    fn prepare_query(&self) -> Vec<WriteQuery> {
+    fn prepare_query(&self, settings: QuerySettings) -> Vec<(AnalyticsCategory, WriteQuery)> {
         self.inner
             .iter()
-            .flat_map(|inner| PerMilestone { at: self.at, inner }.prepare_query())
+            .flat_map(|inner| PerMilestone { at: self.at, inner }.prepare_query(settings))
             .collect()
     }
 }
@@ -92,24 +369,27 @@ impl<M: Send + Sync> PrepareQuery for PerInterval<M>
 where
     M: IntervalMeasurement,
 {
-    fn prepare_query(&self) -> Vec<WriteQuery> {
-        vec![
-            influxdb::Timestamp::Seconds(self.start_date.midnight().assume_utc().unix_timestamp() as _)
+    fn prepare_query(&self, settings: QuerySettings) -> Vec<(AnalyticsCategory, WriteQuery)> {
+        vec![(
+            M::CATEGORY,
+            settings
+                .interval_timestamp(self.start.unix_timestamp() as u64)
                 .into_query(M::name(self.interval))
-                .add_fields(&self.inner),
-        ]
+                .add_fields(&self.inner, settings.amounts),
+        )]
     }
 }
 
 impl Measurement for AddressBalanceMeasurement {
     const NAME: &'static str = "stardust_addresses";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
         let mut query = query.add_field("address_with_balance_count", self.address_with_balance_count as u64);
         for (index, stat) in self.token_distribution.iter().enumerate() {
             query = query
                 .add_field(format!("address_count_{index}"), stat.address_count)
-                .add_field(format!("total_amount_{index}"), stat.total_amount.0);
+                .add_amount_field(format!("total_amount_{index}"), stat.total_amount.0, amounts);
         }
         query
     }
@@ -117,18 +397,20 @@ impl Measurement for AddressBalanceMeasurement {
 
 impl Measurement for BaseTokenActivityMeasurement {
     const NAME: &'static str = "stardust_base_token_activity";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
         query
-            .add_field("booked_amount", self.booked_amount.0)
-            .add_field("transferred_amount", self.transferred_amount.0)
+            .add_amount_field("booked_amount", self.booked_amount.0, amounts)
+            .add_amount_field("transferred_amount", self.transferred_amount.0, amounts)
     }
 }
 
 impl Measurement for BlockActivityMeasurement {
     const NAME: &'static str = "stardust_block_activity";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Tangle;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
         query
             .add_field("transaction_count", self.transaction_count as u64)
             .add_field("treasury_transaction_count", self.treasury_transaction_count as u64)
@@ -138,17 +420,82 @@ impl Measurement for BlockActivityMeasurement {
             .add_field("confirmed_count", self.confirmed_count as u64)
             .add_field("conflicting_count", self.conflicting_count as u64)
             .add_field("no_transaction_count", self.no_transaction_count as u64)
+            .add_conflict_reasons(&self.conflict_reasons)
+    }
+}
+
+trait AddConflictReasons {
+    fn add_conflict_reasons(self, counts: &HashMap<ConflictReason, usize>) -> Self;
+}
+
+impl<F: AddField> AddConflictReasons for F {
+    fn add_conflict_reasons(mut self, counts: &HashMap<ConflictReason, usize>) -> Self {
+        // All reasons are written, even if they did not occur, so that every point has the same fields.
+        for (reason, name) in [
+            (ConflictReason::InputUtxoAlreadySpent, "input_utxo_already_spent"),
+            (
+                ConflictReason::InputUtxoAlreadySpentInThisMilestone,
+                "input_utxo_already_spent_in_this_milestone",
+            ),
+            (ConflictReason::InputUtxoNotFound, "input_utxo_not_found"),
+            (
+                ConflictReason::CreatedConsumedAmountMismatch,
+                "created_consumed_amount_mismatch",
+            ),
+            (ConflictReason::InvalidSignature, "invalid_signature"),
+            (ConflictReason::TimelockNotExpired, "timelock_not_expired"),
+            (ConflictReason::InvalidNativeTokens, "invalid_native_tokens"),
+            (
+                ConflictReason::StorageDepositReturnUnfulfilled,
+                "storage_deposit_return_unfulfilled",
+            ),
+            (ConflictReason::InvalidUnlock, "invalid_unlock"),
+            (ConflictReason::InputsCommitmentsMismatch, "inputs_commitments_mismatch"),
+            (ConflictReason::UnverifiedSender, "unverified_sender"),
+            (
+                ConflictReason::InvalidChainStateTransition,
+                "invalid_chain_state_transition",
+            ),
+            (ConflictReason::SemanticValidationFailed, "semantic_validation_failed"),
+        ] {
+            let count = counts.get(&reason).copied().unwrap_or_default();
+            self = self.add_field(format!("conflict_{name}_count"), count as u64);
+        }
+        self
     }
 }
 
 impl Measurement for AddressActivityMeasurement {
     const NAME: &'static str = "stardust_active_addresses";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
         query.add_field("count", self.count as u64)
     }
 }
 
+impl Measurement for ActiveAddressesByKindMeasurement {
+    const NAME: &'static str = "stardust_active_addresses_by_kind";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
+        query
+            .add_field("basic_count", self.basic as u64)
+            .add_field("alias_count", self.alias as u64)
+            .add_field("foundry_count", self.foundry as u64)
+            .add_field("nft_count", self.nft as u64)
+    }
+}
+
+impl Measurement for DepositViolationMeasurement {
+    const NAME: &'static str = "stardust_deposit_violations";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
+        query.add_field("deposit_violation_count", self.deposit_violation_count as u64)
+    }
+}
+
 impl IntervalMeasurement for AddressActivityMeasurement {
     fn name(interval: AnalyticsInterval) -> String {
         format!("stardust_{interval}_active_addresses")
@@ -157,8 +504,9 @@ impl IntervalMeasurement for AddressActivityMeasurement {
 
 impl Measurement for TransactionSizeMeasurement {
     const NAME: &'static str = "stardust_transaction_size_distribution";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
 
-    fn add_fields(&self, mut query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, mut query: F, _amounts: AmountFields) -> F {
         for (bucket, value) in self.input_buckets.single_buckets() {
             query = query.add_field(format!("input_{bucket}"), value as u64);
         }
@@ -179,39 +527,112 @@ impl Measurement for TransactionSizeMeasurement {
     }
 }
 
+impl Measurement for FoundryPerAliasMeasurement {
+    const NAME: &'static str = "stardust_foundry_per_alias_distribution";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
+        query
+            .add_field("aliases_1", self.single as u64)
+            .add_field("aliases_2_to_5", self.small as u64)
+            .add_field("aliases_6_to_20", self.medium as u64)
+            .add_field("aliases_over_20", self.large as u64)
+    }
+}
+
+impl Measurement for FeatureUsageMeasurement {
+    const NAME: &'static str = "stardust_feature_usage";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
+        query
+            .add_field("sender_count", self.sender_count as u64)
+            .add_field("issuer_count", self.issuer_count as u64)
+            .add_field("metadata_count", self.metadata_count as u64)
+            .add_field("tag_count", self.tag_count as u64)
+    }
+}
+
+impl Measurement for CoinDaysDestroyedMeasurement {
+    const NAME: &'static str = "stardust_coin_days_destroyed";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
+        query
+            .add_field("spent_count", self.spent_count as u64)
+            .add_amount_field("coin_days_destroyed", self.coin_days_destroyed(), amounts)
+    }
+}
+
+impl Measurement for HoldingTimeMeasurement {
+    const NAME: &'static str = "stardust_holding_time";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
+        query
+            .add_field("spent_count", self.spent_count as u64)
+            .add_field("average_holding_time", self.average_holding_time())
+            .add_field("minute_count", self.buckets.minute as u64)
+            .add_field("hour_count", self.buckets.hour as u64)
+            .add_field("day_count", self.buckets.day as u64)
+            .add_field("week_count", self.buckets.week as u64)
+            .add_field("month_count", self.buckets.month as u64)
+            .add_field("year_count", self.buckets.year as u64)
+            .add_field("longer_count", self.buckets.longer as u64)
+    }
+}
+
 impl Measurement for LedgerOutputMeasurement {
     const NAME: &'static str = "stardust_ledger_outputs";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
         query
             .add_field("basic_count", self.basic.count as u64)
-            .add_field("basic_amount", self.basic.amount.0)
+            .add_amount_field("basic_amount", self.basic.amount.0, amounts)
             .add_field("alias_count", self.alias.count as u64)
-            .add_field("alias_amount", self.alias.amount.0)
+            .add_amount_field("alias_amount", self.alias.amount.0, amounts)
             .add_field("foundry_count", self.foundry.count as u64)
-            .add_field("foundry_amount", self.foundry.amount.0)
+            .add_amount_field("foundry_amount", self.foundry.amount.0, amounts)
             .add_field("nft_count", self.nft.count as u64)
-            .add_field("nft_amount", self.nft.amount.0)
+            .add_amount_field("nft_amount", self.nft.amount.0, amounts)
             .add_field("treasury_count", self.treasury.count as u64)
-            .add_field("treasury_amount", self.treasury.amount.0)
+            .add_amount_field("treasury_amount", self.treasury.amount.0, amounts)
     }
 }
 
 impl Measurement for LedgerSizeMeasurement {
     const NAME: &'static str = "stardust_ledger_size";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
         query
             .add_field("total_key_bytes", self.total_key_bytes)
             .add_field("total_data_bytes", self.total_data_bytes)
-            .add_field("total_storage_deposit_amount", self.total_storage_deposit_amount.0)
+            .add_amount_field(
+                "total_storage_deposit_amount",
+                self.total_storage_deposit_amount.0,
+                amounts,
+            )
+    }
+}
+
+impl Measurement for MedianTransactionValueMeasurement {
+    const NAME: &'static str = "stardust_median_transaction_value";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
+        query
+            .add_amount_field("median_value", self.median_value.0, amounts)
+            .add_field("transaction_count", self.transaction_count as u64)
     }
 }
 
 impl Measurement for MilestoneSizeMeasurement {
     const NAME: &'static str = "stardust_milestone_size";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Tangle;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
         query
             .add_field(
                 "total_milestone_payload_bytes",
@@ -230,13 +651,26 @@ impl Measurement for MilestoneSizeMeasurement {
                 self.total_treasury_transaction_payload_bytes as u64,
             )
             .add_field("total_milestone_bytes", self.total_milestone_bytes as u64)
+            .add_field("block_count", self.block_count as u64)
+            .add_field("avg_block_bytes", self.avg_block_bytes())
+            .add_field("max_block_bytes", self.max_block_bytes as u64)
+    }
+}
+
+impl Measurement for MilestoneSizeTotalsMeasurement {
+    const NAME: &'static str = "stardust_milestone_size_totals";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Tangle;
+
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
+        self.0.add_fields(query, amounts)
     }
 }
 
 impl Measurement for OutputActivityMeasurement {
     const NAME: &'static str = "stardust_output_activity";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
         query
             .add_field("alias_created_count", self.alias.created_count as u64)
             .add_field("alias_state_changed_count", self.alias.state_changed_count as u64)
@@ -251,12 +685,62 @@ impl Measurement for OutputActivityMeasurement {
     }
 }
 
+impl Measurement for OutputsPerAddressMeasurement {
+    const NAME: &'static str = "stardust_outputs_per_address";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
+        query
+            .add_field("address_count", self.address_count as u64)
+            .add_field("output_count", self.output_count as u64)
+            .add_field("avg_outputs_per_address", self.avg_outputs_per_address())
+            .add_field("max_outputs_per_address", self.max_outputs_per_address as u64)
+    }
+}
+
+impl Measurement for OutputValueHistogramMeasurement {
+    const NAME: &'static str = "stardust_output_value_histogram";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, mut query: F, _amounts: AmountFields) -> F {
+        for (index, &count) in self.output_counts.iter().enumerate() {
+            query = query.add_field(format!("output_count_{index}"), count as u64);
+        }
+        query
+    }
+}
+
+impl Measurement for OutputVelocityMeasurement {
+    const NAME: &'static str = "stardust_output_velocity";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
+        query
+            .add_field("velocity", self.velocity())
+            .add_field("consumed_count", self.consumed_count as u64)
+            .add_field("unspent_count", self.unspent_count as u64)
+    }
+}
+
+impl Measurement for OutputsPerTransactionMeasurement {
+    const NAME: &'static str = "stardust_outputs_per_transaction";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, _amounts: AmountFields) -> F {
+        query
+            .add_field("average_output_count", self.average_output_count())
+            .add_field("transaction_count", self.transaction_count as u64)
+            .add_field("output_count", self.output_count as u64)
+    }
+}
+
 impl Measurement for ProtocolParameters {
     const NAME: &'static str = "stardust_protocol_params";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Tangle;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
         query
-            .add_field("token_supply", self.token_supply)
+            .add_amount_field("token_supply", self.token_supply, amounts)
             .add_field("min_pow_score", self.min_pow_score)
             .add_field("below_max_depth", self.below_max_depth)
             .add_field("v_byte_cost", self.rent_structure.v_byte_cost)
@@ -265,38 +749,193 @@ impl Measurement for ProtocolParameters {
     }
 }
 
+impl Measurement for SupplyLockStateMeasurement {
+    const NAME: &'static str = "stardust_supply_lock_state";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
+
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
+        query
+            .add_amount_field("spendable_amount", self.spendable.0, amounts)
+            .add_amount_field("time_locked_amount", self.time_locked.0, amounts)
+            .add_amount_field("conditionally_held_amount", self.conditionally_held.0, amounts)
+    }
+}
+
 impl Measurement for UnclaimedTokenMeasurement {
     const NAME: &'static str = "stardust_unclaimed_rewards";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
         query
             .add_field("unclaimed_count", self.unclaimed_count as u64)
-            .add_field("unclaimed_amount", self.unclaimed_amount.0)
+            .add_amount_field("unclaimed_amount", self.unclaimed_amount.0, amounts)
     }
 }
 
 impl Measurement for UnlockConditionMeasurement {
     const NAME: &'static str = "stardust_unlock_conditions";
+    const CATEGORY: AnalyticsCategory = AnalyticsCategory::Ledger;
 
-    fn add_fields(&self, query: WriteQuery) -> WriteQuery {
+    fn add_fields<F: AddField>(&self, query: F, amounts: AmountFields) -> F {
         query
             .add_field("expiration_count", self.expiration.count as u64)
-            .add_field("expiration_amount", self.expiration.amount.0)
+            .add_amount_field("expiration_amount", self.expiration.amount.0, amounts)
             .add_field("timelock_count", self.timelock.count as u64)
-            .add_field("timelock_amount", self.timelock.amount.0)
+            .add_amount_field("timelock_amount", self.timelock.amount.0, amounts)
             .add_field("storage_deposit_return_count", self.storage_deposit_return.count as u64)
-            .add_field("storage_deposit_return_amount", self.storage_deposit_return.amount.0)
-            .add_field(
+            .add_amount_field(
+                "storage_deposit_return_amount",
+                self.storage_deposit_return.amount.0,
+                amounts,
+            )
+            .add_amount_field(
                 "storage_deposit_return_inner_amount",
                 self.storage_deposit_return_inner_amount,
+                amounts,
             )
     }
 }
 
 impl InfluxDb {
-    /// Writes a [`Measurement`] to the InfluxDB database.
+    /// Writes a [`Measurement`] to the InfluxDB database of its category.
     pub(super) async fn insert_measurement(&self, measurement: impl PrepareQuery) -> Result<(), influxdb::Error> {
-        self.analytics().query(measurement.prepare_query()).await?;
+        let mut queries = HashMap::<_, Vec<_>>::new();
+        for (category, query) in measurement.prepare_query(QuerySettings::from_config(self.config())) {
+            queries.entry(category).or_default().push(query);
+        }
+        for (category, queries) in queries {
+            self.analytics_for(category).query(queries).await?;
+        }
         Ok(())
     }
+
+    /// Gets the milestone size totals that were written for the given milestone, if any.
+    pub(super) async fn get_milestone_size_totals(
+        &self,
+        index: MilestoneIndex,
+    ) -> Result<Option<MilestoneSizeMeasurement>, influxdb::Error> {
+        let query = ReadQuery::new(format!(
+            "SELECT * FROM \"{}\" WHERE \"milestone_index\" = {index}",
+            MilestoneSizeTotalsMeasurement::NAME
+        ));
+        Ok(self
+            .analytics_for(MilestoneSizeTotalsMeasurement::CATEGORY)
+            .select::<MilestoneSizeMeasurement>(query)
+            .await?
+            .next())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use influxdb::{InfluxDbWriteable, Query};
+
+    use super::{
+        measurement_schema, AddAmountField, AddField, AmountFields, FieldSchema, FieldType, MilestoneSizeMeasurement,
+        PerMilestone, PrepareQuery, QuerySettings,
+    };
+    use crate::{
+        db::influxdb::{AmountFormat, AnalyticsChoice, TimestampResolution},
+        model::{tangle::MilestoneIndexTimestamp, ProtocolParameters},
+    };
+
+    #[test]
+    fn test_amount_fields() {
+        let query = |format| {
+            influxdb::Timestamp::Seconds(0)
+                .into_query("test")
+                .add_amount_field(
+                    "amount",
+                    1_500_000,
+                    AmountFields {
+                        format,
+                        base_token_decimals: 6,
+                    },
+                )
+                .build()
+                .unwrap()
+                .get()
+        };
+        assert_eq!(query(AmountFormat::Raw), "test amount=1500000i 0");
+        assert_eq!(query(AmountFormat::Scaled), "test amount=1.5 0");
+    }
+
+    #[test]
+    fn test_timestamp_resolution() {
+        // Wednesday, 2023-03-15 13:37:42 UTC
+        let timestamp = 1678887462;
+        assert_eq!(TimestampResolution::Exact.snap(timestamp), timestamp);
+        assert_eq!(TimestampResolution::Minute.snap(timestamp), 1678887420);
+        assert_eq!(TimestampResolution::Hour.snap(timestamp), 1678885200);
+        assert_eq!(TimestampResolution::Day.snap(timestamp), 1678838400);
+        // Monday, 2023-03-13 00:00:00 UTC
+        assert_eq!(TimestampResolution::Week.snap(timestamp), 1678665600);
+        assert_eq!(TimestampResolution::Week.snap(1678665600), 1678665600);
+    }
+
+    #[test]
+    fn test_per_milestone_timestamps_are_exact() {
+        let settings = QuerySettings {
+            amounts: AmountFields {
+                format: AmountFormat::Raw,
+                base_token_decimals: 6,
+            },
+            timestamp_resolution: TimestampResolution::Week,
+        };
+        let measurement = PerMilestone {
+            at: MilestoneIndexTimestamp {
+                milestone_index: 1.into(),
+                milestone_timestamp: 1678887462.into(),
+            },
+            inner: MilestoneSizeMeasurement::default(),
+        };
+        let queries = measurement.prepare_query(settings);
+        assert_eq!(queries.len(), 1);
+        let line = queries[0].1.build().unwrap().get();
+        assert!(line.ends_with(" 1678887462"), "{line}");
+    }
+
+    #[test]
+    fn test_field_schema() {
+        let amounts = AmountFields {
+            format: AmountFormat::Scaled,
+            base_token_decimals: 6,
+        };
+        let field = |name: &str, kind| FieldSchema {
+            name: name.to_string(),
+            kind,
+        };
+        assert_eq!(
+            Vec::new()
+                .add_field("count", 1u64)
+                .add_field("ratio", 0.5)
+                .add_field("enabled", true)
+                .add_field("name", "a b")
+                .add_amount_field("amount", 1500000, amounts),
+            vec![
+                field("count", FieldType::Integer),
+                field("ratio", FieldType::Float),
+                field("enabled", FieldType::Boolean),
+                field("name", FieldType::String),
+                field("amount", FieldType::Float),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_described_measurement_schema() {
+        let protocol_params = ProtocolParameters::from(iota_types::block::protocol::ProtocolParameters::default());
+        let amounts = AmountFields {
+            format: AmountFormat::Raw,
+            base_token_decimals: 6,
+        };
+        let field_count = |choice| measurement_schema(&choice, &protocol_params, amounts).fields.len();
+
+        // The protocol parameters are only measured when they change, so an empty ledger would not describe them.
+        assert_eq!(field_count(AnalyticsChoice::ProtocolParameters), 7);
+        // One field for the milestone index and one per power of ten up to the token supply.
+        let buckets = protocol_params.token_supply.ilog10() as usize + 1;
+        assert_eq!(field_count(AnalyticsChoice::OutputValueHistogram), 1 + buckets);
+        assert_eq!(field_count(AnalyticsChoice::AddressBalance), 2 + 2 * buckets);
+    }
 }
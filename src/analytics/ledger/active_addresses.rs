@@ -1,11 +1,11 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashSet;
+use std::{collections::HashSet, ops::Range};
 
 use super::*;
 use crate::{
-    analytics::{AnalyticsInterval, IntervalAnalytics},
+    analytics::IntervalAnalytics,
     db::{mongodb::collections::OutputCollection, MongoDb},
     model::utxo::Address,
 };
@@ -28,13 +28,12 @@ impl IntervalAnalytics for AddressActivityMeasurement {
 
     async fn handle_date_range(
         &mut self,
-        start_date: time::Date,
-        interval: AnalyticsInterval,
+        range: Range<time::OffsetDateTime>,
         db: &MongoDb,
     ) -> eyre::Result<Self::Measurement> {
         let count = db
             .collection::<OutputCollection>()
-            .get_address_activity_count_in_range(start_date, interval.end_date(&start_date))
+            .get_address_activity_count_in_range(range.start.into(), range.end.into())
             .await?;
         Ok(AddressActivityMeasurement { count })
     }
@@ -63,3 +62,56 @@ impl Analytics for AddressActivityAnalytics {
         }
     }
 }
+
+/// The number of distinct addresses that owned outputs of each kind that were consumed or created in a milestone.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ActiveAddressesByKindMeasurement {
+    pub(crate) basic: usize,
+    pub(crate) alias: usize,
+    pub(crate) foundry: usize,
+    pub(crate) nft: usize,
+}
+
+/// Computes the number of addresses that were active per milestone, for each kind of output they owned.
+#[derive(Debug, Default)]
+pub(crate) struct ActiveAddressesByKindAnalytics {
+    basic: HashSet<Address>,
+    alias: HashSet<Address>,
+    foundry: HashSet<Address>,
+    nft: HashSet<Address>,
+}
+
+impl ActiveAddressesByKindAnalytics {
+    fn insert(&mut self, output: &Output, address: Address) {
+        let addresses = match output {
+            Output::Basic(_) => &mut self.basic,
+            Output::Alias(_) => &mut self.alias,
+            Output::Foundry(_) => &mut self.foundry,
+            Output::Nft(_) => &mut self.nft,
+            Output::Treasury(_) => return,
+        };
+        addresses.insert(address);
+    }
+}
+
+impl Analytics for ActiveAddressesByKindAnalytics {
+    type Measurement = ActiveAddressesByKindMeasurement;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        for output in consumed.iter().map(|spent| &spent.output).chain(created) {
+            if let Some(a) = output.owning_address() {
+                self.insert(&output.output, *a);
+            }
+        }
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        let analytics = std::mem::take(self);
+        ActiveAddressesByKindMeasurement {
+            basic: analytics.basic.len(),
+            alias: analytics.alias.len(),
+            foundry: analytics.foundry.len(),
+            nft: analytics.nft.len(),
+        }
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    ops::{AddAssign, SubAssign},
+};
 
 use super::*;
 use crate::model::utxo::{Address, TokenAmount};
@@ -21,36 +24,50 @@ pub(crate) struct DistributionStat {
     pub(crate) total_amount: TokenAmount,
 }
 
-/// Computes the number of addresses the currently hold a balance.
+/// A value that is accumulated per address over the unspent outputs it owns.
+pub(crate) trait AddressValue: Copy + Default + PartialEq + AddAssign + SubAssign {
+    /// The contribution of a single output.
+    fn of(output: &LedgerOutput) -> Self;
+}
+
+impl AddressValue for TokenAmount {
+    fn of(output: &LedgerOutput) -> Self {
+        output.amount()
+    }
+}
+
+impl AddressValue for usize {
+    fn of(_output: &LedgerOutput) -> Self {
+        1
+    }
+}
+
+/// Maps each address to a value accumulated over its unspent outputs. Addresses whose value drops to zero are removed.
 #[derive(Serialize, Deserialize)]
-pub(crate) struct AddressBalancesAnalytics {
-    balances: HashMap<Address, TokenAmount>,
+pub(crate) struct AddressMap<V> {
+    values: HashMap<Address, V>,
 }
 
-impl AddressBalancesAnalytics {
-    /// Initialize the analytics by reading the current ledger state.
+impl<V: AddressValue> AddressMap<V> {
+    /// Initialize the map by reading the current ledger state.
     pub(crate) fn init<'a>(unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>) -> Self {
-        let mut balances = HashMap::new();
+        let mut values = HashMap::<_, V>::new();
         for output in unspent_outputs {
             if let Some(&a) = output.owning_address() {
-                *balances.entry(a).or_default() += output.amount();
+                *values.entry(a).or_default() += V::of(output);
             }
         }
-        Self { balances }
+        Self { values }
     }
-}
-
-impl Analytics for AddressBalancesAnalytics {
-    type Measurement = AddressBalanceMeasurement;
 
-    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+    pub(crate) fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput]) {
         for output in consumed {
             if let Some(a) = output.owning_address() {
-                // All inputs should be present in `addresses`. If not, we skip it's value.
-                if let Some(amount) = self.balances.get_mut(a) {
-                    *amount -= output.amount();
-                    if amount.0 == 0 {
-                        self.balances.remove(a);
+                // All inputs should be present in `values`. If not, we skip it's value.
+                if let Some(value) = self.values.get_mut(a) {
+                    *value -= V::of(&output.output);
+                    if *value == V::default() {
+                        self.values.remove(a);
                     }
                 }
             }
@@ -58,12 +75,43 @@ impl Analytics for AddressBalancesAnalytics {
 
         for output in created {
             if let Some(&a) = output.owning_address() {
-                // All inputs should be present in `addresses`. If not, we skip it's value.
-                *self.balances.entry(a).or_default() += output.amount();
+                *self.values.entry(a).or_default() += V::of(output);
             }
         }
     }
 
+    /// The number of addresses with a non-zero value.
+    pub(crate) fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &V> {
+        self.values.values()
+    }
+}
+
+/// Computes the number of addresses the currently hold a balance.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct AddressBalancesAnalytics {
+    balances: AddressMap<TokenAmount>,
+}
+
+impl AddressBalancesAnalytics {
+    /// Initialize the analytics by reading the current ledger state.
+    pub(crate) fn init<'a>(unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>) -> Self {
+        Self {
+            balances: AddressMap::init(unspent_outputs),
+        }
+    }
+}
+
+impl Analytics for AddressBalancesAnalytics {
+    type Measurement = AddressBalanceMeasurement;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        self.balances.handle_transaction(consumed, created);
+    }
+
     fn take_measurement(&mut self, ctx: &dyn AnalyticsContext) -> Self::Measurement {
         let bucket_max = ctx.protocol_params().token_supply.ilog10() as usize + 1;
         let mut token_distribution = vec![DistributionStat::default(); bucket_max];
@@ -79,4 +127,11 @@ impl Analytics for AddressBalancesAnalytics {
             token_distribution,
         }
     }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "address_count": self.balances.len(),
+            "total_amount": self.balances.values().copied().sum::<TokenAmount>(),
+        }))
+    }
 }
@@ -0,0 +1,39 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+const DAY: u128 = 24 * 60 * 60;
+
+/// Measures the coin-days destroyed by spent outputs, which weighs the spent amounts by how long they were held.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct CoinDaysDestroyedMeasurement {
+    /// The number of spent outputs.
+    pub(crate) spent_count: usize,
+    /// The sum of the amounts of all spent outputs, each multiplied by the number of seconds it was held.
+    pub(crate) coin_seconds_destroyed: u128,
+}
+
+impl CoinDaysDestroyedMeasurement {
+    /// The coin-days destroyed, in the smallest unit of the base token.
+    pub(crate) fn coin_days_destroyed(&self) -> u64 {
+        (self.coin_seconds_destroyed / DAY).try_into().unwrap_or(u64::MAX)
+    }
+}
+
+impl Analytics for CoinDaysDestroyedMeasurement {
+    type Measurement = Self;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], _created: &[LedgerOutput], ctx: &dyn AnalyticsContext) {
+        let milestone_timestamp = ctx.at().milestone_timestamp.0;
+        for output in consumed {
+            let held = milestone_timestamp.saturating_sub(output.output.booked.milestone_timestamp.0);
+            self.spent_count += 1;
+            self.coin_seconds_destroyed += output.amount().0 as u128 * held as u128;
+        }
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        std::mem::take(self)
+    }
+}
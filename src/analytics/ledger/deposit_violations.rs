@@ -0,0 +1,94 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use iota_types::block::output::Rent;
+use tracing::warn;
+
+use super::*;
+use crate::model::{utxo::OutputId, ProtocolParameters, TryFromWithContext};
+
+fn convert_protocol_params(protocol_params: &ProtocolParameters) -> iota_types::block::protocol::ProtocolParameters {
+    // Unwrap: acceptable risk
+    iota_types::block::protocol::ProtocolParameters::try_from(protocol_params.clone())
+        .expect("protocol parameters conversion error")
+}
+
+/// Whether the output holds less than the storage deposit it requires under the given protocol parameters. Outputs
+/// that can not be converted are logged and not counted as violations.
+fn violates_deposit(output: &LedgerOutput, protocol_params: &iota_types::block::protocol::ProtocolParameters) -> bool {
+    match iota_types::block::output::Output::try_from_with_context(protocol_params, output.output.clone()) {
+        Ok(output) => output.amount() < output.rent_cost(protocol_params.rent_structure()),
+        Err(e) => {
+            warn!(
+                "Could not check the storage deposit of output {}: {e}",
+                output.output_id.to_hex()
+            );
+            false
+        }
+    }
+}
+
+/// The number of unspent outputs that hold less than their required storage deposit.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct DepositViolationMeasurement {
+    pub(crate) deposit_violation_count: usize,
+}
+
+/// Tracks the unspent outputs that hold less than their required storage deposit. The protocol rejects such outputs,
+/// so any violation points to a bug in indexing rather than to the ledger itself.
+///
+/// Outputs that are created while the analytics run are checked against the protocol parameters at the time they are
+/// created, so that raising the storage costs later does not flag them. The outputs of the ledger state that the
+/// analytics are initialized from are checked against the protocol parameters at that time.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct DepositViolationAnalytics {
+    violations: HashSet<OutputId>,
+}
+
+impl DepositViolationAnalytics {
+    /// Initialize the analytics by reading the current ledger state.
+    pub(crate) fn init<'a>(
+        protocol_params: &ProtocolParameters,
+        unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>,
+    ) -> Self {
+        let protocol_params = convert_protocol_params(protocol_params);
+        Self {
+            violations: unspent_outputs
+                .into_iter()
+                .filter(|output| violates_deposit(output, &protocol_params))
+                .map(|output| output.output_id)
+                .collect(),
+        }
+    }
+}
+
+impl Analytics for DepositViolationAnalytics {
+    type Measurement = DepositViolationMeasurement;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], ctx: &dyn AnalyticsContext) {
+        for output in consumed {
+            self.violations.remove(&output.output_id());
+        }
+        if created.is_empty() {
+            return;
+        }
+        let protocol_params = convert_protocol_params(ctx.protocol_params());
+        for output in created {
+            if violates_deposit(output, &protocol_params) {
+                self.violations.insert(output.output_id);
+            }
+        }
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        DepositViolationMeasurement {
+            deposit_violation_count: self.violations.len(),
+        }
+    }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(self).ok()
+    }
+}
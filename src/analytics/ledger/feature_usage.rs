@@ -0,0 +1,54 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::model::utxo::Feature;
+
+/// The number of created outputs that use each feature kind.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FeatureUsageMeasurement {
+    pub(crate) sender_count: usize,
+    pub(crate) issuer_count: usize,
+    pub(crate) metadata_count: usize,
+    pub(crate) tag_count: usize,
+}
+
+impl FeatureUsageMeasurement {
+    fn add_output(&mut self, output: &Output) {
+        let (features, immutable_features): (&[Feature], &[Feature]) = match output {
+            Output::Basic(output) => (&output.features, &[]),
+            Output::Alias(output) => (&output.features, &output.immutable_features),
+            Output::Foundry(output) => (&output.features, &output.immutable_features),
+            Output::Nft(output) => (&output.features, &output.immutable_features),
+            Output::Treasury(_) => return,
+        };
+        let (mut sender, mut issuer, mut metadata, mut tag) = (false, false, false, false);
+        // An output is only counted once per feature kind, even if it appears as both mutable and immutable feature.
+        for feature in features.iter().chain(immutable_features) {
+            match feature {
+                Feature::Sender { .. } => sender = true,
+                Feature::Issuer { .. } => issuer = true,
+                Feature::Metadata { .. } => metadata = true,
+                Feature::Tag { .. } => tag = true,
+            }
+        }
+        self.sender_count += sender as usize;
+        self.issuer_count += issuer as usize;
+        self.metadata_count += metadata as usize;
+        self.tag_count += tag as usize;
+    }
+}
+
+impl Analytics for FeatureUsageMeasurement {
+    type Measurement = Self;
+
+    fn handle_transaction(&mut self, _consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        for output in created {
+            self.add_output(&output.output);
+        }
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        std::mem::take(self)
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::*;
+use crate::model::utxo::Address;
+
+/// The distribution of the number of unspent foundries controlled by an alias.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FoundryPerAliasMeasurement {
+    /// The number of aliases that control a single foundry.
+    pub(crate) single: usize,
+    /// The number of aliases that control 2 to 5 foundries.
+    pub(crate) small: usize,
+    /// The number of aliases that control 6 to 20 foundries.
+    pub(crate) medium: usize,
+    /// The number of aliases that control more than 20 foundries.
+    pub(crate) large: usize,
+}
+
+impl FoundryPerAliasMeasurement {
+    fn add(&mut self, foundry_count: usize) {
+        match foundry_count {
+            0 => (),
+            1 => self.single += 1,
+            2..=5 => self.small += 1,
+            6..=20 => self.medium += 1,
+            _ => self.large += 1,
+        }
+    }
+}
+
+/// Tracks the number of unspent foundries controlled by each alias.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct FoundryPerAliasAnalytics {
+    foundry_counts: HashMap<Address, usize>,
+}
+
+impl FoundryPerAliasAnalytics {
+    /// Initialize the analytics by reading the current ledger state.
+    pub(crate) fn init<'a>(unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>) -> Self {
+        let mut foundry_counts = HashMap::new();
+        for output in unspent_outputs {
+            if let Some(&alias) = controlling_alias(&output.output) {
+                *foundry_counts.entry(alias).or_default() += 1;
+            }
+        }
+        Self { foundry_counts }
+    }
+}
+
+/// The alias address that controls a foundry, which can never change.
+fn controlling_alias(output: &Output) -> Option<&Address> {
+    match output {
+        Output::Foundry(foundry) => Some(&foundry.immutable_alias_address_unlock_condition.address),
+        _ => None,
+    }
+}
+
+impl Analytics for FoundryPerAliasAnalytics {
+    type Measurement = FoundryPerAliasMeasurement;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        // Foundries that are only updated are both consumed and created, so the counts stay the same.
+        for output in consumed {
+            if let Some(alias) = controlling_alias(&output.output.output) {
+                if let Some(count) = self.foundry_counts.get_mut(alias) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.foundry_counts.remove(alias);
+                    }
+                }
+            }
+        }
+
+        for output in created {
+            if let Some(&alias) = controlling_alias(&output.output) {
+                *self.foundry_counts.entry(alias).or_default() += 1;
+            }
+        }
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        let mut measurement = FoundryPerAliasMeasurement::default();
+        for &count in self.foundry_counts.values() {
+            measurement.add(count);
+        }
+        measurement
+    }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "alias_count": self.foundry_counts.len(),
+            "foundry_count": self.foundry_counts.values().sum::<usize>(),
+        }))
+    }
+}
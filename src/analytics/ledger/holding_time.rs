@@ -0,0 +1,86 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+const MINUTE: u32 = 60;
+const HOUR: u32 = 60 * MINUTE;
+const DAY: u32 = 24 * HOUR;
+const WEEK: u32 = 7 * DAY;
+const MONTH: u32 = 30 * DAY;
+const YEAR: u32 = 365 * DAY;
+
+/// The number of spent outputs per holding time range.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct HoldingTimeBuckets {
+    /// [0..1 minute)
+    pub(crate) minute: usize,
+    /// [1 minute..1 hour)
+    pub(crate) hour: usize,
+    /// [1 hour..1 day)
+    pub(crate) day: usize,
+    /// [1 day..1 week)
+    pub(crate) week: usize,
+    /// [1 week..30 days)
+    pub(crate) month: usize,
+    /// [30 days..365 days)
+    pub(crate) year: usize,
+    /// 365 days and longer
+    pub(crate) longer: usize,
+}
+
+impl HoldingTimeBuckets {
+    fn add(&mut self, seconds: u32) {
+        match seconds {
+            s if s < MINUTE => self.minute += 1,
+            s if s < HOUR => self.hour += 1,
+            s if s < DAY => self.day += 1,
+            s if s < WEEK => self.week += 1,
+            s if s < MONTH => self.month += 1,
+            s if s < YEAR => self.year += 1,
+            _ => self.longer += 1,
+        }
+    }
+}
+
+/// Measures how long outputs were held before being spent.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct HoldingTimeMeasurement {
+    /// The number of spent outputs.
+    pub(crate) spent_count: usize,
+    /// The sum of the holding times of all spent outputs, in seconds.
+    pub(crate) total_holding_time: u64,
+    /// The distribution of holding times.
+    pub(crate) buckets: HoldingTimeBuckets,
+}
+
+impl HoldingTimeMeasurement {
+    /// The average holding time of the spent outputs, in seconds.
+    pub(crate) fn average_holding_time(&self) -> u64 {
+        self.total_holding_time
+            .checked_div(self.spent_count as u64)
+            .unwrap_or_default()
+    }
+}
+
+impl Analytics for HoldingTimeMeasurement {
+    type Measurement = Self;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], _created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        for output in consumed {
+            let holding_time = output
+                .spent_metadata
+                .spent
+                .milestone_timestamp
+                .0
+                .saturating_sub(output.output.booked.milestone_timestamp.0);
+            self.spent_count += 1;
+            self.total_holding_time += holding_time as u64;
+            self.buckets.add(holding_time);
+        }
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        std::mem::take(self)
+    }
+}
@@ -61,4 +61,8 @@ impl Analytics for LedgerOutputMeasurement {
     fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
         *self
     }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(self).ok()
+    }
 }
@@ -83,12 +83,32 @@ impl LedgerSizeAnalytics {
             measurement,
         }
     }
+
+    /// Re-derives the total storage deposit from the ledger bytes if the protocol parameters changed.
+    fn update_protocol_params(&mut self, protocol_params: &ProtocolParameters) {
+        if &self.protocol_params != protocol_params {
+            let rent_structure = protocol_params.rent_structure;
+            self.measurement.total_storage_deposit_amount = TokenAmount(
+                self.measurement
+                    .total_data_bytes
+                    .wrapping_mul(rent_structure.v_byte_factor_data as u64)
+                    .wrapping_add(
+                        self.measurement
+                            .total_key_bytes
+                            .wrapping_mul(rent_structure.v_byte_factor_key as u64),
+                    )
+                    .wrapping_mul(rent_structure.v_byte_cost as u64),
+            );
+            self.protocol_params = protocol_params.clone();
+        }
+    }
 }
 
 impl Analytics for LedgerSizeAnalytics {
     type Measurement = LedgerSizeMeasurement;
 
-    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], ctx: &dyn AnalyticsContext) {
+        self.update_protocol_params(ctx.protocol_params());
         for output in created {
             self.measurement
                 .wrapping_add(output.output.ledger_size(&self.protocol_params));
@@ -99,7 +119,12 @@ impl Analytics for LedgerSizeAnalytics {
         }
     }
 
-    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+    fn take_measurement(&mut self, ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        self.update_protocol_params(ctx.protocol_params());
         self.measurement
     }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(self.measurement).ok()
+    }
 }
@@ -0,0 +1,65 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use super::*;
+
+/// The number of buckets per power of two. Every bucket spans a factor of `2^(1/8)` (~9%), and values are estimated
+/// by the geometric center of their bucket, so the estimate is within `2^(1/16) - 1` (~4.4%) of the value of the
+/// true median.
+const BUCKETS_PER_OCTAVE: f64 = 8.0;
+
+/// The approximate median value of the transactions in a milestone.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MedianTransactionValueMeasurement {
+    /// The estimated median of the amounts created by a transaction.
+    pub(crate) median_value: TokenAmount,
+    /// The number of transactions the estimate is based on.
+    pub(crate) transaction_count: usize,
+}
+
+/// Estimates the median transaction value with a logarithmically bucketed histogram, which needs constant memory
+/// regardless of the number of transactions.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MedianTransactionValueAnalytics {
+    /// The number of transactions per bucket. Transactions without value are counted in bucket `None`.
+    buckets: BTreeMap<Option<u16>, usize>,
+}
+
+impl MedianTransactionValueAnalytics {
+    fn bucket(value: u64) -> Option<u16> {
+        (value > 0).then(|| ((value as f64).log2() * BUCKETS_PER_OCTAVE).floor() as u16)
+    }
+
+    fn estimate(bucket: Option<u16>) -> u64 {
+        bucket.map_or(0, |bucket| {
+            (2f64).powf((bucket as f64 + 0.5) / BUCKETS_PER_OCTAVE).round() as u64
+        })
+    }
+}
+
+impl Analytics for MedianTransactionValueAnalytics {
+    type Measurement = MedianTransactionValueMeasurement;
+
+    fn handle_transaction(&mut self, _consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        let value = created.iter().map(|output| output.amount().0).sum::<u64>();
+        *self.buckets.entry(Self::bucket(value)).or_default() += 1;
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        let buckets = std::mem::take(&mut self.buckets);
+        let transaction_count = buckets.values().sum::<usize>();
+        // The lower median for an even number of transactions.
+        let rank = (transaction_count + 1) / 2;
+        let mut seen = 0;
+        let median_bucket = buckets.into_iter().find_map(|(bucket, count)| {
+            seen += count;
+            (seen >= rank).then(|| bucket)
+        });
+        MedianTransactionValueMeasurement {
+            median_value: TokenAmount(median_bucket.map_or(0, Self::estimate)),
+            transaction_count,
+        }
+    }
+}
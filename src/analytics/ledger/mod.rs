@@ -6,12 +6,26 @@
 use serde::{Deserialize, Serialize};
 
 pub(super) use self::{
-    active_addresses::{AddressActivityAnalytics, AddressActivityMeasurement},
+    active_addresses::{
+        ActiveAddressesByKindAnalytics, ActiveAddressesByKindMeasurement, AddressActivityAnalytics,
+        AddressActivityMeasurement,
+    },
     address_balance::{AddressBalanceMeasurement, AddressBalancesAnalytics},
     base_token::BaseTokenActivityMeasurement,
+    coin_days_destroyed::CoinDaysDestroyedMeasurement,
+    deposit_violations::{DepositViolationAnalytics, DepositViolationMeasurement},
+    feature_usage::FeatureUsageMeasurement,
+    foundry_per_alias::{FoundryPerAliasAnalytics, FoundryPerAliasMeasurement},
+    holding_time::HoldingTimeMeasurement,
     ledger_outputs::LedgerOutputMeasurement,
     ledger_size::{LedgerSizeAnalytics, LedgerSizeMeasurement},
+    median_transaction_value::{MedianTransactionValueAnalytics, MedianTransactionValueMeasurement},
     output_activity::OutputActivityMeasurement,
+    output_value_histogram::{OutputValueHistogramAnalytics, OutputValueHistogramMeasurement},
+    output_velocity::{OutputVelocityAnalytics, OutputVelocityMeasurement},
+    outputs_per_address::{OutputsPerAddressAnalytics, OutputsPerAddressMeasurement},
+    outputs_per_transaction::OutputsPerTransactionMeasurement,
+    supply_lock_state::{SupplyLockStateAnalytics, SupplyLockStateMeasurement},
     transaction_size::TransactionSizeMeasurement,
     unclaimed_tokens::UnclaimedTokenMeasurement,
     unlock_conditions::UnlockConditionMeasurement,
@@ -27,9 +41,20 @@ use crate::{
 mod active_addresses;
 mod address_balance;
 mod base_token;
+mod coin_days_destroyed;
+mod deposit_violations;
+mod feature_usage;
+mod foundry_per_alias;
+mod holding_time;
 mod ledger_outputs;
 mod ledger_size;
+mod median_transaction_value;
 mod output_activity;
+mod output_value_histogram;
+mod output_velocity;
+mod outputs_per_address;
+mod outputs_per_transaction;
+mod supply_lock_state;
 mod transaction_size;
 mod unclaimed_tokens;
 mod unlock_conditions;
@@ -72,8 +97,12 @@ mod test {
             ledger::{LedgerOutput, LedgerSpent, RentStructureBytes},
             metadata::SpentMetadata,
             payload::TransactionId,
+            protocol::ProtocolParameters,
             tangle::MilestoneIndexTimestamp,
-            utxo::{Address, AliasId, AliasOutput, BasicOutput, NftId, NftOutput, Output, OutputId, TokenAmount},
+            utxo::{
+                Address, AliasId, AliasOutput, BasicOutput, Feature, FoundryOutput, NftId, NftOutput, Output, OutputId,
+                TokenAmount,
+            },
             BlockId,
         },
     };
@@ -85,6 +114,40 @@ mod test {
         Output::Basic(output)
     }
 
+    fn context_at(milestone_index: u32, milestone_timestamp: u32) -> TestContext {
+        TestContext {
+            at: MilestoneIndexTimestamp {
+                milestone_index: milestone_index.into(),
+                milestone_timestamp: milestone_timestamp.into(),
+            },
+            params: iota_types::block::protocol::protocol_parameters().into(),
+        }
+    }
+
+    fn ledger_output_at(output: Output, booked: MilestoneIndexTimestamp) -> LedgerOutput {
+        LedgerOutput {
+            output_id: OutputId::rand(),
+            rent_structure: RentStructureBytes {
+                num_key_bytes: 0,
+                num_data_bytes: 100,
+            },
+            output,
+            block_id: BlockId::rand(),
+            booked,
+        }
+    }
+
+    fn spent_at(output: LedgerOutput, spent: MilestoneIndexTimestamp) -> LedgerSpent {
+        LedgerSpent {
+            output,
+            spent_metadata: SpentMetadata {
+                transaction_id: TransactionId::rand(),
+                spent,
+                input_index: None,
+            },
+        }
+    }
+
     #[test]
     fn test_claiming() {
         let protocol_params = iota_types::block::protocol::protocol_parameters();
@@ -118,6 +181,7 @@ mod test {
                         milestone_index: (i as u32 + 1).into(),
                         milestone_timestamp: (i as u32 + 10001).into(),
                     },
+                    input_index: None,
                 },
             })
             .map(|output| (output.spent_metadata.spent, output))
@@ -217,6 +281,7 @@ mod test {
                     milestone_index: 2.into(),
                     milestone_timestamp: 12345.into(),
                 },
+                input_index: None,
             },
         };
 
@@ -322,6 +387,7 @@ mod test {
                     milestone_index: 2.into(),
                     milestone_timestamp: 12345.into(),
                 },
+                input_index: None,
             },
         };
 
@@ -473,6 +539,7 @@ mod test {
             spent_metadata: SpentMetadata {
                 transaction_id,
                 spent: milestone,
+                input_index: None,
             },
         };
 
@@ -536,4 +603,449 @@ mod test {
         // Address 1 has delta +175, Address 2 has delta +70, Address 3 has delta -255
         assert_eq!(base_tokens_measurement.transferred_amount.0, 245)
     }
+
+    #[test]
+    fn test_ledger_size_protocol_params_change() {
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+        let booked = context_at(0, 10000).at;
+
+        let ledger_state = (0..5)
+            .map(|_| ledger_output_at(Output::Basic(BasicOutput::rand(&protocol_params)), booked))
+            .collect::<Vec<_>>();
+
+        let mut changed_params: ProtocolParameters = protocol_params.clone().into();
+        changed_params.rent_structure.v_byte_cost *= 2;
+
+        let mut ledger_size = LedgerSizeAnalytics::init(protocol_params.into(), &ledger_state);
+        let ctx = context_at(1, 10001);
+        let before = ledger_size.take_measurement(&ctx);
+
+        let ctx = TestContext {
+            params: changed_params.clone(),
+            ..context_at(2, 10002)
+        };
+        ledger_size.handle_transaction(&[], &[], &ctx);
+        let after = ledger_size.take_measurement(&ctx);
+
+        assert_eq!(after.total_key_bytes, before.total_key_bytes);
+        assert_eq!(after.total_data_bytes, before.total_data_bytes);
+        assert_eq!(
+            after.total_storage_deposit_amount.0,
+            2 * before.total_storage_deposit_amount.0
+        );
+        assert_eq!(
+            after,
+            LedgerSizeAnalytics::init(changed_params, &ledger_state).take_measurement(&ctx)
+        );
+    }
+
+    #[test]
+    fn test_holding_time() {
+        let ctx = context_at(10, 1_000_000);
+        let spent = |held: u32| {
+            let booked = MilestoneIndexTimestamp {
+                milestone_index: 1.into(),
+                milestone_timestamp: (1_000_000 - held).into(),
+            };
+            spent_at(ledger_output_at(rand_output_with_amount(1.into()), booked), ctx.at)
+        };
+
+        let mut holding_time = HoldingTimeMeasurement::default();
+        holding_time.handle_transaction(&[spent(30), spent(90)], &[], &ctx);
+        holding_time.handle_transaction(&[spent(2 * 24 * 60 * 60)], &[], &ctx);
+        let measurement = holding_time.take_measurement(&ctx);
+
+        assert_eq!(measurement.spent_count, 3);
+        assert_eq!(measurement.average_holding_time(), (30 + 90 + 2 * 24 * 60 * 60) / 3);
+        assert_eq!(measurement.buckets.minute, 1);
+        assert_eq!(measurement.buckets.hour, 1);
+        assert_eq!(measurement.buckets.week, 1);
+        assert_eq!(holding_time.take_measurement(&ctx).spent_count, 0);
+    }
+
+    #[test]
+    fn test_feature_usage() {
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+        let ctx = context_at(1, 10000);
+
+        let mut basic = BasicOutput::rand(&protocol_params);
+        basic.features = vec![Feature::rand_sender(), Feature::rand_tag()].into_boxed_slice();
+        let mut nft = NftOutput::rand(&protocol_params);
+        nft.features = vec![Feature::rand_metadata()].into_boxed_slice();
+        nft.immutable_features = vec![Feature::rand_issuer(), Feature::rand_metadata()].into_boxed_slice();
+
+        let created = [Output::Basic(basic), Output::Nft(nft)]
+            .into_iter()
+            .map(|output| ledger_output_at(output, ctx.at))
+            .collect::<Vec<_>>();
+
+        let mut feature_usage = FeatureUsageMeasurement::default();
+        feature_usage.handle_transaction(&[], &created, &ctx);
+
+        assert_eq!(
+            feature_usage.take_measurement(&ctx),
+            FeatureUsageMeasurement {
+                sender_count: 1,
+                issuer_count: 1,
+                metadata_count: 1,
+                tag_count: 1,
+            }
+        );
+        assert_eq!(feature_usage.take_measurement(&ctx), FeatureUsageMeasurement::default());
+    }
+
+    #[test]
+    fn test_outputs_per_address() {
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+        let (address_1, address_2) = (Address::rand_ed25519(), Address::rand_ed25519());
+
+        let output_for = |address| {
+            let mut output = BasicOutput::rand(&protocol_params);
+            output.address_unlock_condition.address = address;
+            ledger_output_at(Output::Basic(output), context_at(0, 10000).at)
+        };
+
+        let ledger_state = vec![output_for(address_1), output_for(address_1), output_for(address_2)];
+        let ctx = context_at(1, 10001);
+
+        let mut outputs_per_address = OutputsPerAddressAnalytics::init(&ledger_state);
+        let measurement = outputs_per_address.take_measurement(&ctx);
+        assert_eq!(
+            measurement,
+            OutputsPerAddressMeasurement {
+                address_count: 2,
+                output_count: 3,
+                max_outputs_per_address: 2,
+            }
+        );
+        assert_eq!(measurement.avg_outputs_per_address(), 1.5);
+
+        let consumed = [spent_at(ledger_state[2].clone(), ctx.at)];
+        let created = [output_for(address_1)];
+        outputs_per_address.handle_transaction(&consumed, &created, &ctx);
+
+        assert_eq!(
+            outputs_per_address.take_measurement(&ctx),
+            OutputsPerAddressMeasurement {
+                address_count: 1,
+                output_count: 3,
+                max_outputs_per_address: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_supply_lock_state() {
+        use crate::model::utxo::unlock_condition::{
+            ExpirationUnlockCondition, StorageDepositReturnUnlockCondition, TimelockUnlockCondition,
+        };
+
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+        let booked = context_at(0, 10000).at;
+
+        let output_with = |amount: u64, timelock: Option<u32>, expiration: Option<u32>, sdruc: bool| {
+            let mut output = BasicOutput::rand(&protocol_params);
+            output.amount = TokenAmount(amount);
+            output.timelock_unlock_condition = timelock.map(|timestamp| TimelockUnlockCondition {
+                timestamp: timestamp.into(),
+            });
+            output.expiration_unlock_condition = expiration.map(|timestamp| ExpirationUnlockCondition {
+                return_address: Address::rand_ed25519(),
+                timestamp: timestamp.into(),
+            });
+            output.storage_deposit_return_unlock_condition = sdruc.then(|| StorageDepositReturnUnlockCondition {
+                return_address: Address::rand_ed25519(),
+                amount: TokenAmount(1),
+            });
+            ledger_output_at(Output::Basic(output), booked)
+        };
+
+        let ledger_state = vec![
+            output_with(100, None, None, false),
+            output_with(200, Some(20000), None, false),
+            output_with(300, None, Some(30000), false),
+            output_with(400, Some(20000), None, true),
+        ];
+        let mut supply_lock_state = SupplyLockStateAnalytics::init(&ledger_state);
+
+        assert_eq!(
+            supply_lock_state.take_measurement(&context_at(1, 10001)),
+            SupplyLockStateMeasurement {
+                spendable: TokenAmount(100),
+                time_locked: TokenAmount(600),
+                conditionally_held: TokenAmount(300),
+            }
+        );
+
+        // Once the timelocks expire, the outputs become spendable unless they carry other conditions.
+        let ctx = context_at(1, 20000);
+        assert_eq!(
+            supply_lock_state.take_measurement(&ctx),
+            SupplyLockStateMeasurement {
+                spendable: TokenAmount(300),
+                time_locked: TokenAmount(0),
+                conditionally_held: TokenAmount(700),
+            }
+        );
+
+        let consumed = [spent_at(ledger_state[2].clone(), ctx.at)];
+        let created = [output_with(300, None, None, false)];
+        supply_lock_state.handle_transaction(&consumed, &created, &ctx);
+
+        assert_eq!(
+            supply_lock_state.take_measurement(&ctx),
+            SupplyLockStateMeasurement {
+                spendable: TokenAmount(600),
+                time_locked: TokenAmount(0),
+                conditionally_held: TokenAmount(400),
+            }
+        );
+    }
+
+    #[test]
+    fn test_median_transaction_value() {
+        let ctx = context_at(1, 10001);
+        let output_with_amount = |amount| ledger_output_at(rand_output_with_amount(TokenAmount(amount)), ctx.at);
+
+        let mut median_value = MedianTransactionValueAnalytics::default();
+        assert_eq!(
+            median_value.take_measurement(&ctx),
+            MedianTransactionValueMeasurement::default()
+        );
+
+        for amounts in [
+            vec![10],
+            vec![1_000_000, 500_000],
+            vec![2_000_000_000],
+            vec![20],
+            vec![3_000_000],
+        ] {
+            let created = amounts.into_iter().map(output_with_amount).collect::<Vec<_>>();
+            median_value.handle_transaction(&[], &created, &ctx);
+        }
+        let measurement = median_value.take_measurement(&ctx);
+        assert_eq!(measurement.transaction_count, 5);
+        // The median is 1_500_000, which the estimate must be within 4.5% of.
+        assert!((measurement.median_value.0 as f64 / 1_500_000.0 - 1.0).abs() < 0.045);
+
+        // The histogram is reset after every measurement.
+        assert_eq!(median_value.take_measurement(&ctx).transaction_count, 0);
+    }
+
+    #[test]
+    fn test_outputs_per_transaction() {
+        let ctx = context_at(1, 10001);
+        let output = || ledger_output_at(rand_output_with_amount(TokenAmount(100)), ctx.at);
+
+        let mut outputs_per_transaction = OutputsPerTransactionMeasurement::default();
+        assert_eq!(
+            outputs_per_transaction.take_measurement(&ctx).average_output_count(),
+            0.0
+        );
+
+        for count in [1, 2, 6] {
+            let created = (0..count).map(|_| output()).collect::<Vec<_>>();
+            outputs_per_transaction.handle_transaction(&[], &created, &ctx);
+        }
+        let measurement = outputs_per_transaction.take_measurement(&ctx);
+        assert_eq!(measurement.transaction_count, 3);
+        assert_eq!(measurement.output_count, 9);
+        assert_eq!(measurement.average_output_count(), 3.0);
+
+        assert_eq!(
+            outputs_per_transaction.take_measurement(&ctx),
+            OutputsPerTransactionMeasurement::default()
+        );
+    }
+
+    #[test]
+    fn test_active_addresses_by_kind() {
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+        let ctx = context_at(1, 10001);
+        let (address_1, address_2) = (Address::rand_ed25519(), Address::rand_ed25519());
+        let basic_for = |address| {
+            let mut output = BasicOutput::rand(&protocol_params);
+            output.address_unlock_condition.address = address;
+            ledger_output_at(Output::Basic(output), ctx.at)
+        };
+        let nft_for = |address| {
+            let mut output = NftOutput::rand(&protocol_params);
+            output.address_unlock_condition.address = address;
+            ledger_output_at(Output::Nft(output), ctx.at)
+        };
+
+        let consumed = [spent_at(basic_for(address_1), ctx.at)];
+        let created = [nft_for(address_2), basic_for(address_2), basic_for(address_1)];
+
+        let mut active_addresses = ActiveAddressesByKindAnalytics::default();
+        active_addresses.handle_transaction(&consumed, &created, &ctx);
+        active_addresses.handle_transaction(&[], &[nft_for(address_2)], &ctx);
+        assert_eq!(
+            active_addresses.take_measurement(&ctx),
+            ActiveAddressesByKindMeasurement {
+                basic: 2,
+                nft: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            active_addresses.take_measurement(&ctx),
+            ActiveAddressesByKindMeasurement::default()
+        );
+    }
+
+    #[test]
+    fn test_deposit_violations() {
+        let ctx = context_at(1, 10001);
+        let ledger_output = |amount: u64| ledger_output_at(rand_output_with_amount(amount.into()), ctx.at);
+
+        let ledger_state = [ledger_output(1), ledger_output(1_000_000_000)];
+        let mut violations = DepositViolationAnalytics::init(&ctx.params, &ledger_state);
+        assert_eq!(violations.take_measurement(&ctx).deposit_violation_count, 1);
+
+        let consumed = [spent_at(ledger_state[0].clone(), ctx.at)];
+        let created = [ledger_output(2), ledger_output(3), ledger_output(1_000_000_000)];
+        violations.handle_transaction(&consumed, &created, &ctx);
+        assert_eq!(violations.take_measurement(&ctx).deposit_violation_count, 2);
+    }
+
+    #[test]
+    fn test_output_velocity() {
+        let ctx = context_at(1, 10001);
+        let ledger_output = || ledger_output_at(rand_output_with_amount(100.into()), ctx.at);
+        let spent = |output| spent_at(output, ctx.at);
+
+        let mut velocity = OutputVelocityAnalytics::default();
+        velocity.handle_transaction(&[], &[ledger_output(), ledger_output()], &ctx);
+        // The ledger was empty at the start of the milestone.
+        assert_eq!(velocity.take_measurement(&ctx).velocity(), 0.0);
+
+        let ledger_state = (0..4).map(|_| ledger_output()).collect::<Vec<_>>();
+        let mut velocity = OutputVelocityAnalytics::init(&ledger_state);
+        velocity.handle_transaction(
+            &[spent(ledger_state[0].clone())],
+            &[ledger_output(), ledger_output()],
+            &ctx,
+        );
+        let measurement = velocity.take_measurement(&ctx);
+        assert_eq!(
+            measurement,
+            OutputVelocityMeasurement {
+                consumed_count: 1,
+                unspent_count: 4,
+            }
+        );
+        assert_eq!(measurement.velocity(), 0.25);
+
+        velocity.handle_transaction(
+            &[spent(ledger_state[1].clone()), spent(ledger_state[2].clone())],
+            &[],
+            &ctx,
+        );
+        assert_eq!(velocity.take_measurement(&ctx).velocity(), 2.0 / 5.0);
+    }
+
+    #[test]
+    fn test_foundry_per_alias() {
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+        let ctx = context_at(1, 10001);
+        let foundry = |alias: Address| {
+            let mut output = FoundryOutput::rand(&protocol_params);
+            output.immutable_alias_address_unlock_condition.address = alias;
+            ledger_output_at(Output::Foundry(output), ctx.at)
+        };
+        let spent = |output| spent_at(output, ctx.at);
+
+        let (first, second) = (Address::rand_alias(), Address::rand_alias());
+        let mut ledger_state = (0..6).map(|_| foundry(first)).collect::<Vec<_>>();
+        ledger_state.push(foundry(second));
+        ledger_state.push(LedgerOutput {
+            output: rand_output_with_amount(100.into()),
+            ..foundry(second)
+        });
+
+        let mut foundries = FoundryPerAliasAnalytics::init(&ledger_state);
+        assert_eq!(
+            foundries.take_measurement(&ctx),
+            FoundryPerAliasMeasurement {
+                single: 1,
+                medium: 1,
+                ..Default::default()
+            }
+        );
+
+        // Updating a foundry does not change the distribution, while destroying one does.
+        foundries.handle_transaction(
+            &[spent(ledger_state[0].clone()), spent(ledger_state[1].clone())],
+            &[foundry(first)],
+            &ctx,
+        );
+        foundries.handle_transaction(&[spent(ledger_state[6].clone())], &[], &ctx);
+        assert_eq!(
+            foundries.take_measurement(&ctx),
+            FoundryPerAliasMeasurement {
+                small: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_output_value_histogram() {
+        let ctx = context_at(1, 10001);
+        let ledger_output = |amount: u64| ledger_output_at(rand_output_with_amount(amount.into()), ctx.at);
+        let spent = |output| spent_at(output, ctx.at);
+        let histogram = |counts: &[(usize, usize)]| {
+            let mut output_counts = vec![0; ctx.params.token_supply.ilog10() as usize + 1];
+            for &(index, count) in counts {
+                output_counts[index] = count;
+            }
+            OutputValueHistogramMeasurement { output_counts }
+        };
+
+        let ledger_state = vec![
+            ledger_output(9),
+            ledger_output(10),
+            ledger_output(99),
+            ledger_output(1_000_000),
+        ];
+        let mut outputs = OutputValueHistogramAnalytics::init(&ledger_state);
+        assert_eq!(outputs.take_measurement(&ctx), histogram(&[(0, 1), (1, 2), (6, 1)]));
+
+        outputs.handle_transaction(
+            &[spent(ledger_state[1].clone()), spent(ledger_state[3].clone())],
+            &[ledger_output(100), ledger_output(999_999)],
+            &ctx,
+        );
+        assert_eq!(
+            outputs.take_measurement(&ctx),
+            histogram(&[(0, 1), (1, 1), (2, 1), (5, 1)])
+        );
+    }
+
+    #[test]
+    fn test_coin_days_destroyed() {
+        // Two and a half days after the outputs were booked.
+        let ctx = context_at(2, 2 * 86400 + 43200);
+        let spent = |amount: u64, booked_timestamp: u32| {
+            let booked = MilestoneIndexTimestamp {
+                milestone_index: 1.into(),
+                milestone_timestamp: booked_timestamp.into(),
+            };
+            spent_at(ledger_output_at(rand_output_with_amount(amount.into()), booked), ctx.at)
+        };
+
+        let mut coin_days = CoinDaysDestroyedMeasurement::default();
+        // 100 and 1000 tokens held for 2.5 days destroy 250 and 2500 coin-days. An output that is spent in the
+        // milestone it was booked in destroys none.
+        coin_days.handle_transaction(&[spent(100, 0), spent(1000, 0)], &[], &ctx);
+        coin_days.handle_transaction(&[spent(5000, 2 * 86400 + 43200)], &[], &ctx);
+        let measurement = coin_days.take_measurement(&ctx);
+        assert_eq!(measurement.spent_count, 3);
+        assert_eq!(measurement.coin_seconds_destroyed, 237_600_000);
+        assert_eq!(measurement.coin_days_destroyed(), 2750);
+
+        // The measurement is reset for the next milestone.
+        assert_eq!(coin_days.take_measurement(&ctx), CoinDaysDestroyedMeasurement::default());
+    }
 }
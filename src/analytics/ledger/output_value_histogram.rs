@@ -0,0 +1,67 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// The number of buckets needed to cover every possible amount, since `u64::MAX` has 20 decimal digits.
+const BUCKET_COUNT: usize = u64::MAX.ilog10() as usize + 1;
+
+/// The distribution of the amounts of unspent outputs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct OutputValueHistogramMeasurement {
+    /// The number of unspent outputs per bucket, where the bucket at `index` holds the outputs with an amount in
+    /// `[10^index..10^(index+1))`.
+    pub(crate) output_counts: Vec<usize>,
+}
+
+/// Tracks the number of unspent outputs in each logarithmic range of amounts.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct OutputValueHistogramAnalytics {
+    output_counts: [usize; BUCKET_COUNT],
+}
+
+impl OutputValueHistogramAnalytics {
+    /// Initialize the analytics by reading the current ledger state.
+    pub(crate) fn init<'a>(unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>) -> Self {
+        let mut output_counts = [0; BUCKET_COUNT];
+        for output in unspent_outputs {
+            output_counts[bucket(output.amount())] += 1;
+        }
+        Self { output_counts }
+    }
+}
+
+/// The index of the bucket that an amount falls into. Outputs can not hold zero tokens, but they would be counted in
+/// the first bucket.
+fn bucket(amount: TokenAmount) -> usize {
+    amount.0.checked_ilog10().unwrap_or_default() as usize
+}
+
+impl Analytics for OutputValueHistogramAnalytics {
+    type Measurement = OutputValueHistogramMeasurement;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        for output in consumed {
+            let count = &mut self.output_counts[bucket(output.amount())];
+            *count = count.saturating_sub(1);
+        }
+
+        for output in created {
+            self.output_counts[bucket(output.amount())] += 1;
+        }
+    }
+
+    fn take_measurement(&mut self, ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        // No output can hold more than the token supply, so the buckets above it are left out.
+        let bucket_max = ctx.protocol_params().token_supply.ilog10() as usize + 1;
+        OutputValueHistogramMeasurement {
+            output_counts: self.output_counts[..bucket_max].to_vec(),
+        }
+    }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "output_count": self.output_counts.iter().sum::<usize>(),
+        }))
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// The share of the unspent outputs that was consumed in a milestone.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct OutputVelocityMeasurement {
+    /// The number of outputs consumed in the milestone.
+    pub(crate) consumed_count: usize,
+    /// The number of unspent outputs at the start of the milestone.
+    pub(crate) unspent_count: usize,
+}
+
+impl OutputVelocityMeasurement {
+    /// The ratio of consumed to unspent outputs, or zero if the ledger was empty.
+    pub(crate) fn velocity(&self) -> f64 {
+        if self.unspent_count == 0 {
+            0.0
+        } else {
+            self.consumed_count as f64 / self.unspent_count as f64
+        }
+    }
+}
+
+/// Tracks the number of unspent outputs, so that the outputs consumed in a milestone can be related to it.
+#[derive(Debug, Default)]
+pub(crate) struct OutputVelocityAnalytics {
+    /// The number of unspent outputs at the start of the current milestone.
+    unspent_at_start: usize,
+    created_count: usize,
+    consumed_count: usize,
+}
+
+impl OutputVelocityAnalytics {
+    /// Initialize the analytics by reading the current ledger state.
+    pub(crate) fn init<'a>(unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>) -> Self {
+        Self {
+            unspent_at_start: unspent_outputs.into_iter().count(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Analytics for OutputVelocityAnalytics {
+    type Measurement = OutputVelocityMeasurement;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        self.consumed_count += consumed.len();
+        self.created_count += created.len();
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        let measurement = OutputVelocityMeasurement {
+            consumed_count: self.consumed_count,
+            unspent_count: self.unspent_at_start,
+        };
+        self.unspent_at_start = (self.unspent_at_start + self.created_count).saturating_sub(self.consumed_count);
+        self.created_count = 0;
+        self.consumed_count = 0;
+        measurement
+    }
+}
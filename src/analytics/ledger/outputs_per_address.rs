@@ -0,0 +1,57 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{address_balance::AddressMap, *};
+
+/// The fragmentation of the ledger into unspent outputs per address.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct OutputsPerAddressMeasurement {
+    /// The number of addresses that own at least one unspent output.
+    pub(crate) address_count: usize,
+    /// The number of unspent outputs owned by an address.
+    pub(crate) output_count: usize,
+    /// The largest number of unspent outputs owned by a single address.
+    pub(crate) max_outputs_per_address: usize,
+}
+
+impl OutputsPerAddressMeasurement {
+    /// The average number of unspent outputs owned by an address.
+    pub(crate) fn avg_outputs_per_address(&self) -> f64 {
+        if self.address_count == 0 {
+            0.0
+        } else {
+            self.output_count as f64 / self.address_count as f64
+        }
+    }
+}
+
+/// Tracks the number of unspent outputs owned by each address.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct OutputsPerAddressAnalytics {
+    output_counts: AddressMap<usize>,
+}
+
+impl OutputsPerAddressAnalytics {
+    /// Initialize the analytics by reading the current ledger state.
+    pub(crate) fn init<'a>(unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>) -> Self {
+        Self {
+            output_counts: AddressMap::init(unspent_outputs),
+        }
+    }
+}
+
+impl Analytics for OutputsPerAddressAnalytics {
+    type Measurement = OutputsPerAddressMeasurement;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        self.output_counts.handle_transaction(consumed, created);
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        OutputsPerAddressMeasurement {
+            address_count: self.output_counts.len(),
+            output_count: self.output_counts.values().sum(),
+            max_outputs_per_address: self.output_counts.values().copied().max().unwrap_or_default(),
+        }
+    }
+}
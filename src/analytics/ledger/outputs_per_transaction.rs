@@ -0,0 +1,35 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// The number of outputs created by the transactions in a milestone.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct OutputsPerTransactionMeasurement {
+    pub(crate) transaction_count: usize,
+    pub(crate) output_count: usize,
+}
+
+impl OutputsPerTransactionMeasurement {
+    /// The average number of outputs created by a transaction, or zero if there were no transactions.
+    pub(crate) fn average_output_count(&self) -> f64 {
+        if self.transaction_count == 0 {
+            0.0
+        } else {
+            self.output_count as f64 / self.transaction_count as f64
+        }
+    }
+}
+
+impl Analytics for OutputsPerTransactionMeasurement {
+    type Measurement = Self;
+
+    fn handle_transaction(&mut self, _consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        self.transaction_count += 1;
+        self.output_count += created.len();
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        std::mem::take(self)
+    }
+}
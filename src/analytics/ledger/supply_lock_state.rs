@@ -0,0 +1,124 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::*;
+use crate::model::{tangle::MilestoneTimestamp, utxo::OutputId};
+
+/// The total supply split by whether it can currently be spent.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SupplyLockStateMeasurement {
+    /// The amount held in outputs that can be unlocked by their owner right now.
+    pub(crate) spendable: TokenAmount,
+    /// The amount held in outputs whose timelock has not yet expired.
+    pub(crate) time_locked: TokenAmount,
+    /// The amount held in outputs with a storage deposit return or expiration unlock condition.
+    pub(crate) conditionally_held: TokenAmount,
+}
+
+/// The unlock conditions of an output that restrict who can spend it, and when.
+#[derive(Copy, Clone, Debug)]
+struct Conditions {
+    amount: TokenAmount,
+    timelock: Option<MilestoneTimestamp>,
+    conditional: bool,
+}
+
+impl Conditions {
+    fn of(output: &LedgerOutput) -> Option<Self> {
+        let (timelock, expiration, storage_deposit_return) = match &output.output {
+            Output::Basic(basic) => (
+                basic.timelock_unlock_condition,
+                basic.expiration_unlock_condition,
+                basic.storage_deposit_return_unlock_condition,
+            ),
+            Output::Nft(nft) => (
+                nft.timelock_unlock_condition,
+                nft.expiration_unlock_condition,
+                nft.storage_deposit_return_unlock_condition,
+            ),
+            _ => return None,
+        };
+        let conditions = Self {
+            amount: output.amount(),
+            timelock: timelock.map(|uc| uc.timestamp),
+            conditional: expiration.is_some() || storage_deposit_return.is_some(),
+        };
+        (conditions.timelock.is_some() || conditions.conditional).then(|| conditions)
+    }
+}
+
+/// Tracks the outputs whose unlock conditions restrict spending, so that the supply can be split at every milestone
+/// using its timestamp.
+#[derive(Debug)]
+pub(crate) struct SupplyLockStateAnalytics {
+    total: TokenAmount,
+    restricted: HashMap<OutputId, Conditions>,
+}
+
+impl SupplyLockStateAnalytics {
+    /// Initialize the analytics by reading the current ledger state.
+    pub(crate) fn init<'a>(unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>) -> Self {
+        let mut res = Self {
+            total: TokenAmount(0),
+            restricted: HashMap::new(),
+        };
+        for output in unspent_outputs {
+            res.add_output(output);
+        }
+        res
+    }
+
+    fn add_output(&mut self, output: &LedgerOutput) {
+        self.total += output.amount();
+        if let Some(conditions) = Conditions::of(output) {
+            self.restricted.insert(output.output_id, conditions);
+        }
+    }
+
+    fn remove_output(&mut self, output: &LedgerOutput) {
+        self.total = TokenAmount(self.total.0.wrapping_sub(output.amount().0));
+        self.restricted.remove(&output.output_id);
+    }
+}
+
+impl Analytics for SupplyLockStateAnalytics {
+    type Measurement = SupplyLockStateMeasurement;
+
+    fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], _ctx: &dyn AnalyticsContext) {
+        for output in consumed {
+            self.remove_output(&output.output);
+        }
+        for output in created {
+            self.add_output(output);
+        }
+    }
+
+    fn take_measurement(&mut self, ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        let now = ctx.at().milestone_timestamp;
+        let mut measurement = SupplyLockStateMeasurement::default();
+        for conditions in self.restricted.values() {
+            // A timelock takes precedence, since nobody can spend the output before it expires.
+            if conditions.timelock.map_or(false, |timelock| timelock > now) {
+                measurement.time_locked += conditions.amount;
+            } else if conditions.conditional {
+                measurement.conditionally_held += conditions.amount;
+            }
+        }
+        measurement.spendable = TokenAmount(
+            self.total
+                .0
+                .wrapping_sub(measurement.time_locked.0)
+                .wrapping_sub(measurement.conditionally_held.0),
+        );
+        measurement
+    }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "total_amount": self.total,
+            "restricted_output_count": self.restricted.len(),
+        }))
+    }
+}
@@ -41,4 +41,8 @@ impl Analytics for UnclaimedTokenMeasurement {
     fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
         *self
     }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(self).ok()
+    }
 }
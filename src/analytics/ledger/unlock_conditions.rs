@@ -83,4 +83,8 @@ impl Analytics for UnlockConditionMeasurement {
     fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
         *self
     }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(self).ok()
+    }
 }
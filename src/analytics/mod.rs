@@ -3,21 +3,32 @@
 
 //! Various analytics that give insight into the usage of the tangle.
 
+use std::{num::NonZeroU32, ops::Range};
+
 use futures::TryStreamExt;
 use thiserror::Error;
 
+pub use self::{
+    influx::{FieldSchema, FieldType, MeasurementSchema, PrepareQuery, QuerySettings},
+    sink::{AnalyticsSink, LineProtocolFile, TeeSink},
+};
 use self::{
-    influx::PrepareQuery,
     ledger::{
-        AddressActivityAnalytics, AddressActivityMeasurement, AddressBalancesAnalytics, BaseTokenActivityMeasurement,
-        LedgerOutputMeasurement, LedgerSizeAnalytics, OutputActivityMeasurement, TransactionSizeMeasurement,
-        UnclaimedTokenMeasurement, UnlockConditionMeasurement,
+        ActiveAddressesByKindAnalytics, AddressActivityAnalytics, AddressActivityMeasurement, AddressBalancesAnalytics,
+        BaseTokenActivityMeasurement, CoinDaysDestroyedMeasurement, DepositViolationAnalytics, FeatureUsageMeasurement,
+        FoundryPerAliasAnalytics, HoldingTimeMeasurement, LedgerOutputMeasurement, LedgerSizeAnalytics,
+        MedianTransactionValueAnalytics, OutputActivityMeasurement, OutputValueHistogramAnalytics,
+        OutputVelocityAnalytics, OutputsPerAddressAnalytics, OutputsPerTransactionMeasurement,
+        SupplyLockStateAnalytics, TransactionSizeMeasurement, UnclaimedTokenMeasurement, UnlockConditionMeasurement,
+    },
+    tangle::{
+        BlockActivityMeasurement, MilestoneSizeMeasurement, MilestoneSizeTotalsAnalytics, ProtocolParamsAnalytics,
     },
-    tangle::{BlockActivityMeasurement, MilestoneSizeMeasurement, ProtocolParamsAnalytics},
 };
 use crate::{
     db::{
-        influxdb::{config::IntervalAnalyticsChoice, AnalyticsChoice, InfluxDb},
+        influxdb::{config::IntervalAnalyticsChoice, AnalyticsChoice, InfluxDb, InfluxDbConfig},
+        mongodb::collections::MilestoneCollection,
         MongoDb,
     },
     model::{
@@ -33,6 +44,7 @@ use crate::{
 
 mod influx;
 mod ledger;
+mod sink;
 mod tangle;
 
 /// Provides an API to access basic information used for analytics
@@ -69,6 +81,10 @@ pub trait Analytics {
     fn handle_block(&mut self, _block_data: &BlockData, _ctx: &dyn AnalyticsContext) {}
     /// Take the measurement from the analytic. This should prepare the analytic for the next milestone.
     fn take_measurement(&mut self, ctx: &dyn AnalyticsContext) -> Self::Measurement;
+    /// Summarize the state that the analytic carries across milestones, for diagnostics.
+    fn state(&self) -> Option<serde_json::Value> {
+        None
+    }
 }
 
 // This trait allows using the above implementation dynamically
@@ -76,6 +92,7 @@ trait DynAnalytics: Send {
     fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], ctx: &dyn AnalyticsContext);
     fn handle_block(&mut self, block_data: &BlockData, ctx: &dyn AnalyticsContext);
     fn take_measurement(&mut self, ctx: &dyn AnalyticsContext) -> Box<dyn PrepareQuery>;
+    fn state(&self) -> Option<serde_json::Value>;
 }
 
 impl<T: Analytics + Send> DynAnalytics for T
@@ -96,6 +113,10 @@ where
             inner: Analytics::take_measurement(self, ctx),
         }) as _
     }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        Analytics::state(self)
+    }
 }
 
 #[async_trait::async_trait]
@@ -103,8 +124,7 @@ trait IntervalAnalytics {
     type Measurement;
     async fn handle_date_range(
         &mut self,
-        start_date: time::Date,
-        interval: AnalyticsInterval,
+        range: Range<time::OffsetDateTime>,
         db: &MongoDb,
     ) -> eyre::Result<Self::Measurement>;
 }
@@ -116,6 +136,7 @@ trait DynIntervalAnalytics: Send {
         &mut self,
         start_date: time::Date,
         interval: AnalyticsInterval,
+        offset: time::UtcOffset,
         db: &MongoDb,
     ) -> eyre::Result<Box<dyn PrepareQuery>>;
 }
@@ -129,46 +150,147 @@ where
         &mut self,
         start_date: time::Date,
         interval: AnalyticsInterval,
+        offset: time::UtcOffset,
         db: &MongoDb,
     ) -> eyre::Result<Box<dyn PrepareQuery>> {
-        IntervalAnalytics::handle_date_range(self, start_date, interval, db)
-            .await
-            .map(|r| {
-                Box::new(PerInterval {
-                    start_date,
-                    interval,
-                    inner: r,
-                }) as _
-            })
+        let range = interval.time_range(&start_date, offset);
+        let start = range.start;
+        IntervalAnalytics::handle_date_range(self, range, db).await.map(|r| {
+            Box::new(PerInterval {
+                start,
+                interval,
+                inner: r,
+            }) as _
+        })
     }
 }
 
 #[allow(missing_docs)]
-pub struct Analytic(Box<dyn DynAnalytics>);
+pub struct Analytic {
+    choice: AnalyticsChoice,
+    inner: Box<dyn DynAnalytics>,
+    /// If set, the analytic only produces a measurement for milestones whose index is a multiple of this value.
+    sample_every: Option<NonZeroU32>,
+    /// Whether the analytic carries state across milestones, in which case it must handle every milestone even if it
+    /// does not produce a measurement for it.
+    stateful: bool,
+}
 
 impl Analytic {
     /// Init an analytic from a choice and ledger state.
-    pub fn init<'a>(
+    ///
+    /// Analytics that accumulate across milestones are seeded from the measurement written for the milestone before
+    /// `index`, or start from zero if there is none.
+    pub async fn init<'a>(
         choice: &AnalyticsChoice,
         protocol_params: &ProtocolParameters,
         unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>,
-    ) -> Self {
-        Self(match choice {
+        influxdb: &InfluxDb,
+        index: MilestoneIndex,
+    ) -> eyre::Result<Self> {
+        let milestone_size_totals = if *choice == AnalyticsChoice::MilestoneSizeTotals {
+            influxdb.get_milestone_size_totals(index - 1).await?.unwrap_or_default()
+        } else {
+            Default::default()
+        };
+        let inner = Self::new_inner(choice, protocol_params, unspent_outputs, milestone_size_totals);
+        Ok(Self {
+            choice: *choice,
+            inner,
+            sample_every: influxdb.config().analytics_sampling.get(choice).copied(),
+            stateful: matches!(
+                choice,
+                AnalyticsChoice::AddressBalance
+                    | AnalyticsChoice::DepositViolations
+                    | AnalyticsChoice::FoundryPerAlias
+                    | AnalyticsChoice::LedgerOutputs
+                    | AnalyticsChoice::LedgerSize
+                    | AnalyticsChoice::MilestoneSizeTotals
+                    | AnalyticsChoice::OutputValueHistogram
+                    | AnalyticsChoice::OutputVelocity
+                    | AnalyticsChoice::OutputsPerAddress
+                    | AnalyticsChoice::ProtocolParameters
+                    | AnalyticsChoice::SupplyLockState
+                    | AnalyticsChoice::UnclaimedTokens
+                    | AnalyticsChoice::UnlockConditions
+            ),
+        })
+    }
+
+    fn new_inner<'a>(
+        choice: &AnalyticsChoice,
+        protocol_params: &ProtocolParameters,
+        unspent_outputs: impl IntoIterator<Item = &'a LedgerOutput>,
+        milestone_size_totals: MilestoneSizeMeasurement,
+    ) -> Box<dyn DynAnalytics> {
+        match choice {
             AnalyticsChoice::AddressBalance => Box::new(AddressBalancesAnalytics::init(unspent_outputs)) as _,
             AnalyticsChoice::BaseTokenActivity => Box::<BaseTokenActivityMeasurement>::default() as _,
             AnalyticsChoice::BlockActivity => Box::<BlockActivityMeasurement>::default() as _,
             AnalyticsChoice::ActiveAddresses => Box::<AddressActivityAnalytics>::default() as _,
+            AnalyticsChoice::ActiveAddressesByKind => Box::<ActiveAddressesByKindAnalytics>::default() as _,
+            AnalyticsChoice::CoinDaysDestroyed => Box::<CoinDaysDestroyedMeasurement>::default() as _,
+            AnalyticsChoice::DepositViolations => {
+                Box::new(DepositViolationAnalytics::init(protocol_params, unspent_outputs)) as _
+            }
+            AnalyticsChoice::FeatureUsage => Box::<FeatureUsageMeasurement>::default() as _,
+            AnalyticsChoice::FoundryPerAlias => Box::new(FoundryPerAliasAnalytics::init(unspent_outputs)) as _,
+            AnalyticsChoice::HoldingTime => Box::<HoldingTimeMeasurement>::default() as _,
             AnalyticsChoice::LedgerOutputs => Box::new(LedgerOutputMeasurement::init(unspent_outputs)) as _,
             AnalyticsChoice::LedgerSize => {
                 Box::new(LedgerSizeAnalytics::init(protocol_params.clone(), unspent_outputs)) as _
             }
+            AnalyticsChoice::MedianTransactionValue => Box::<MedianTransactionValueAnalytics>::default() as _,
             AnalyticsChoice::MilestoneSize => Box::<MilestoneSizeMeasurement>::default() as _,
+            AnalyticsChoice::MilestoneSizeTotals => {
+                Box::new(MilestoneSizeTotalsAnalytics::init(milestone_size_totals)) as _
+            }
             AnalyticsChoice::OutputActivity => Box::<OutputActivityMeasurement>::default() as _,
+            AnalyticsChoice::OutputValueHistogram => {
+                Box::new(OutputValueHistogramAnalytics::init(unspent_outputs)) as _
+            }
+            AnalyticsChoice::OutputVelocity => Box::new(OutputVelocityAnalytics::init(unspent_outputs)) as _,
+            AnalyticsChoice::OutputsPerAddress => Box::new(OutputsPerAddressAnalytics::init(unspent_outputs)) as _,
+            AnalyticsChoice::OutputsPerTransaction => Box::<OutputsPerTransactionMeasurement>::default() as _,
             AnalyticsChoice::ProtocolParameters => Box::<ProtocolParamsAnalytics>::default() as _,
+            AnalyticsChoice::SupplyLockState => Box::new(SupplyLockStateAnalytics::init(unspent_outputs)) as _,
             AnalyticsChoice::TransactionSizeDistribution => Box::<TransactionSizeMeasurement>::default() as _,
             AnalyticsChoice::UnclaimedTokens => Box::new(UnclaimedTokenMeasurement::init(unspent_outputs)) as _,
             AnalyticsChoice::UnlockConditions => Box::new(UnlockConditionMeasurement::init(unspent_outputs)) as _,
-        })
+        }
+    }
+
+    /// Describes the measurement written by an analytic. The schema is derived from the type of its measurement, so it
+    /// includes every field that can be written, independent of the ledger. Amount fields are typed according to the
+    /// configured [`AmountFormat`](crate::db::influxdb::AmountFormat), and the number of token distribution fields
+    /// depends on the token supply of the given protocol parameters.
+    pub fn measurement_schema(
+        choice: &AnalyticsChoice,
+        protocol_params: &ProtocolParameters,
+        config: &InfluxDbConfig,
+    ) -> MeasurementSchema {
+        influx::measurement_schema(choice, protocol_params, influx::AmountFields::from_config(config))
+    }
+
+    /// The choice that the analytic was created from.
+    pub fn choice(&self) -> AnalyticsChoice {
+        self.choice
+    }
+
+    /// Summarizes the state that the analytic carries across milestones, if any.
+    pub fn state(&self) -> Option<serde_json::Value> {
+        self.inner.state()
+    }
+
+    /// Whether the analytic produces a measurement for the given milestone.
+    fn is_sampled(&self, ctx: &dyn AnalyticsContext) -> bool {
+        self.sample_every
+            .map_or(true, |every| ctx.at().milestone_index.0 % every.get() == 0)
+    }
+
+    /// Whether the analytic needs to handle the blocks and transactions of the given milestone.
+    fn should_handle(&self, ctx: &dyn AnalyticsContext) -> bool {
+        self.stateful || self.is_sampled(ctx)
     }
 }
 
@@ -176,21 +298,23 @@ impl<T: AsMut<[Analytic]>> Analytics for T {
     type Measurement = Vec<Box<dyn PrepareQuery>>;
 
     fn handle_block(&mut self, block_data: &BlockData, ctx: &dyn AnalyticsContext) {
-        for analytic in self.as_mut().iter_mut() {
-            analytic.0.handle_block(block_data, ctx);
+        for analytic in self.as_mut().iter_mut().filter(|analytic| analytic.should_handle(ctx)) {
+            analytic.inner.handle_block(block_data, ctx);
         }
     }
 
     fn handle_transaction(&mut self, consumed: &[LedgerSpent], created: &[LedgerOutput], ctx: &dyn AnalyticsContext) {
-        for analytic in self.as_mut().iter_mut() {
-            analytic.0.handle_transaction(consumed, created, ctx);
+        for analytic in self.as_mut().iter_mut().filter(|analytic| analytic.should_handle(ctx)) {
+            analytic.inner.handle_transaction(consumed, created, ctx);
         }
     }
 
     fn take_measurement(&mut self, ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        // Sampled analytics emit nothing for skipped milestones, so their series are sparser.
         self.as_mut()
             .iter_mut()
-            .map(|analytic| analytic.0.take_measurement(ctx))
+            .filter(|analytic| analytic.is_sampled(ctx))
+            .map(|analytic| analytic.inner.take_measurement(ctx))
             .collect()
     }
 }
@@ -220,14 +344,26 @@ pub enum AnalyticsError {
         output_id: String,
         milestone_index: MilestoneIndex,
     },
+    #[error("no milestones are stored to compute {interval} analytics from")]
+    NoStoredMilestones { interval: AnalyticsInterval },
+    #[error(
+        "{interval} analytics for {start}..{end} lie outside of the stored milestones, which range from {first} to {last}"
+    )]
+    IntervalOutOfRange {
+        interval: AnalyticsInterval,
+        start: time::Date,
+        end: time::Date,
+        first: time::OffsetDateTime,
+        last: time::OffsetDateTime,
+    },
 }
 
 impl<'a, I: InputSource> Milestone<'a, I> {
-    /// Update a list of analytics with this milestone
-    pub async fn update_analytics<A: Analytics + Send>(
+    /// Update a list of analytics with this milestone and write their measurements to the sink.
+    pub async fn update_analytics<A: Analytics + Send, S: AnalyticsSink + ?Sized>(
         &self,
         analytics: &mut A,
-        influxdb: &InfluxDb,
+        sink: &S,
     ) -> eyre::Result<()>
     where
         PerMilestone<A::Measurement>: 'static + PrepareQuery,
@@ -238,8 +374,7 @@ impl<'a, I: InputSource> Milestone<'a, I> {
             self.handle_block(analytics, &block_data)?;
         }
 
-        influxdb
-            .insert_measurement((analytics as &mut dyn DynAnalytics).take_measurement(self))
+        sink.write_measurement(&(analytics as &mut dyn DynAnalytics).take_measurement(self))
             .await?;
 
         Ok(())
@@ -290,30 +425,82 @@ impl<'a, I: InputSource> Milestone<'a, I> {
 }
 
 impl MongoDb {
-    /// Update a list of interval analytics with this date.
-    pub async fn update_interval_analytics(
+    /// Update a list of interval analytics with this date, which begins at midnight in the given offset from UTC, and
+    /// write their measurements to the sink.
+    ///
+    /// Intervals that do not overlap the range of stored milestones are rejected, since they would silently produce
+    /// empty measurements. Intervals that only partially overlap it are computed from the milestones that are stored.
+    pub async fn update_interval_analytics<S: AnalyticsSink + ?Sized>(
         &self,
         analytics: &mut [IntervalAnalytic],
-        influxdb: &InfluxDb,
+        sink: &S,
         start: time::Date,
         interval: AnalyticsInterval,
+        offset: time::UtcOffset,
     ) -> eyre::Result<()> {
+        let milestones = self.collection::<MilestoneCollection>();
+        let (first, last) = match (
+            milestones.get_oldest_milestone().await?,
+            milestones.get_newest_milestone().await?,
+        ) {
+            (Some(first), Some(last)) => (
+                time::OffsetDateTime::try_from(first.milestone_timestamp)?,
+                time::OffsetDateTime::try_from(last.milestone_timestamp)?,
+            ),
+            _ => return Err(AnalyticsError::NoStoredMilestones { interval }.into()),
+        };
+        let end = interval.end_date(&start);
+        let range = interval.time_range(&start, offset);
+        if range.end <= first || range.start > last {
+            return Err(AnalyticsError::IntervalOutOfRange {
+                interval,
+                start,
+                end,
+                first,
+                last,
+            }
+            .into());
+        }
+
         for analytic in analytics {
-            influxdb
-                .insert_measurement(analytic.0.handle_date_range(start, interval, self).await?)
+            sink.write_measurement(analytic.0.handle_date_range(start, interval, offset, self).await?.as_ref())
                 .await?;
         }
         Ok(())
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[allow(missing_docs)]
 pub enum AnalyticsInterval {
     Day,
     Week,
     Month,
     Year,
+    /// A custom interval spanning the given number of days.
+    Days(u16),
+}
+
+#[derive(Debug, Error)]
+#[error("invalid analytics interval. Expected `day`, `week`, `month`, `year` or a day count like `14d`, found `{0}`")]
+#[allow(missing_docs)]
+pub struct ParseAnalyticsIntervalError(String);
+
+impl std::str::FromStr for AnalyticsInterval {
+    type Err = ParseAnalyticsIntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "day" => AnalyticsInterval::Day,
+            "week" => AnalyticsInterval::Week,
+            "month" => AnalyticsInterval::Month,
+            "year" => AnalyticsInterval::Year,
+            _ => match s.strip_suffix('d').and_then(|days| days.parse::<u16>().ok()) {
+                Some(days) if days > 0 => AnalyticsInterval::Days(days),
+                _ => Err(ParseAnalyticsIntervalError(s.to_string()))?,
+            },
+        })
+    }
 }
 
 impl AnalyticsInterval {
@@ -326,6 +513,7 @@ impl AnalyticsInterval {
                 time::Duration::days(time::util::days_in_year_month(start_date.year(), start_date.month()) as _)
             }
             AnalyticsInterval::Year => time::Duration::days(time::util::days_in_year(start_date.year()) as _),
+            AnalyticsInterval::Days(days) => time::Duration::days(*days as _),
         }
     }
 
@@ -333,20 +521,23 @@ impl AnalyticsInterval {
     pub fn end_date(&self, start_date: &time::Date) -> time::Date {
         *start_date + self.to_duration(start_date)
     }
+
+    /// Get the time range covered by the interval beginning on the start date, where dates begin at midnight in the
+    /// given offset from UTC. Since the offset is fixed, daylight saving time is not accounted for.
+    pub fn time_range(&self, start_date: &time::Date, offset: time::UtcOffset) -> Range<time::OffsetDateTime> {
+        start_date.midnight().assume_offset(offset)..self.end_date(start_date).midnight().assume_offset(offset)
+    }
 }
 
 impl std::fmt::Display for AnalyticsInterval {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                AnalyticsInterval::Day => "daily",
-                AnalyticsInterval::Week => "weekly",
-                AnalyticsInterval::Month => "monthly",
-                AnalyticsInterval::Year => "yearly",
-            }
-        )
+        match self {
+            AnalyticsInterval::Day => write!(f, "daily"),
+            AnalyticsInterval::Week => write!(f, "weekly"),
+            AnalyticsInterval::Month => write!(f, "monthly"),
+            AnalyticsInterval::Year => write!(f, "yearly"),
+            AnalyticsInterval::Days(days) => write!(f, "{days}d"),
+        }
     }
 }
 
@@ -360,7 +551,7 @@ pub struct PerMilestone<M> {
 #[derive(Clone, Debug)]
 #[allow(missing_docs)]
 struct PerInterval<M> {
-    start_date: time::Date,
+    start: time::OffsetDateTime,
     interval: AnalyticsInterval,
     inner: M,
 }
@@ -383,7 +574,7 @@ mod test {
             BaseTokenActivityMeasurement, LedgerSizeMeasurement, OutputActivityMeasurement, TransactionSizeMeasurement,
         },
         tangle::{BlockActivityMeasurement, MilestoneSizeMeasurement},
-        Analytics, AnalyticsContext,
+        Analytics, AnalyticsContext, AnalyticsInterval,
     };
     use crate::{
         analytics::ledger::{
@@ -528,6 +719,39 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_analytics_interval() {
+        let start_date = time::macros::date!(2023 - 02 - 01);
+
+        let interval: AnalyticsInterval = "month".parse().unwrap();
+        assert_eq!(interval.end_date(&start_date), time::macros::date!(2023 - 03 - 01));
+
+        let interval: AnalyticsInterval = "14d".parse().unwrap();
+        assert_eq!(interval, AnalyticsInterval::Days(14));
+        assert_eq!(interval.to_string(), "14d");
+        assert_eq!(interval.end_date(&start_date), time::macros::date!(2023 - 02 - 15));
+
+        assert!("0d".parse::<AnalyticsInterval>().is_err());
+        assert!("fortnight".parse::<AnalyticsInterval>().is_err());
+    }
+
+    #[test]
+    fn test_analytics_interval_time_range() {
+        let start_date = time::macros::date!(2023 - 02 - 01);
+
+        let range = AnalyticsInterval::Day.time_range(&start_date, time::UtcOffset::UTC);
+        assert_eq!(
+            range,
+            time::macros::datetime!(2023-02-01 0:00 UTC)..time::macros::datetime!(2023-02-02 0:00 UTC)
+        );
+
+        let range = AnalyticsInterval::Day.time_range(&start_date, time::macros::offset!(+2));
+        assert_eq!(
+            range,
+            time::macros::datetime!(2023-01-31 22:00 UTC)..time::macros::datetime!(2023-02-01 22:00 UTC)
+        );
+    }
+
     #[tokio::test]
     async fn test_in_memory_analytics() {
         let analytics_map = gather_in_memory_analytics().await.unwrap();
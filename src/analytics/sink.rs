@@ -0,0 +1,154 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Destinations that analytics measurements are written to.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use influxdb::Query;
+
+use super::influx::{PrepareQuery, QuerySettings};
+use crate::db::influxdb::{AnalyticsSinkConfig, InfluxDb};
+
+/// A destination that analytics measurements are written to.
+#[async_trait::async_trait]
+pub trait AnalyticsSink: Send + Sync {
+    /// Writes a measurement to the sink.
+    async fn write_measurement(&self, measurement: &dyn PrepareQuery) -> eyre::Result<()>;
+}
+
+#[async_trait::async_trait]
+impl AnalyticsSink for InfluxDb {
+    async fn write_measurement(&self, measurement: &dyn PrepareQuery) -> eyre::Result<()> {
+        Ok(self.insert_measurement(measurement).await?)
+    }
+}
+
+/// Appends measurements to a file in the InfluxDb line protocol, so that they can be archived or imported later.
+#[derive(Clone, Debug)]
+pub struct LineProtocolFile {
+    file: Arc<Mutex<File>>,
+    settings: QuerySettings,
+}
+
+impl LineProtocolFile {
+    /// Opens the file at the given path for appending, creating it if it does not exist.
+    pub fn open(path: &str, settings: QuerySettings) -> std::io::Result<Self> {
+        Ok(Self {
+            file: Arc::new(Mutex::new(OpenOptions::new().create(true).append(true).open(path)?)),
+            settings,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AnalyticsSink for LineProtocolFile {
+    async fn write_measurement(&self, measurement: &dyn PrepareQuery) -> eyre::Result<()> {
+        let mut lines = String::new();
+        for (_, query) in measurement.prepare_query(self.settings) {
+            lines.push_str(&query.build()?.get());
+            lines.push('\n');
+        }
+        // Writing to the file blocks, so it is kept off of the async runtime.
+        let file = self.file.clone();
+        tokio::task::spawn_blocking(move || {
+            // Panic: The lock is never held across a panic.
+            file.lock().unwrap().write_all(lines.as_bytes())
+        })
+        .await??;
+        Ok(())
+    }
+}
+
+/// Forwards every measurement to multiple sinks.
+#[derive(Clone, Default)]
+pub struct TeeSink {
+    sinks: Vec<Arc<dyn AnalyticsSink>>,
+}
+
+impl TeeSink {
+    /// Creates the sinks that are configured for the given InfluxDb.
+    pub fn from_config(influx_db: &InfluxDb) -> std::io::Result<Self> {
+        let config = influx_db.config();
+        let mut tee = Self::default();
+        for sink in &config.analytics_sinks {
+            tee = match sink {
+                AnalyticsSinkConfig::InfluxDb => tee.with_sink(influx_db.clone()),
+                AnalyticsSinkConfig::File(path) => {
+                    tee.with_sink(LineProtocolFile::open(path, QuerySettings::from_config(config))?)
+                }
+            };
+        }
+        Ok(tee)
+    }
+
+    /// Adds a sink that measurements are forwarded to.
+    pub fn with_sink(mut self, sink: impl AnalyticsSink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+}
+
+impl std::fmt::Debug for TeeSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TeeSink").field("sinks", &self.sinks.len()).finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl AnalyticsSink for TeeSink {
+    /// Writes the measurement to every sink, even if writing to some of them fails. The first error is returned.
+    async fn write_measurement(&self, measurement: &dyn PrepareQuery) -> eyre::Result<()> {
+        futures::future::join_all(self.sinks.iter().map(|sink| sink.write_measurement(measurement)))
+            .await
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use influxdb::{InfluxDbWriteable, WriteQuery};
+
+    use super::{AnalyticsSink, PrepareQuery, QuerySettings, TeeSink};
+    use crate::db::influxdb::{AnalyticsCategory, InfluxDbConfig};
+
+    struct TestMeasurement;
+
+    impl PrepareQuery for TestMeasurement {
+        fn prepare_query(&self, _settings: QuerySettings) -> Vec<(AnalyticsCategory, WriteQuery)> {
+            vec![(
+                AnalyticsCategory::Ledger,
+                influxdb::Timestamp::Seconds(10)
+                    .into_query("test")
+                    .add_field("count", 1u64),
+            )]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tee_sink() {
+        let dir = std::env::temp_dir().join(format!("chronicle-tee-sink-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (first, second) = (dir.join("first.lp"), dir.join("second.lp"));
+        let settings = QuerySettings::from_config(&InfluxDbConfig::default());
+
+        let tee = TeeSink::default()
+            .with_sink(super::LineProtocolFile::open(first.to_str().unwrap(), settings).unwrap())
+            .with_sink(super::LineProtocolFile::open(second.to_str().unwrap(), settings).unwrap());
+        tee.write_measurement(&TestMeasurement).await.unwrap();
+        tee.write_measurement(&TestMeasurement).await.unwrap();
+
+        for path in [first, second] {
+            assert_eq!(
+                std::fs::read_to_string(path).unwrap(),
+                "test count=1i 10\ntest count=1i 10\n"
+            );
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
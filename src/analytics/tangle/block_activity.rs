@@ -1,11 +1,13 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use super::*;
-use crate::model::metadata::LedgerInclusionState;
+use crate::model::metadata::{ConflictReason, LedgerInclusionState};
 
 /// The type of payloads that occured within a single milestone.
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub(crate) struct BlockActivityMeasurement {
     pub(crate) milestone_count: usize,
     pub(crate) no_payload_count: usize,
@@ -15,6 +17,8 @@ pub(crate) struct BlockActivityMeasurement {
     pub(crate) confirmed_count: usize,
     pub(crate) conflicting_count: usize,
     pub(crate) no_transaction_count: usize,
+    /// The number of conflicting blocks per conflict reason.
+    pub(crate) conflict_reasons: HashMap<ConflictReason, usize>,
 }
 
 impl Analytics for BlockActivityMeasurement {
@@ -29,7 +33,10 @@ impl Analytics for BlockActivityMeasurement {
             None => self.no_payload_count += 1,
         }
         match metadata.inclusion_state {
-            LedgerInclusionState::Conflicting => self.conflicting_count += 1,
+            LedgerInclusionState::Conflicting => {
+                self.conflicting_count += 1;
+                *self.conflict_reasons.entry(metadata.conflict_reason).or_default() += 1;
+            }
             LedgerInclusionState::Included => self.confirmed_count += 1,
             LedgerInclusionState::NoTransaction => self.no_transaction_count += 1,
         }
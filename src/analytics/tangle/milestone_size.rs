@@ -1,16 +1,34 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::{Deserialize, Serialize};
+
 use super::*;
 
 /// Milestone size statistics.
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
 pub(crate) struct MilestoneSizeMeasurement {
     pub(crate) total_milestone_payload_bytes: usize,
     pub(crate) total_tagged_data_payload_bytes: usize,
     pub(crate) total_transaction_payload_bytes: usize,
     pub(crate) total_treasury_transaction_payload_bytes: usize,
     pub(crate) total_milestone_bytes: usize,
+    // Defaults allow reading totals that were written before these fields existed.
+    #[serde(default)]
+    pub(crate) block_count: usize,
+    #[serde(default)]
+    pub(crate) max_block_bytes: usize,
+}
+
+impl MilestoneSizeMeasurement {
+    /// The average size of a block in bytes.
+    pub(crate) fn avg_block_bytes(&self) -> f64 {
+        if self.block_count == 0 {
+            0.0
+        } else {
+            self.total_milestone_bytes as f64 / self.block_count as f64
+        }
+    }
 }
 
 impl Analytics for MilestoneSizeMeasurement {
@@ -18,6 +36,8 @@ impl Analytics for MilestoneSizeMeasurement {
 
     fn handle_block(&mut self, BlockData { block, raw, .. }: &BlockData, _ctx: &dyn AnalyticsContext) {
         self.total_milestone_bytes += raw.len();
+        self.block_count += 1;
+        self.max_block_bytes = self.max_block_bytes.max(raw.len());
         match block.payload {
             Some(Payload::Milestone(_)) => self.total_milestone_payload_bytes += raw.len(),
             Some(Payload::TaggedData(_)) => self.total_tagged_data_payload_bytes += raw.len(),
@@ -33,3 +53,36 @@ impl Analytics for MilestoneSizeMeasurement {
         std::mem::take(self)
     }
 }
+
+/// Running totals of the milestone size statistics across all processed milestones.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct MilestoneSizeTotalsMeasurement(pub(crate) MilestoneSizeMeasurement);
+
+/// Accumulates milestone sizes across milestones, starting from previously written totals.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct MilestoneSizeTotalsAnalytics {
+    totals: MilestoneSizeMeasurement,
+}
+
+impl MilestoneSizeTotalsAnalytics {
+    /// Initialize the analytics with the totals up to the previous milestone.
+    pub(crate) fn init(totals: MilestoneSizeMeasurement) -> Self {
+        Self { totals }
+    }
+}
+
+impl Analytics for MilestoneSizeTotalsAnalytics {
+    type Measurement = MilestoneSizeTotalsMeasurement;
+
+    fn handle_block(&mut self, block_data: &BlockData, ctx: &dyn AnalyticsContext) {
+        self.totals.handle_block(block_data, ctx);
+    }
+
+    fn take_measurement(&mut self, _ctx: &dyn AnalyticsContext) -> Self::Measurement {
+        MilestoneSizeTotalsMeasurement(self.totals)
+    }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(self.totals).ok()
+    }
+}
@@ -4,7 +4,8 @@
 //! Statistics about the tangle.
 
 pub(crate) use self::{
-    block_activity::BlockActivityMeasurement, milestone_size::MilestoneSizeMeasurement,
+    block_activity::BlockActivityMeasurement,
+    milestone_size::{MilestoneSizeMeasurement, MilestoneSizeTotalsAnalytics, MilestoneSizeTotalsMeasurement},
     protocol_params::ProtocolParamsAnalytics,
 };
 use crate::{
@@ -19,7 +20,7 @@ mod protocol_params;
 
 #[cfg(test)]
 mod test {
-    use super::BlockActivityMeasurement;
+    use super::{BlockActivityMeasurement, MilestoneSizeTotalsAnalytics};
     use crate::{
         analytics::{tangle::MilestoneSizeMeasurement, test::TestContext, Analytics},
         model::{
@@ -95,11 +96,61 @@ mod test {
         assert_eq!(block_activity_measurement.confirmed_count, 1);
         assert_eq!(block_activity_measurement.conflicting_count, 1);
         assert_eq!(block_activity_measurement.no_transaction_count, 3);
+        assert_eq!(
+            block_activity_measurement.conflict_reasons,
+            [(ConflictReason::InputUtxoNotFound, 1)].into()
+        );
 
         assert_eq!(milestone_size_measurement.total_treasury_transaction_payload_bytes, 100);
         assert_eq!(milestone_size_measurement.total_transaction_payload_bytes, 200);
         assert_eq!(milestone_size_measurement.total_milestone_payload_bytes, 300);
         assert_eq!(milestone_size_measurement.total_tagged_data_payload_bytes, 400);
         assert_eq!(milestone_size_measurement.total_milestone_bytes, 1500);
+        assert_eq!(milestone_size_measurement.block_count, 5);
+        assert_eq!(milestone_size_measurement.avg_block_bytes(), 300.0);
+        assert_eq!(milestone_size_measurement.max_block_bytes, 500);
+    }
+
+    #[test]
+    fn test_milestone_size_totals() {
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+
+        let block = Block::rand_tagged_data();
+        let block_data = BlockData {
+            block_id: BlockId::rand(),
+            metadata: BlockMetadata {
+                parents: block.parents.clone(),
+                is_solid: true,
+                should_promote: false,
+                should_reattach: false,
+                referenced_by_milestone_index: 1.into(),
+                milestone_index: 0.into(),
+                inclusion_state: LedgerInclusionState::NoTransaction,
+                conflict_reason: ConflictReason::None,
+                white_flag_index: 0,
+            },
+            block,
+            raw: iota_types::block::rand::bytes::rand_bytes(100),
+        };
+
+        let mut totals = MilestoneSizeTotalsAnalytics::init(MilestoneSizeMeasurement {
+            total_tagged_data_payload_bytes: 1000,
+            total_milestone_bytes: 5000,
+            ..Default::default()
+        });
+
+        for index in 1..=2 {
+            let ctx = TestContext {
+                at: MilestoneIndex(index).with_timestamp(12345.into()),
+                params: protocol_params.clone().into(),
+            };
+            totals.handle_block(&block_data, &ctx);
+            let measurement = totals.take_measurement(&ctx).0;
+            assert_eq!(measurement.total_tagged_data_payload_bytes, 1000 + 100 * index as usize);
+            assert_eq!(measurement.total_milestone_bytes, 5000 + 100 * index as usize);
+            assert_eq!(measurement.total_transaction_payload_bytes, 0);
+            assert_eq!(measurement.block_count, index as usize);
+            assert_eq!(measurement.max_block_bytes, 100);
+        }
     }
 }
@@ -18,4 +18,8 @@ impl Analytics for ProtocolParamsAnalytics {
             ctx.protocol_params().clone()
         })
     }
+
+    fn state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(&self.params).ok()
+    }
 }
@@ -0,0 +1,98 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use axum::{http::Request, middleware::Next, response::Response};
+use hyper::StatusCode;
+use tracing::{info, warn};
+
+use super::ApiConfigData;
+
+/// Decides which requests are written to the access log.
+#[derive(Clone, Debug, Default)]
+pub struct AccessLog {
+    sample_rate: u64,
+    log_errors: bool,
+    requests: Arc<AtomicU64>,
+}
+
+impl AccessLog {
+    /// Logs one in every `sample_rate` requests, where `0` disables sampling. If `log_errors` is set, every request
+    /// that fails is logged as well.
+    pub fn new(sample_rate: u64, log_errors: bool) -> Self {
+        Self {
+            sample_rate,
+            log_errors,
+            requests: Default::default(),
+        }
+    }
+
+    /// Whether any requests are logged at all.
+    pub fn is_enabled(&self) -> bool {
+        self.sample_rate > 0 || self.log_errors
+    }
+
+    /// Counts a finished request and decides whether it is logged.
+    fn should_log(&self, status: StatusCode) -> bool {
+        let sampled = self.sample_rate > 0 && self.requests.fetch_add(1, Ordering::Relaxed) % self.sample_rate == 0;
+        sampled || (self.log_errors && is_error(status))
+    }
+}
+
+fn is_error(status: StatusCode) -> bool {
+    status.is_client_error() || status.is_server_error()
+}
+
+/// Writes the method, path, status and latency of sampled and failed requests to the log.
+pub async fn access_log<B>(req: Request<B>, next: Next<B>) -> Response {
+    let access_log = match req.extensions().get::<ApiConfigData>() {
+        Some(config) if config.access_log.is_enabled() => config.access_log.clone(),
+        _ => return next.run(req).await,
+    };
+    let method = req.method().clone();
+    let path = req.uri().path().to_owned();
+    let start = Instant::now();
+    let res = next.run(req).await;
+    let status = res.status();
+    if access_log.should_log(status) {
+        let latency_ms = start.elapsed().as_millis() as u64;
+        if is_error(status) {
+            warn!(target: "access_log", method = %method, path = %path, status = status.as_u16(), latency_ms);
+        } else {
+            info!(target: "access_log", method = %method, path = %path, status = status.as_u16(), latency_ms);
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use hyper::StatusCode;
+
+    use super::AccessLog;
+
+    #[test]
+    fn test_access_log_sampling() {
+        let access_log = AccessLog::new(3, false);
+        let logged = (0..7)
+            .map(|_| access_log.should_log(StatusCode::OK))
+            .collect::<Vec<_>>();
+        assert_eq!(logged, [true, false, false, true, false, false, true]);
+        assert!(!access_log.should_log(StatusCode::INTERNAL_SERVER_ERROR));
+
+        let access_log = AccessLog::new(0, true);
+        assert!(access_log.is_enabled());
+        assert!(!access_log.should_log(StatusCode::OK));
+        assert!(access_log.should_log(StatusCode::NOT_FOUND));
+        assert!(access_log.should_log(StatusCode::INTERNAL_SERVER_ERROR));
+
+        assert!(!AccessLog::new(0, false).is_enabled());
+    }
+}
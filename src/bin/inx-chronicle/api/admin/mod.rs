@@ -0,0 +1,9 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Routes for operating Chronicle, which always require authentication.
+
+mod responses;
+mod routes;
+
+pub use self::routes::{routes, RecomputeJobs};
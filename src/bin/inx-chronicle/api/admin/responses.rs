@@ -0,0 +1,39 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use chronicle::model::tangle::MilestoneIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::api::responses::impl_success_response;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecomputeJobResponse {
+    pub job_id: String,
+    pub start_milestone: MilestoneIndex,
+    pub end_milestone: MilestoneIndex,
+    pub analytics: Vec<String>,
+    #[serde(flatten)]
+    pub status: RecomputeJobStatus,
+}
+
+impl_success_response!(RecomputeJobResponse);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum RecomputeJobStatus {
+    Running,
+    Completed,
+    Failed { error: String },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsStateResponse {
+    pub milestone_index: MilestoneIndex,
+    pub analytics: BTreeMap<String, serde_json::Value>,
+}
+
+impl_success_response!(AnalyticsStateResponse);
@@ -0,0 +1,213 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{Json, Path},
+    middleware::from_extractor,
+    routing::{get, post},
+    Extension,
+};
+use chronicle::{
+    db::{
+        influxdb::{config::all_analytics, AnalyticsChoice, InfluxDb},
+        MongoDb,
+    },
+    model::tangle::MilestoneIndex,
+    tangle::Tangle,
+};
+use clap::ValueEnum;
+use serde::Deserialize;
+use tokio::sync::Semaphore;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::responses::{AnalyticsStateResponse, RecomputeJobResponse, RecomputeJobStatus};
+use crate::{
+    api::{
+        auth::AdminAuth,
+        error::{AnalyticsDisabledError, MissingError, OverloadedError, RequestError},
+        router::Router,
+        ApiResult,
+    },
+    cli::analytics::{fill_analytics, SharedAnalytics},
+};
+
+pub fn routes() -> Router {
+    Router::new()
+        .nest(
+            "/analytics",
+            Router::new()
+                .route("/recompute", post(recompute_analytics))
+                .route("/recompute/:job_id", get(recompute_job))
+                .route("/state", get(analytics_state)),
+        )
+        .route_layer(from_extractor::<AdminAuth>())
+}
+
+#[derive(Clone, Debug)]
+struct RecomputeJob {
+    start_milestone: MilestoneIndex,
+    end_milestone: MilestoneIndex,
+    analytics: Vec<AnalyticsChoice>,
+    status: RecomputeJobStatus,
+}
+
+impl RecomputeJob {
+    fn response(&self, job_id: String) -> RecomputeJobResponse {
+        RecomputeJobResponse {
+            job_id,
+            start_milestone: self.start_milestone,
+            end_milestone: self.end_milestone,
+            analytics: self
+                .analytics
+                .iter()
+                .filter_map(|choice| choice.to_possible_value())
+                .map(|value| value.get_name().to_string())
+                .collect(),
+            status: self.status.clone(),
+        }
+    }
+}
+
+/// The maximum number of recomputation jobs that run at the same time, since each of them reads the ledger state.
+const MAX_RUNNING_RECOMPUTE_JOBS: usize = 2;
+
+/// The analytics recomputation jobs started since the API was started.
+#[derive(Clone, Debug)]
+pub struct RecomputeJobs {
+    jobs: Arc<Mutex<HashMap<String, RecomputeJob>>>,
+    running: Arc<Semaphore>,
+}
+
+impl Default for RecomputeJobs {
+    fn default() -> Self {
+        Self {
+            jobs: Default::default(),
+            running: Arc::new(Semaphore::new(MAX_RUNNING_RECOMPUTE_JOBS)),
+        }
+    }
+}
+
+impl RecomputeJobs {
+    fn insert(&self, job_id: String, job: RecomputeJob) {
+        // Panic: The lock is never held across a panic.
+        self.jobs.lock().unwrap().insert(job_id, job);
+    }
+
+    fn get(&self, job_id: &str) -> Option<RecomputeJob> {
+        self.jobs.lock().unwrap().get(job_id).cloned()
+    }
+
+    fn set_status(&self, job_id: &str, status: RecomputeJobStatus) {
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(job_id) {
+            job.status = status;
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct RecomputeRequest {
+    start_milestone: MilestoneIndex,
+    end_milestone: MilestoneIndex,
+    /// The analytics to recompute, or all of them if empty.
+    #[serde(default)]
+    analytics: Vec<String>,
+}
+
+/// Recomputes the per-milestone analytics for an inclusive milestone range in the background, overwriting the
+/// previously written points. The returned job id can be used to poll for the outcome.
+async fn recompute_analytics(
+    Extension(database): Extension<MongoDb>,
+    influx_db: Option<Extension<InfluxDb>>,
+    Extension(jobs): Extension<RecomputeJobs>,
+    Json(request): Json<RecomputeRequest>,
+) -> ApiResult<RecomputeJobResponse> {
+    let Extension(influx_db) = influx_db.ok_or(AnalyticsDisabledError)?;
+    if request.end_milestone < request.start_milestone {
+        return Err(RequestError::BadMilestoneRange.into());
+    }
+    let analytics = if request.analytics.is_empty() {
+        all_analytics().into_iter().collect()
+    } else {
+        request
+            .analytics
+            .iter()
+            .map(|choice| {
+                AnalyticsChoice::from_str(choice, true)
+                    .map_err(|_| RequestError::InvalidAnalyticsChoice(choice.clone()))
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    // The permit is held until the job finishes.
+    let permit = jobs
+        .running
+        .clone()
+        .try_acquire_owned()
+        .map_err(|_| OverloadedError)?;
+
+    let job_id = Uuid::new_v4().to_string();
+    let job = RecomputeJob {
+        start_milestone: request.start_milestone,
+        end_milestone: request.end_milestone,
+        analytics,
+        status: RecomputeJobStatus::Running,
+    };
+    jobs.insert(job_id.clone(), job.clone());
+    info!(
+        "Recomputing analytics for milestones {}..={} in job {job_id}.",
+        job.start_milestone, job.end_milestone
+    );
+
+    let response = job.response(job_id.clone());
+    tokio::spawn(async move {
+        let status = match fill_analytics(
+            &database,
+            &influx_db,
+            &Tangle::from(database.clone()),
+            job.start_milestone,
+            job.end_milestone + 1,
+            1,
+            &job.analytics,
+        )
+        .await
+        {
+            Ok(()) => {
+                info!("Finished recomputing analytics in job {job_id}.");
+                RecomputeJobStatus::Completed
+            }
+            Err(e) => {
+                error!("Recomputing analytics in job {job_id} failed: {e}");
+                RecomputeJobStatus::Failed { error: e.to_string() }
+            }
+        };
+        jobs.set_status(&job_id, status);
+        drop(permit);
+    });
+
+    Ok(response)
+}
+
+async fn recompute_job(
+    Extension(jobs): Extension<RecomputeJobs>,
+    Path(job_id): Path<String>,
+) -> ApiResult<RecomputeJobResponse> {
+    Ok(jobs.get(&job_id).ok_or(MissingError::NoResults)?.response(job_id))
+}
+
+/// Returns a summary of the state of the analytics run by the INX worker, as of the latest analyzed milestone.
+async fn analytics_state(
+    Extension(shared_analytics): Extension<SharedAnalytics>,
+) -> ApiResult<AnalyticsStateResponse> {
+    let snapshot = shared_analytics.snapshot().await.ok_or(MissingError::NoResults)?;
+    Ok(AnalyticsStateResponse {
+        milestone_index: snapshot.milestone_index,
+        analytics: snapshot.analytics,
+    })
+}
@@ -23,24 +23,54 @@ impl<B: Send> FromRequest<B> for Auth {
 
         let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
 
-        if config.public_routes.is_match(&uri.to_string()) {
+        // Public routes are configured relative to the base path.
+        let uri = uri.to_string();
+        if config
+            .public_routes
+            .is_match(uri.strip_prefix(config.base_path.as_str()).unwrap_or(&uri))
+        {
             return Ok(Auth);
         }
 
-        let TypedHeader(Authorization(bearer)) = TypedHeader::<Authorization<Bearer>>::from_request(req)
-            .await
-            .map_err(RequestError::from)?;
-        let jwt = JsonWebToken(bearer.token().to_string());
-
-        jwt.validate(
-            Validation::default()
-                .with_issuer(ApiConfigData::ISSUER)
-                .with_audience(ApiConfigData::AUDIENCE)
-                .validate_nbf(true),
-            config.jwt_secret_key.as_ref(),
-        )
-        .map_err(AuthError::InvalidJwt)?;
+        validate_jwt(req, &config).await?;
 
         Ok(Auth)
     }
 }
+
+/// Requires a valid JWT, even if the route matches one of the configured public routes.
+pub struct AdminAuth;
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for AdminAuth {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+
+        validate_jwt(req, &config).await?;
+
+        Ok(AdminAuth)
+    }
+}
+
+async fn validate_jwt<B: Send>(
+    req: &mut axum::extract::RequestParts<B>,
+    config: &ApiConfigData,
+) -> Result<(), ApiError> {
+    let TypedHeader(Authorization(bearer)) = TypedHeader::<Authorization<Bearer>>::from_request(req)
+        .await
+        .map_err(RequestError::from)?;
+    let jwt = JsonWebToken(bearer.token().to_string());
+
+    jwt.validate(
+        Validation::default()
+            .with_issuer(&config.jwt_issuer)
+            .with_audience(ApiConfigData::AUDIENCE)
+            .validate_nbf(true),
+        config.jwt_secret_key.as_ref(),
+    )
+    .map_err(AuthError::InvalidJwt)?;
+
+    Ok(())
+}
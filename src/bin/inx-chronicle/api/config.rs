@@ -1,23 +1,97 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::time::Duration;
+use std::{num::NonZeroUsize, time::Duration};
 
+use chronicle::model::utxo::Address;
 use derive_more::From;
+use mongodb::options::ReadPreference;
 use regex::RegexSet;
 use serde::{Deserialize, Serialize};
 use tower_http::cors::AllowOrigin;
 
-use super::{error::ConfigError, SecretKey};
+use super::{
+    access_log::AccessLog, denylist::AddressDenylist, error::ConfigError, health::HealthTracker,
+    ledger_index_cache::LedgerIndexCache, output_cache::OutputCache, SecretKey,
+};
 
 pub const DEFAULT_ENABLED: bool = true;
 pub const DEFAULT_PORT: u16 = 8042;
+pub const DEFAULT_BASE_PATH: &str = "";
 pub const DEFAULT_ALLOW_ORIGINS: &str = "0.0.0.0";
 pub const DEFAULT_PUBLIC_ROUTES: &str = "api/core/v2/*";
 pub const DEFAULT_MAX_PAGE_SIZE: usize = 1000;
 pub const DEFAULT_JWT_PASSWORD: &str = "password";
 pub const DEFAULT_JWT_SALT: &str = "saltines";
 pub const DEFAULT_JWT_EXPIRATION: &str = "72h";
+pub const DEFAULT_JWT_ISSUER: &str = "chronicle";
+pub const DEFAULT_REQUEST_TIMEOUT: &str = "30s";
+pub const DEFAULT_UNHEALTHY_GRACE_PERIOD: &str = "0s";
+pub const DEFAULT_CURSOR_FORMAT: CursorFormat = CursorFormat::Chronicle;
+pub const DEFAULT_READ_PREFERENCE: ReadPreferenceMode = ReadPreferenceMode::Primary;
+pub const DEFAULT_OUTPUT_CACHE_SIZE: usize = 10_000;
+pub const DEFAULT_ADDRESS_INDEX_ENABLED: bool = true;
+pub const DEFAULT_AMOUNT_FORMAT: AmountFormat = AmountFormat::String;
+pub const DEFAULT_ACCESS_LOG_SAMPLE_RATE: u64 = 0;
+pub const DEFAULT_ACCESS_LOG_ERRORS: bool = false;
+
+/// The format in which the indexer API emits pagination cursors. Cursors in either format are accepted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum CursorFormat {
+    /// The milestone index, output id and page size separated by dots.
+    Chronicle,
+    /// The opaque hex encoded form used by the indexer plugin of the node.
+    Node,
+}
+
+/// The members of a MongoDB replica set that the database reads of API requests are routed to.
+///
+/// Secondaries may lag behind the primary, so reads from them can return a slightly stale ledger index.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadPreferenceMode {
+    /// Only read from the primary.
+    Primary,
+    /// Read from the primary, or from a secondary if the primary is unavailable.
+    PrimaryPreferred,
+    /// Only read from secondaries.
+    Secondary,
+    /// Read from a secondary, or from the primary if no secondary is available.
+    SecondaryPreferred,
+    /// Read from the member with the lowest latency.
+    Nearest,
+}
+
+/// How token amounts are represented in the JSON responses of the explorer API.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum AmountFormat {
+    /// Decimal strings, which keep their precision in every JSON parser.
+    String,
+    /// JSON numbers, which lose precision above 2^53 in JavaScript.
+    Number,
+}
+
+impl From<ReadPreferenceMode> for ReadPreference {
+    fn from(value: ReadPreferenceMode) -> Self {
+        match value {
+            ReadPreferenceMode::Primary => Self::Primary,
+            ReadPreferenceMode::PrimaryPreferred => Self::PrimaryPreferred {
+                options: Default::default(),
+            },
+            ReadPreferenceMode::Secondary => Self::Secondary {
+                options: Default::default(),
+            },
+            ReadPreferenceMode::SecondaryPreferred => Self::SecondaryPreferred {
+                options: Default::default(),
+            },
+            ReadPreferenceMode::Nearest => Self::Nearest {
+                options: Default::default(),
+            },
+        }
+    }
+}
 
 /// API configuration
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -25,14 +99,42 @@ pub const DEFAULT_JWT_EXPIRATION: &str = "72h";
 pub struct ApiConfig {
     pub enabled: bool,
     pub port: u16,
+    pub base_path: String,
     pub allow_origins: SingleOrMultiple<String>,
     pub public_routes: Vec<String>,
     pub max_page_size: usize,
     pub jwt_password: String,
     pub jwt_salt: String,
     pub jwt_identity_file: Option<String>,
+    pub jwt_issuer: String,
+    pub address_denylist_file: Option<String>,
+    /// Addresses that are left out of the richlist, like those of the treasury or of burned tokens.
+    pub richlist_excluded_addresses: Vec<String>,
     #[serde(with = "humantime_serde")]
     pub jwt_expiration: Duration,
+    #[serde(with = "humantime_serde")]
+    pub request_timeout: Duration,
+    #[serde(with = "humantime_serde")]
+    pub unhealthy_grace_period: Duration,
+    pub max_concurrent_requests: Option<usize>,
+    pub cursor_format: CursorFormat,
+    pub read_preference: ReadPreferenceMode,
+    pub amount_format: AmountFormat,
+    /// The number of outputs kept in memory to answer repeated requests for the same outputs. Zero disables the cache.
+    pub output_cache_size: usize,
+    /// Whether balances are read from the address outputs index rather than from the outputs collection.
+    pub address_index_enabled: bool,
+    /// The maximum number of outputs of a single address that the balance and summary endpoints read. Results for
+    /// addresses with more outputs are truncated.
+    pub max_address_outputs: Option<NonZeroUsize>,
+    /// The name of the network that the database is expected to contain.
+    pub network_name: Option<String>,
+    /// The bech32 HRP of the network that the database is expected to contain.
+    pub bech32_hrp: Option<String>,
+    /// One in this many requests is written to the access log. Zero disables sampling.
+    pub access_log_sample_rate: u64,
+    /// Whether every request that fails is written to the access log, regardless of sampling.
+    pub access_log_errors: bool,
 }
 
 impl Default for ApiConfig {
@@ -40,13 +142,33 @@ impl Default for ApiConfig {
         Self {
             enabled: DEFAULT_ENABLED,
             port: DEFAULT_PORT,
+            base_path: DEFAULT_BASE_PATH.to_string(),
             allow_origins: SingleOrMultiple::Single(DEFAULT_ALLOW_ORIGINS.to_string()),
             public_routes: vec![DEFAULT_PUBLIC_ROUTES.to_string()],
             max_page_size: DEFAULT_MAX_PAGE_SIZE,
             jwt_identity_file: None,
+            address_denylist_file: None,
+            richlist_excluded_addresses: Vec::new(),
             jwt_password: DEFAULT_JWT_PASSWORD.to_string(),
             jwt_salt: DEFAULT_JWT_SALT.to_string(),
+            jwt_issuer: DEFAULT_JWT_ISSUER.to_string(),
             jwt_expiration: DEFAULT_JWT_EXPIRATION.parse::<humantime::Duration>().unwrap().into(),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT.parse::<humantime::Duration>().unwrap().into(),
+            unhealthy_grace_period: DEFAULT_UNHEALTHY_GRACE_PERIOD
+                .parse::<humantime::Duration>()
+                .unwrap()
+                .into(),
+            max_concurrent_requests: None,
+            cursor_format: DEFAULT_CURSOR_FORMAT,
+            read_preference: DEFAULT_READ_PREFERENCE,
+            amount_format: DEFAULT_AMOUNT_FORMAT,
+            output_cache_size: DEFAULT_OUTPUT_CACHE_SIZE,
+            address_index_enabled: DEFAULT_ADDRESS_INDEX_ENABLED,
+            max_address_outputs: None,
+            network_name: None,
+            bech32_hrp: None,
+            access_log_sample_rate: DEFAULT_ACCESS_LOG_SAMPLE_RATE,
+            access_log_errors: DEFAULT_ACCESS_LOG_ERRORS,
         }
     }
 }
@@ -54,6 +176,8 @@ impl Default for ApiConfig {
 #[derive(Clone, Debug)]
 pub struct ApiConfigData {
     pub port: u16,
+    /// The path prefix under which all routes are mounted, without a trailing slash.
+    pub base_path: String,
     pub allow_origins: AllowOrigin,
     pub public_routes: RegexSet,
     pub max_page_size: usize,
@@ -61,11 +185,42 @@ pub struct ApiConfigData {
     pub jwt_password_salt: String,
     pub jwt_secret_key: SecretKey,
     pub jwt_expiration: Duration,
+    /// The issuer that tokens are minted with and that incoming tokens must match.
+    pub jwt_issuer: String,
     pub jwt_argon_config: JwtArgonConfig,
+    /// The maximum duration a request handler may take before the request is aborted.
+    pub request_timeout: Duration,
+    /// The maximum number of API requests that are handled concurrently. Additional requests are rejected.
+    pub max_concurrent_requests: Option<usize>,
+    /// The format in which the indexer API emits pagination cursors.
+    pub cursor_format: CursorFormat,
+    /// The replica set members that reads are routed to, unless a request asks for different ones.
+    pub read_preference: ReadPreferenceMode,
+    /// How token amounts are represented, unless a request asks for a different format.
+    pub amount_format: AmountFormat,
+    /// Addresses for which no data is served.
+    pub address_denylist: AddressDenylist,
+    /// Addresses that are left out of the richlist.
+    pub richlist_excluded_addresses: Vec<Address>,
+    /// Debounces the health status over the configured grace period.
+    pub health_tracker: HealthTracker,
+    /// Outputs that were recently requested.
+    pub output_cache: OutputCache,
+    /// The ledger index, as last seen on the stream of new milestones.
+    pub ledger_index_cache: LedgerIndexCache,
+    /// Whether balances are read from the address outputs index.
+    pub address_index_enabled: bool,
+    /// The maximum number of outputs of a single address that are read to answer a request.
+    pub max_address_outputs: Option<NonZeroUsize>,
+    /// The name of the network that the database is expected to contain.
+    pub network_name: Option<String>,
+    /// The bech32 HRP of the network that the database is expected to contain.
+    pub bech32_hrp: Option<String>,
+    /// Decides which requests are written to the access log.
+    pub access_log: AccessLog,
 }
 
 impl ApiConfigData {
-    pub const ISSUER: &'static str = "chronicle";
     pub const AUDIENCE: &'static str = "api";
 }
 
@@ -73,8 +228,12 @@ impl TryFrom<ApiConfig> for ApiConfigData {
     type Error = ConfigError;
 
     fn try_from(config: ApiConfig) -> Result<Self, Self::Error> {
+        if config.jwt_issuer.trim().is_empty() {
+            return Err(ConfigError::EmptyJwtIssuer);
+        }
         Ok(Self {
             port: config.port,
+            base_path: normalize_base_path(&config.base_path),
             allow_origins: AllowOrigin::try_from(config.allow_origins)?,
             public_routes: RegexSet::new(config.public_routes.iter().map(route_to_regex).collect::<Vec<_>>())?,
             max_page_size: config.max_page_size,
@@ -97,11 +256,47 @@ impl TryFrom<ApiConfig> for ApiConfigData {
                 }
             },
             jwt_expiration: config.jwt_expiration,
+            jwt_issuer: config.jwt_issuer,
             jwt_argon_config: JwtArgonConfig::default(),
+            request_timeout: config.request_timeout,
+            max_concurrent_requests: config.max_concurrent_requests,
+            cursor_format: config.cursor_format,
+            read_preference: config.read_preference,
+            amount_format: config.amount_format,
+            address_denylist: match &config.address_denylist_file {
+                Some(path) => AddressDenylist::from_file(path)?,
+                None => AddressDenylist::default(),
+            },
+            richlist_excluded_addresses: config
+                .richlist_excluded_addresses
+                .iter()
+                .map(|address| {
+                    address
+                        .parse()
+                        .map_err(|_| ConfigError::InvalidRichlistExclusion(address.clone()))
+                })
+                .collect::<Result<_, _>>()?,
+            health_tracker: HealthTracker::new(config.unhealthy_grace_period),
+            output_cache: OutputCache::new(config.output_cache_size),
+            ledger_index_cache: Default::default(),
+            address_index_enabled: config.address_index_enabled,
+            max_address_outputs: config.max_address_outputs,
+            network_name: config.network_name,
+            bech32_hrp: config.bech32_hrp,
+            access_log: AccessLog::new(config.access_log_sample_rate, config.access_log_errors),
         })
     }
 }
 
+fn normalize_base_path(base_path: &str) -> String {
+    let base_path = base_path.trim_matches('/');
+    if base_path.is_empty() {
+        String::new()
+    } else {
+        format!("/{base_path}")
+    }
+}
+
 fn route_to_regex(route: &impl AsRef<str>) -> String {
     // Escape the string to make sure a regex can be built from it.
     // Existing wildcards `*` get escaped to `\\*`.
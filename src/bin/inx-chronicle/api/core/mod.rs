@@ -5,3 +5,10 @@ mod responses;
 mod routes;
 
 pub use self::routes::routes;
+pub(super) use self::{
+    responses::OutputMetadataResponse,
+    routes::{
+        create_output_metadata_response, create_output_metadata_with_input_index_response,
+        create_protocol_parameters_response,
+    },
+};
@@ -1,7 +1,10 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_types::{api::core::response as iota, block::protocol::dto::ProtocolParametersDto};
+use iota_types::{
+    api::core::response as iota,
+    block::{output::dto::OutputMetadataDto, protocol::dto::ProtocolParametersDto},
+};
 use serde::{Deserialize, Serialize};
 
 use crate::api::responses::impl_success_response;
@@ -19,6 +22,19 @@ pub struct InfoResponse {
 
 impl_success_response!(InfoResponse);
 
+/// The metadata of an output as returned by the node, along with the position of the output among the inputs of the
+/// transaction that spent it, if that is known.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputMetadataResponse {
+    #[serde(flatten)]
+    pub metadata: OutputMetadataDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_index_spent: Option<u16>,
+}
+
+impl_success_response!(OutputMetadataResponse);
+
 /// A wrapper struct that allows us to implement [`IntoResponse`](axum::response::IntoResponse) for the foreign
 /// responses from [`iota_types`](iota_types::api::core::response).
 #[derive(Clone, Debug, Serialize, derive_more::From)]
@@ -14,13 +14,14 @@ use chronicle::{
         mongodb::collections::{
             BlockCollection, ConfigurationUpdateCollection, MilestoneCollection, OutputCollection,
             OutputMetadataResult, OutputWithMetadataResult, ProtocolUpdateCollection, TreasuryCollection,
-            UtxoChangesResult,
+            UnreferencedBlockCollection, UtxoChangesResult,
         },
         MongoDb,
     },
     model::{
         metadata::BlockMetadata,
         payload::{MilestoneId, TransactionId},
+        protocol::ProtocolParameters,
         tangle::MilestoneIndex,
         utxo::OutputId,
         BlockId, TryFromWithContext,
@@ -45,9 +46,11 @@ use iota_types::{
 };
 use packable::PackableExt;
 
-use super::responses::{InfoResponse, IotaRawResponse, IotaResponse};
+use super::responses::{InfoResponse, IotaRawResponse, IotaResponse, OutputMetadataResponse};
 use crate::api::{
-    error::{ApiError, CorruptStateError, MissingError, RequestError},
+    config::ApiConfigData,
+    error::{ApiError, CorruptStateError, MissingError, NotSyncedError, RequestError},
+    responses::{Conditional, ETag},
     router::Router,
     routes::{is_healthy, not_implemented, BYTE_CONTENT_HEADER},
     ApiResult,
@@ -101,7 +104,10 @@ pub fn routes() -> Router {
         .route("/control/snapshot/create", not_implemented.into_service())
 }
 
-pub async fn info(database: Extension<MongoDb>) -> ApiResult<InfoResponse> {
+pub async fn info(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+) -> ApiResult<InfoResponse> {
     let protocol = database
         .collection::<ProtocolUpdateCollection>()
         .get_latest_protocol_parameters()
@@ -109,21 +115,23 @@ pub async fn info(database: Extension<MongoDb>) -> ApiResult<InfoResponse> {
         .ok_or(CorruptStateError::ProtocolParams)?
         .parameters;
 
-    let is_healthy = is_healthy(&database).await.unwrap_or_else(|ApiError { error, .. }| {
-        tracing::error!("An error occured during health check: {error}");
-        false
-    });
+    let is_healthy = is_healthy(&database, &config)
+        .await
+        .unwrap_or_else(|ApiError { error, .. }| {
+            tracing::error!("An error occured during health check: {error}");
+            false
+        });
 
     let newest_milestone = database
         .collection::<MilestoneCollection>()
         .get_newest_milestone()
         .await?
-        .ok_or(CorruptStateError::Milestone)?;
+        .ok_or(NotSyncedError)?;
     let oldest_milestone = database
         .collection::<MilestoneCollection>()
         .get_oldest_milestone()
         .await?
-        .ok_or(CorruptStateError::Milestone)?;
+        .ok_or(NotSyncedError)?;
 
     let latest_milestone = LatestMilestoneResponse {
         index: newest_milestone.milestone_index.0,
@@ -164,19 +172,7 @@ pub async fn info(database: Extension<MongoDb>) -> ApiResult<InfoResponse> {
             confirmed_milestone,
             pruning_index: oldest_milestone.milestone_index.0 - 1,
         },
-        protocol: ProtocolParametersDto {
-            protocol_version: protocol.version,
-            network_name: protocol.network_name,
-            below_max_depth: protocol.below_max_depth,
-            bech32_hrp: protocol.bech32_hrp,
-            min_pow_score: protocol.min_pow_score,
-            rent_structure: RentStructureDto {
-                v_byte_cost: protocol.rent_structure.v_byte_cost,
-                v_byte_factor_data: protocol.rent_structure.v_byte_factor_data,
-                v_byte_factor_key: protocol.rent_structure.v_byte_factor_key,
-            },
-            token_supply: protocol.token_supply.to_string(),
-        },
+        protocol: create_protocol_parameters_response(protocol),
         base_token: BaseTokenResponse {
             name: base_token.name,
             ticker_symbol: base_token.ticker_symbol,
@@ -195,21 +191,32 @@ async fn block(
 ) -> ApiResult<IotaRawResponse<BlockDto>> {
     let block_id = BlockId::from_str(&block_id).map_err(RequestError::from)?;
 
+    // Unreferenced blocks are only stored if the INX worker is configured to do so.
     if matches!(headers.get(axum::http::header::ACCEPT), Some(header) if header == BYTE_CONTENT_HEADER) {
-        return Ok(IotaRawResponse::Raw(
-            database
-                .collection::<BlockCollection>()
-                .get_block_raw(&block_id)
-                .await?
-                .ok_or(MissingError::NoResults)?,
-        ));
+        let raw = match database
+            .collection::<BlockCollection>()
+            .get_block_raw(&block_id)
+            .await?
+        {
+            Some(raw) => Some(raw),
+            None => {
+                database
+                    .collection::<UnreferencedBlockCollection>()
+                    .get_block_raw(&block_id)
+                    .await?
+            }
+        };
+        return Ok(IotaRawResponse::Raw(raw.ok_or(MissingError::NoResults)?));
     }
 
-    let block = database
-        .collection::<BlockCollection>()
-        .get_block(&block_id)
-        .await?
-        .ok_or(MissingError::NoResults)?;
+    let block = match database.collection::<BlockCollection>().get_block(&block_id).await? {
+        Some(block) => block,
+        None => database
+            .collection::<UnreferencedBlockCollection>()
+            .get_block(&block_id)
+            .await?
+            .ok_or(MissingError::NoResults)?,
+    };
 
     Ok(IotaRawResponse::Json(block.into()))
 }
@@ -243,7 +250,26 @@ async fn block_metadata(
     Ok(create_block_metadata_response(block_id, metadata).into())
 }
 
-fn create_output_metadata_response(metadata: OutputMetadataResult, ledger_index: MilestoneIndex) -> OutputMetadataDto {
+pub(crate) fn create_protocol_parameters_response(protocol: ProtocolParameters) -> ProtocolParametersDto {
+    ProtocolParametersDto {
+        protocol_version: protocol.version,
+        network_name: protocol.network_name,
+        below_max_depth: protocol.below_max_depth,
+        bech32_hrp: protocol.bech32_hrp,
+        min_pow_score: protocol.min_pow_score,
+        rent_structure: RentStructureDto {
+            v_byte_cost: protocol.rent_structure.v_byte_cost,
+            v_byte_factor_data: protocol.rent_structure.v_byte_factor_data,
+            v_byte_factor_key: protocol.rent_structure.v_byte_factor_key,
+        },
+        token_supply: protocol.token_supply.to_string(),
+    }
+}
+
+pub(crate) fn create_output_metadata_response(
+    metadata: OutputMetadataResult,
+    ledger_index: MilestoneIndex,
+) -> OutputMetadataDto {
     OutputMetadataDto {
         block_id: metadata.block_id.to_hex(),
         transaction_id: metadata.output_id.transaction_id.to_hex(),
@@ -267,25 +293,42 @@ fn create_output_metadata_response(metadata: OutputMetadataResult, ledger_index:
     }
 }
 
+pub(crate) fn create_output_metadata_with_input_index_response(
+    metadata: OutputMetadataResult,
+    ledger_index: MilestoneIndex,
+) -> OutputMetadataResponse {
+    OutputMetadataResponse {
+        input_index_spent: metadata.spent_metadata.and_then(|spent_md| spent_md.input_index),
+        metadata: create_output_metadata_response(metadata, ledger_index),
+    }
+}
+
 async fn output(
     database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
     Path(output_id): Path<String>,
     headers: HeaderMap,
-) -> ApiResult<IotaRawResponse<OutputWithMetadataResponse>> {
+) -> ApiResult<Conditional<IotaRawResponse<OutputWithMetadataResponse>>> {
     let ledger_index = database
         .collection::<MilestoneCollection>()
         .get_ledger_index()
         .await?
-        .ok_or(MissingError::NoResults)?;
+        .ok_or(NotSyncedError)?;
     let output_id = OutputId::from_str(&output_id).map_err(RequestError::from)?;
 
-    let OutputWithMetadataResult { output, metadata } = database
-        .collection::<OutputCollection>()
-        .get_output_with_metadata(&output_id, ledger_index)
+    let OutputWithMetadataResult { output, metadata } = config
+        .output_cache
+        .get_output_with_metadata(&database, &output_id, ledger_index)
         .await?
         .ok_or(MissingError::NoResults)?;
 
-    if matches!(headers.get(axum::http::header::ACCEPT), Some(header) if header == BYTE_CONTENT_HEADER) {
+    let raw = matches!(headers.get(axum::http::header::ACCEPT), Some(header) if header == BYTE_CONTENT_HEADER);
+    let etag = output_etag(&metadata, raw);
+    if etag.matches(&headers) {
+        return Ok(Conditional::NotModified(etag));
+    }
+
+    if raw {
         let ctx = database
             .collection::<ProtocolUpdateCollection>()
             .get_protocol_parameters_for_ledger_index(metadata.booked.milestone_index)
@@ -293,26 +336,44 @@ async fn output(
             .ok_or(MissingError::NoResults)?
             .parameters;
 
-        return Ok(IotaRawResponse::Raw(output.raw(ctx)?));
+        return Ok(Conditional::Modified(etag, IotaRawResponse::Raw(output.raw(ctx)?)));
     }
 
     let metadata = create_output_metadata_response(metadata, ledger_index);
 
-    Ok(IotaRawResponse::Json(OutputWithMetadataResponse {
-        metadata,
-        output: output.into(),
-    }))
+    Ok(Conditional::Modified(
+        etag,
+        IotaRawResponse::Json(OutputWithMetadataResponse {
+            metadata,
+            output: output.into(),
+        }),
+    ))
+}
+
+/// An output only changes when it is spent, so its id and spent status identify the version of the response.
+fn output_etag(metadata: &OutputMetadataResult, raw: bool) -> ETag {
+    ETag::weak(format_args!(
+        "{}-{}{}",
+        metadata.output_id.to_hex(),
+        if metadata.spent_metadata.is_some() {
+            "spent"
+        } else {
+            "unspent"
+        },
+        if raw { "-raw" } else { "" }
+    ))
 }
 
 async fn output_metadata(
     database: Extension<MongoDb>,
     Path(output_id): Path<String>,
-) -> ApiResult<IotaResponse<OutputMetadataDto>> {
+    headers: HeaderMap,
+) -> ApiResult<Conditional<OutputMetadataResponse>> {
     let ledger_index = database
         .collection::<MilestoneCollection>()
         .get_ledger_index()
         .await?
-        .ok_or(MissingError::NoResults)?;
+        .ok_or(NotSyncedError)?;
     let output_id = OutputId::from_str(&output_id).map_err(RequestError::from)?;
     let metadata = database
         .collection::<OutputCollection>()
@@ -320,7 +381,15 @@ async fn output_metadata(
         .await?
         .ok_or(MissingError::NoResults)?;
 
-    Ok(create_output_metadata_response(metadata, ledger_index).into())
+    let etag = output_etag(&metadata, false);
+    if etag.matches(&headers) {
+        return Ok(Conditional::NotModified(etag));
+    }
+
+    Ok(Conditional::Modified(
+        etag,
+        create_output_metadata_with_input_index_response(metadata, ledger_index),
+    ))
 }
 
 async fn included_block(
@@ -506,7 +575,7 @@ async fn collect_utxo_changes(database: &MongoDb, milestone_index: MilestoneInde
         .collection::<MilestoneCollection>()
         .get_ledger_index()
         .await?
-        .ok_or(MissingError::NoResults)?;
+        .ok_or(NotSyncedError)?;
     let UtxoChangesResult {
         created_outputs,
         consumed_outputs,
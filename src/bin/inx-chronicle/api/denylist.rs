@@ -0,0 +1,150 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+
+use chronicle::model::utxo::Address;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use super::{config::ApiConfigData, error::DeniedAddressError, ApiResult};
+
+#[derive(Error, Debug)]
+pub enum AddressDenylistError {
+    #[error("failed to read file: {0}")]
+    FileRead(#[from] std::io::Error),
+    #[error("invalid address `{address}` on line {line}")]
+    InvalidAddress { address: String, line: usize },
+}
+
+/// A list of addresses that must not be served by the API.
+///
+/// The list is read from a file containing one bech32 address per line. Empty lines and lines starting with `#` are
+/// ignored. The file is reloaded whenever its modification time changes, so the list can be updated without restarting.
+#[derive(Clone, Debug, Default)]
+pub struct AddressDenylist(Option<Arc<DenylistFile>>);
+
+#[derive(Debug)]
+struct DenylistFile {
+    path: PathBuf,
+    state: RwLock<DenylistState>,
+}
+
+#[derive(Debug)]
+struct DenylistState {
+    modified: Option<SystemTime>,
+    addresses: HashSet<Address>,
+}
+
+impl AddressDenylist {
+    /// Loads the denylist from a file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AddressDenylistError> {
+        let path = path.as_ref().to_path_buf();
+        let state = DenylistState {
+            modified: std::fs::metadata(&path)?.modified().ok(),
+            addresses: parse_addresses(&std::fs::read_to_string(&path)?)?,
+        };
+        Ok(Self(Some(Arc::new(DenylistFile {
+            path,
+            state: RwLock::new(state),
+        }))))
+    }
+
+    /// Checks whether an address is on the denylist, reloading the file first if it was modified.
+    pub async fn contains(&self, address: &Address) -> bool {
+        if let Some(file) = &self.0 {
+            file.reload_if_modified().await;
+            // Unwrap: the lock is never held across a panic.
+            file.state.read().unwrap().addresses.contains(address)
+        } else {
+            false
+        }
+    }
+}
+
+impl DenylistFile {
+    async fn reload_if_modified(&self) {
+        let modified = match tokio::fs::metadata(&self.path).await.and_then(|m| m.modified()) {
+            Ok(modified) => modified,
+            Err(e) => {
+                warn!(
+                    "Failed to read address denylist `{}`, keeping the previous list: {e}",
+                    self.path.display()
+                );
+                return;
+            }
+        };
+        if self.state.read().unwrap().modified == Some(modified) {
+            return;
+        }
+        match tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(AddressDenylistError::from)
+            .and_then(|contents| parse_addresses(&contents))
+        {
+            Ok(addresses) => {
+                info!(
+                    "Reloaded address denylist `{}` with {} addresses.",
+                    self.path.display(),
+                    addresses.len()
+                );
+                *self.state.write().unwrap() = DenylistState {
+                    modified: Some(modified),
+                    addresses,
+                };
+            }
+            Err(e) => warn!(
+                "Failed to reload address denylist `{}`, keeping the previous list: {e}",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+/// Rejects addresses that are on the configured denylist.
+pub async fn check_denylist(config: &ApiConfigData, address: &Address) -> ApiResult<()> {
+    if config.address_denylist.contains(address).await {
+        Err(DeniedAddressError)?;
+    }
+    Ok(())
+}
+
+fn parse_addresses(contents: &str) -> Result<HashSet<Address>, AddressDenylistError> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, address)| {
+            Address::from_str(address).map_err(|_| AddressDenylistError::InvalidAddress {
+                address: address.to_string(),
+                line,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_denylist() {
+        let address =
+            iota_types::block::address::Address::from(iota_types::block::address::Ed25519Address::new([1; 32]));
+        let addresses = parse_addresses(&format!("# flagged\n\n  {}  \n", address.to_bech32("rms"))).unwrap();
+        assert_eq!(addresses.len(), 1);
+        assert!(addresses.contains(&Address::from(address)));
+
+        assert!(matches!(
+            parse_addresses("# flagged\nnot-an-address"),
+            Err(AddressDenylistError::InvalidAddress { line: 2, .. })
+        ));
+    }
+}
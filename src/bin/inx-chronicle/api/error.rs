@@ -138,6 +138,75 @@ impl IntoResponse for UnimplementedError {
     }
 }
 
+/// Returned when a request needs a ledger index, but the database does not contain any milestones yet.
+#[derive(Error, Debug)]
+#[error("no milestone has been synced yet; retry later")]
+pub struct NotSyncedError;
+
+impl ErrorStatus for NotSyncedError {
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+#[derive(Error, Debug)]
+#[allow(missing_docs)]
+#[error("too many concurrent requests")]
+pub struct OverloadedError;
+
+impl ErrorStatus for OverloadedError {
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+impl IntoResponse for OverloadedError {
+    fn into_response(self) -> axum::response::Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+#[derive(Error, Debug)]
+#[allow(missing_docs)]
+#[error("request timed out")]
+pub struct TimeoutError;
+
+impl ErrorStatus for TimeoutError {
+    fn status(&self) -> StatusCode {
+        StatusCode::GATEWAY_TIMEOUT
+    }
+}
+
+impl IntoResponse for TimeoutError {
+    fn into_response(self) -> axum::response::Response {
+        ApiError::from(self).into_response()
+    }
+}
+
+#[cfg(feature = "analytics")]
+#[derive(Error, Debug)]
+#[allow(missing_docs)]
+#[error("analytics are not enabled")]
+pub struct AnalyticsDisabledError;
+
+#[cfg(feature = "analytics")]
+impl ErrorStatus for AnalyticsDisabledError {
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+#[derive(Error, Debug)]
+#[allow(missing_docs)]
+#[error("the requested address is unavailable for legal reasons")]
+pub struct DeniedAddressError;
+
+impl ErrorStatus for DeniedAddressError {
+    fn status(&self) -> StatusCode {
+        StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS
+    }
+}
+
 #[derive(Error, Debug)]
 #[allow(missing_docs)]
 pub enum MissingError {
@@ -165,6 +234,10 @@ pub enum RequestError {
     BadPagingState,
     #[error("invalid time range")]
     BadTimeRange,
+    #[error("invalid milestone range")]
+    BadMilestoneRange,
+    #[error("sampling cannot be combined with a cursor")]
+    SampleWithCursor,
 
     #[error("invalid IOTA Stardust data: {0}")]
     IotaStardust(#[from] iota_types::block::Error),
@@ -176,15 +249,49 @@ pub enum RequestError {
     Hex(#[from] prefix_hex::Error),
     #[error("invalid integer value provided: {0}")]
     Int(#[from] ParseIntError),
+    #[cfg(feature = "analytics")]
+    #[error("invalid analytic provided: {0}")]
+    InvalidAnalyticsChoice(String),
     #[error("invalid authorization header provided: {0}")]
     InvalidAuthHeader(#[from] TypedHeaderRejection),
+    #[error("invalid output template provided: {0}")]
+    InvalidOutputTemplate(#[from] iota_types::block::DtoError),
     #[error("invalid query parameters provided: {0}")]
     InvalidQueryParams(#[from] QueryRejection),
     #[cfg(feature = "poi")]
     #[error(transparent)]
     PoI(#[from] crate::api::poi::RequestError),
+    #[error("invalid output condition provided: `{0}`, expected one of `nativeTokens`, `storageDepositReturn`, `timelock` or `expiration`")]
+    InvalidOutputCondition(String),
+    #[error("invalid output kind provided: `{0}`, expected one of `basic`, `alias`, `nft` or `foundry`")]
+    InvalidOutputKind(String),
+    #[error("invalid unlock condition provided: `{0}`, expected one of `address`, `storage_deposit_return`, `timelock`, `expiration`, `state_controller_address`, `governor_address` or `immutable_alias_address`")]
+    InvalidUnlockCondition(String),
+    #[error("invalid read preference provided: {0}")]
+    InvalidReadPreference(String),
+    #[error("invalid amount format provided: {0}, expected `string` or `number`")]
+    InvalidAmountFormat(String),
+    #[error("a minimum native token amount requires a native token id")]
+    MinNativeTokenAmountWithoutTokenId,
+    #[error("address `{0}` is not an alias address")]
+    NotAnAliasAddress(String),
     #[error("invalid sort order provided: {0}")]
     SortOrder(#[from] ParseSortError),
+    #[error("address `{0}` is provided more than once")]
+    DuplicateAddress(String),
+    #[error("too many addresses provided: {requested}, the maximum is {max}")]
+    TooManyAddresses { requested: usize, max: usize },
+    #[error("milestone range is too large: {requested} milestones, the maximum is {max}")]
+    MilestoneRangeTooLarge { requested: u32, max: u32 },
+    #[error("too many output ids provided: {requested}, the maximum is {max}")]
+    TooManyOutputIds { requested: usize, max: usize },
+    #[error("query parameter `{parameter}` is not supported for {output_kind} outputs")]
+    UnsupportedQueryParameter {
+        parameter: String,
+        output_kind: &'static str,
+    },
+    #[error("unsupported output template provided: {0}")]
+    UnsupportedOutputTemplate(&'static str),
 }
 
 impl ErrorStatus for RequestError {
@@ -197,12 +304,24 @@ impl ErrorStatus for RequestError {
 pub enum ConfigError {
     #[error("invalid allow-origin header in config: {0}")]
     InvalidHeader(#[from] InvalidHeaderValue),
+    #[error("JWT issuer must not be empty")]
+    EmptyJwtIssuer,
     #[error("invalid hex value in config: {0}")]
     InvalidHex(#[from] hex::FromHexError),
     #[error("invalid regex in config: {0}")]
     InvalidRegex(#[from] regex::Error),
     #[error("invalid secret key: {0}")]
     SecretKey(#[from] super::secret_key::SecretKeyError),
+    #[error("invalid address `{0}` excluded from the richlist")]
+    InvalidRichlistExclusion(String),
+    #[error("invalid address denylist: {0}")]
+    AddressDenylist(#[from] super::denylist::AddressDenylistError),
+    #[error("the database contains {kind} `{found}`, but `{expected}` is configured")]
+    NetworkMismatch {
+        kind: &'static str,
+        expected: String,
+        found: String,
+    },
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -9,10 +9,11 @@ use axum::{
     Extension,
 };
 use chronicle::{
-    db::mongodb::collections::SortOrder,
+    db::mongodb::collections::{MilestoneFilter, SortOrder, UnlockConditionKind},
     model::{
         tangle::{MilestoneIndex, MilestoneTimestamp},
-        utxo::OutputId,
+        utxo::{AliasOutput, BasicOutput, FoundryOutput, NftOutput, OutputId, OutputIndex, TokenAmount},
+        BlockId,
     },
 };
 use serde::Deserialize;
@@ -24,6 +25,8 @@ pub struct LedgerUpdatesByAddressPagination {
     pub page_size: usize,
     pub sort: SortOrder,
     pub cursor: Option<(MilestoneIndex, Option<(OutputId, bool)>)>,
+    /// Whether the summary covers the whole history of the address instead of the returned page.
+    pub full_history_summary: bool,
 }
 
 #[derive(Clone, Deserialize, Default)]
@@ -33,6 +36,7 @@ pub struct LedgerUpdatesByAddressPaginationQuery {
     pub sort: Option<String>,
     pub start_milestone_index: Option<MilestoneIndex>,
     pub cursor: Option<String>,
+    pub full_history_summary: Option<bool>,
 }
 
 #[derive(Clone)]
@@ -106,12 +110,99 @@ impl<B: Send> FromRequest<B> for LedgerUpdatesByAddressPagination {
             page_size: page_size.min(config.max_page_size),
             cursor,
             sort,
+            full_history_summary: query.full_history_summary.unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpentOutputsByAddressPagination {
+    pub page_size: usize,
+    pub sort: SortOrder,
+    pub cursor: Option<(MilestoneIndex, Option<OutputId>)>,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct SpentOutputsByAddressPaginationQuery {
+    pub page_size: Option<usize>,
+    pub sort: Option<String>,
+    pub start_milestone_index: Option<MilestoneIndex>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct SpentOutputsByAddressCursor {
+    pub milestone_index: MilestoneIndex,
+    pub output_id: OutputId,
+    pub page_size: usize,
+}
+
+impl FromStr for SpentOutputsByAddressCursor {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('.').collect();
+        Ok(match parts[..] {
+            [ms, o, ps] => SpentOutputsByAddressCursor {
+                milestone_index: ms.parse().map_err(RequestError::from)?,
+                output_id: o.parse().map_err(RequestError::from)?,
+                page_size: ps.parse().map_err(RequestError::from)?,
+            },
+            _ => return Err(ApiError::from(RequestError::BadPagingState)),
+        })
+    }
+}
+
+impl Display for SpentOutputsByAddressCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            self.milestone_index,
+            self.output_id.to_hex(),
+            self.page_size
+        )
+    }
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for SpentOutputsByAddressPagination {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<SpentOutputsByAddressPaginationQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+
+        let sort = query
+            .sort
+            .as_deref()
+            .map_or(Ok(Default::default()), str::parse)
+            .map_err(RequestError::SortOrder)?;
+
+        let (page_size, cursor) = if let Some(cursor) = query.cursor {
+            let cursor: SpentOutputsByAddressCursor = cursor.parse()?;
+            (cursor.page_size, Some((cursor.milestone_index, Some(cursor.output_id))))
+        } else {
+            (
+                query.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+                query.start_milestone_index.map(|i| (i, None)),
+            )
+        };
+
+        Ok(SpentOutputsByAddressPagination {
+            page_size: page_size.min(config.max_page_size),
+            sort,
+            cursor,
         })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LedgerUpdatesByMilestonePagination {
+    pub output_kind: Option<&'static str>,
     pub page_size: usize,
     pub cursor: Option<(OutputId, bool)>,
 }
@@ -119,14 +210,33 @@ pub struct LedgerUpdatesByMilestonePagination {
 #[derive(Clone, Deserialize, Default)]
 #[serde(default, deny_unknown_fields, rename_all = "camelCase")]
 pub struct LedgerUpdatesByMilestonePaginationQuery {
+    pub output_kind: Option<String>,
     pub page_size: Option<usize>,
     pub cursor: Option<String>,
 }
 
+/// The kinds of outputs that can be owned by an address, and therefore appear in ledger updates.
+const LEDGER_UPDATE_OUTPUT_KINDS: &[&str] = &[
+    BasicOutput::KIND,
+    AliasOutput::KIND,
+    NftOutput::KIND,
+    FoundryOutput::KIND,
+];
+
+fn parse_ledger_update_output_kind(kind: &str) -> Result<&'static str, RequestError> {
+    LEDGER_UPDATE_OUTPUT_KINDS
+        .iter()
+        .find(|k| **k == kind)
+        .copied()
+        .ok_or_else(|| RequestError::InvalidOutputKind(kind.to_string()))
+}
+
 #[derive(Clone)]
 pub struct LedgerUpdatesByMilestoneCursor {
     pub output_id: OutputId,
     pub is_spent: bool,
+    /// The output kind filter of the first page, so that following pages are filtered the same way.
+    pub output_kind: Option<&'static str>,
     pub page_size: usize,
 }
 
@@ -139,6 +249,13 @@ impl FromStr for LedgerUpdatesByMilestoneCursor {
             [o, sp, ps] => LedgerUpdatesByMilestoneCursor {
                 output_id: o.parse().map_err(RequestError::from)?,
                 is_spent: sp.parse().map_err(RequestError::from)?,
+                output_kind: None,
+                page_size: ps.parse().map_err(RequestError::from)?,
+            },
+            [o, sp, k, ps] => LedgerUpdatesByMilestoneCursor {
+                output_id: o.parse().map_err(RequestError::from)?,
+                is_spent: sp.parse().map_err(RequestError::from)?,
+                output_kind: Some(parse_ledger_update_output_kind(k)?),
                 page_size: ps.parse().map_err(RequestError::from)?,
             },
             _ => return Err(ApiError::from(RequestError::BadPagingState)),
@@ -148,7 +265,11 @@ impl FromStr for LedgerUpdatesByMilestoneCursor {
 
 impl Display for LedgerUpdatesByMilestoneCursor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}.{}.{}", self.output_id.to_hex(), self.is_spent, self.page_size)
+        write!(f, "{}.{}.", self.output_id.to_hex(), self.is_spent)?;
+        if let Some(kind) = self.output_kind {
+            write!(f, "{kind}.")?;
+        }
+        write!(f, "{}", self.page_size)
     }
 }
 
@@ -162,14 +283,189 @@ impl<B: Send> FromRequest<B> for LedgerUpdatesByMilestonePagination {
             .map_err(RequestError::from)?;
         let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
 
-        let (page_size, cursor) = if let Some(cursor) = query.cursor {
+        let output_kind = query
+            .output_kind
+            .as_deref()
+            .map(parse_ledger_update_output_kind)
+            .transpose()?;
+
+        let (output_kind, page_size, cursor) = if let Some(cursor) = query.cursor {
             let cursor: LedgerUpdatesByMilestoneCursor = cursor.parse()?;
-            (cursor.page_size, Some((cursor.output_id, cursor.is_spent)))
+            // A cursor is only valid for the filter it was created with.
+            if output_kind.is_some() && output_kind != cursor.output_kind {
+                return Err(ApiError::from(RequestError::BadPagingState));
+            }
+            (
+                cursor.output_kind,
+                cursor.page_size,
+                Some((cursor.output_id, cursor.is_spent)),
+            )
         } else {
-            (query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
+            (output_kind, query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
         };
 
         Ok(LedgerUpdatesByMilestonePagination {
+            output_kind,
+            page_size: page_size.min(config.max_page_size),
+            cursor,
+        })
+    }
+}
+
+/// The largest number of milestones that a ledger diff can span.
+pub const MAX_LEDGER_DIFF_RANGE: u32 = 1_000;
+
+pub struct LedgerDiffPagination {
+    pub from: MilestoneIndex,
+    pub to: MilestoneIndex,
+    pub page_size: usize,
+    pub cursor: Option<OutputId>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct LedgerDiffPaginationQuery {
+    pub from: MilestoneIndex,
+    pub to: MilestoneIndex,
+    pub page_size: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct LedgerDiffCursor {
+    pub output_id: OutputId,
+    pub page_size: usize,
+}
+
+impl FromStr for LedgerDiffCursor {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('.').collect();
+        Ok(match parts[..] {
+            [o, ps] => LedgerDiffCursor {
+                output_id: o.parse().map_err(RequestError::from)?,
+                page_size: ps.parse().map_err(RequestError::from)?,
+            },
+            _ => return Err(ApiError::from(RequestError::BadPagingState)),
+        })
+    }
+}
+
+impl Display for LedgerDiffCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.output_id.to_hex(), self.page_size)
+    }
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for LedgerDiffPagination {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<LedgerDiffPaginationQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+
+        if query.from >= query.to {
+            return Err(ApiError::from(RequestError::BadMilestoneRange));
+        }
+        let requested = query.to.0 - query.from.0;
+        if requested > MAX_LEDGER_DIFF_RANGE {
+            return Err(ApiError::from(RequestError::MilestoneRangeTooLarge {
+                requested,
+                max: MAX_LEDGER_DIFF_RANGE,
+            }));
+        }
+
+        let (page_size, cursor) = if let Some(cursor) = query.cursor {
+            let cursor: LedgerDiffCursor = cursor.parse()?;
+            (cursor.page_size, Some(cursor.output_id))
+        } else {
+            (query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
+        };
+
+        Ok(LedgerDiffPagination {
+            from: query.from,
+            to: query.to,
+            page_size: page_size.min(config.max_page_size),
+            cursor,
+        })
+    }
+}
+
+/// Parses the name of an unlock condition, as used in the path of the outputs by condition endpoint.
+pub fn parse_unlock_condition_kind(name: &str) -> Result<UnlockConditionKind, RequestError> {
+    Ok(match name {
+        "address" => UnlockConditionKind::Address,
+        "storage_deposit_return" => UnlockConditionKind::StorageDepositReturn,
+        "timelock" => UnlockConditionKind::Timelock,
+        "expiration" => UnlockConditionKind::Expiration,
+        "state_controller_address" => UnlockConditionKind::StateControllerAddress,
+        "governor_address" => UnlockConditionKind::GovernorAddress,
+        "immutable_alias_address" => UnlockConditionKind::ImmutableAliasAddress,
+        _ => return Err(RequestError::InvalidUnlockCondition(name.to_string())),
+    })
+}
+
+pub struct OutputsByConditionPagination {
+    pub page_size: usize,
+    pub cursor: Option<OutputId>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct OutputsByConditionPaginationQuery {
+    pub page_size: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct OutputsByConditionCursor {
+    pub output_id: OutputId,
+    pub page_size: usize,
+}
+
+impl FromStr for OutputsByConditionCursor {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('.').collect();
+        Ok(match parts[..] {
+            [o, ps] => OutputsByConditionCursor {
+                output_id: o.parse().map_err(RequestError::from)?,
+                page_size: ps.parse().map_err(RequestError::from)?,
+            },
+            _ => return Err(ApiError::from(RequestError::BadPagingState)),
+        })
+    }
+}
+
+impl Display for OutputsByConditionCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.output_id.to_hex(), self.page_size)
+    }
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for OutputsByConditionPagination {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<OutputsByConditionPaginationQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+
+        let (page_size, cursor) = if let Some(cursor) = query.cursor {
+            let cursor: OutputsByConditionCursor = cursor.parse()?;
+            (cursor.page_size, Some(cursor.output_id))
+        } else {
+            (query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
+        };
+
+        Ok(OutputsByConditionPagination {
             page_size: page_size.min(config.max_page_size),
             cursor,
         })
@@ -179,6 +475,7 @@ impl<B: Send> FromRequest<B> for LedgerUpdatesByMilestonePagination {
 pub struct MilestonesPagination {
     pub start_timestamp: Option<MilestoneTimestamp>,
     pub end_timestamp: Option<MilestoneTimestamp>,
+    pub filter: MilestoneFilter,
     pub sort: SortOrder,
     pub page_size: usize,
     pub cursor: Option<MilestoneIndex>,
@@ -189,6 +486,8 @@ pub struct MilestonesPagination {
 pub struct MilestonesPaginationQuery {
     pub start_timestamp: Option<u32>,
     pub end_timestamp: Option<u32>,
+    pub has_receipt: Option<bool>,
+    pub min_signatures: Option<usize>,
     pub sort: Option<String>,
     pub page_size: Option<usize>,
     pub cursor: Option<String>,
@@ -241,67 +540,262 @@ impl<B: Send> FromRequest<B> for MilestonesPagination {
             .map_or(Ok(Default::default()), str::parse)
             .map_err(RequestError::SortOrder)?;
 
-        let (page_size, cursor) = if let Some(cursor) = query.cursor {
-            let cursor: MilestonesCursor = cursor.parse()?;
-            (cursor.page_size, Some(cursor.milestone_index))
+        let (page_size, cursor) = if let Some(cursor) = query.cursor {
+            let cursor: MilestonesCursor = cursor.parse()?;
+            (cursor.page_size, Some(cursor.milestone_index))
+        } else {
+            (query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
+        };
+
+        Ok(MilestonesPagination {
+            start_timestamp: query.start_timestamp.map(Into::into),
+            end_timestamp: query.end_timestamp.map(Into::into),
+            filter: MilestoneFilter {
+                has_receipt: query.has_receipt,
+                min_signatures: query.min_signatures,
+            },
+            sort,
+            page_size: page_size.min(config.max_page_size),
+            cursor,
+        })
+    }
+}
+
+const DEFAULT_TOP_RICHLIST: usize = 100;
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RichestAddressesQuery {
+    pub top: usize,
+    pub ledger_index: Option<MilestoneIndex>,
+}
+
+impl Default for RichestAddressesQuery {
+    fn default() -> Self {
+        Self {
+            top: DEFAULT_TOP_RICHLIST,
+            ledger_index: None,
+        }
+    }
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for RichestAddressesQuery {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(mut query) = Query::<RichestAddressesQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+        query.top = query.top.min(config.max_page_size);
+        Ok(query)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaturingOutputsPagination {
+    pub page_size: usize,
+    pub cursor: Option<(MilestoneTimestamp, OutputId)>,
+    /// Only outputs whose timelock expires at or after this time are returned. Defaults to the time of the ledger
+    /// index, so that only outputs that are still locked are returned.
+    pub unlockable_after: Option<MilestoneTimestamp>,
+    /// Only outputs whose timelock expires before this time are returned.
+    pub unlockable_before: Option<MilestoneTimestamp>,
+    pub ledger_index: Option<MilestoneIndex>,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct MaturingOutputsPaginationQuery {
+    pub page_size: Option<usize>,
+    pub cursor: Option<String>,
+    pub unlockable_after: Option<MilestoneTimestamp>,
+    pub unlockable_before: Option<MilestoneTimestamp>,
+    pub ledger_index: Option<MilestoneIndex>,
+}
+
+#[derive(Clone)]
+pub struct MaturingOutputsCursor {
+    pub unlockable_at: MilestoneTimestamp,
+    pub output_id: OutputId,
+    pub page_size: usize,
+}
+
+impl FromStr for MaturingOutputsCursor {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('.').collect();
+        Ok(match parts[..] {
+            [t, o, ps] => MaturingOutputsCursor {
+                unlockable_at: t.parse::<u32>().map_err(RequestError::from)?.into(),
+                output_id: o.parse().map_err(RequestError::from)?,
+                page_size: ps.parse().map_err(RequestError::from)?,
+            },
+            _ => return Err(ApiError::from(RequestError::BadPagingState)),
+        })
+    }
+}
+
+impl Display for MaturingOutputsCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            self.unlockable_at.0,
+            self.output_id.to_hex(),
+            self.page_size
+        )
+    }
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for MaturingOutputsPagination {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<MaturingOutputsPaginationQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+
+        let (page_size, cursor) = if let Some(cursor) = query.cursor {
+            let cursor: MaturingOutputsCursor = cursor.parse()?;
+            (cursor.page_size, Some((cursor.unlockable_at, cursor.output_id)))
+        } else {
+            (query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
+        };
+
+        Ok(MaturingOutputsPagination {
+            page_size: page_size.min(config.max_page_size),
+            cursor,
+            unlockable_after: query.unlockable_after,
+            unlockable_before: query.unlockable_before,
+            ledger_index: query.ledger_index,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LargestOutputsPagination {
+    pub limit: usize,
+    pub cursor: Option<(TokenAmount, OutputId)>,
+    pub ledger_index: Option<MilestoneIndex>,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct LargestOutputsPaginationQuery {
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+    pub ledger_index: Option<MilestoneIndex>,
+}
+
+#[derive(Clone)]
+pub struct LargestOutputsCursor {
+    pub amount: TokenAmount,
+    pub output_id: OutputId,
+    pub limit: usize,
+}
+
+impl FromStr for LargestOutputsCursor {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('.').collect();
+        Ok(match parts[..] {
+            [a, o, l] => LargestOutputsCursor {
+                amount: TokenAmount(a.parse().map_err(RequestError::from)?),
+                output_id: o.parse().map_err(RequestError::from)?,
+                limit: l.parse().map_err(RequestError::from)?,
+            },
+            _ => return Err(ApiError::from(RequestError::BadPagingState)),
+        })
+    }
+}
+
+impl Display for LargestOutputsCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.amount.0, self.output_id.to_hex(), self.limit)
+    }
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for LargestOutputsPagination {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<LargestOutputsPaginationQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+
+        let (limit, cursor) = if let Some(cursor) = query.cursor {
+            let cursor: LargestOutputsCursor = cursor.parse()?;
+            (cursor.limit, Some((cursor.amount, cursor.output_id)))
         } else {
-            (query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
+            (query.limit.unwrap_or(DEFAULT_PAGE_SIZE), None)
         };
 
-        Ok(MilestonesPagination {
-            start_timestamp: query.start_timestamp.map(Into::into),
-            end_timestamp: query.end_timestamp.map(Into::into),
-            sort,
-            page_size: page_size.min(config.max_page_size),
+        Ok(LargestOutputsPagination {
+            limit: limit.min(config.max_page_size),
             cursor,
+            ledger_index: query.ledger_index,
         })
     }
 }
 
-const DEFAULT_TOP_RICHLIST: usize = 100;
-
-#[derive(Clone, Deserialize)]
-#[serde(default, deny_unknown_fields)]
-pub struct RichestAddressesQuery {
-    pub top: usize,
+#[derive(Copy, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct LedgerIndex {
     pub ledger_index: Option<MilestoneIndex>,
 }
 
-impl Default for RichestAddressesQuery {
-    fn default() -> Self {
-        Self {
-            top: DEFAULT_TOP_RICHLIST,
-            ledger_index: None,
-        }
+#[async_trait]
+impl<B: Send> FromRequest<B> for LedgerIndex {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<LedgerIndex>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        Ok(query)
     }
 }
 
+#[derive(Copy, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct BalanceQuery {
+    /// Whether to include the amounts of native tokens held by the address.
+    pub native_tokens: bool,
+}
+
 #[async_trait]
-impl<B: Send> FromRequest<B> for RichestAddressesQuery {
+impl<B: Send> FromRequest<B> for BalanceQuery {
     type Rejection = ApiError;
 
     async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let Query(mut query) = Query::<RichestAddressesQuery>::from_request(req)
+        let Query(query) = Query::<BalanceQuery>::from_request(req)
             .await
             .map_err(RequestError::from)?;
-        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
-        query.top = query.top.min(config.max_page_size);
         Ok(query)
     }
 }
 
 #[derive(Copy, Clone, Deserialize, Default)]
 #[serde(default, deny_unknown_fields, rename_all = "camelCase")]
-pub struct LedgerIndex {
-    pub ledger_index: Option<MilestoneIndex>,
+pub struct HistorySizeQuery {
+    /// Whether to count all ledger updates instead of stopping at the estimate limit.
+    pub exact: bool,
 }
 
 #[async_trait]
-impl<B: Send> FromRequest<B> for LedgerIndex {
+impl<B: Send> FromRequest<B> for HistorySizeQuery {
     type Rejection = ApiError;
 
     async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let Query(query) = Query::<LedgerIndex>::from_request(req)
+        let Query(query) = Query::<HistorySizeQuery>::from_request(req)
             .await
             .map_err(RequestError::from)?;
         Ok(query)
@@ -447,6 +941,204 @@ impl<B: Send> FromRequest<B> for BlocksByMilestoneIdPagination {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionOutputsPagination {
+    pub page_size: usize,
+    pub cursor: Option<OutputIndex>,
+    pub include_outputs: bool,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct TransactionOutputsPaginationQuery {
+    pub page_size: Option<usize>,
+    pub cursor: Option<String>,
+    pub include_outputs: bool,
+}
+
+#[derive(Clone)]
+pub struct TransactionOutputsCursor {
+    pub output_index: OutputIndex,
+    pub page_size: usize,
+}
+
+impl FromStr for TransactionOutputsCursor {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('.').collect();
+        Ok(match parts[..] {
+            [i, ps] => TransactionOutputsCursor {
+                output_index: i.parse().map_err(RequestError::from)?,
+                page_size: ps.parse().map_err(RequestError::from)?,
+            },
+            _ => return Err(ApiError::from(RequestError::BadPagingState)),
+        })
+    }
+}
+
+impl Display for TransactionOutputsCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.output_index, self.page_size)
+    }
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for TransactionOutputsPagination {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<TransactionOutputsPaginationQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+
+        let (page_size, cursor) = if let Some(cursor) = query.cursor {
+            let cursor: TransactionOutputsCursor = cursor.parse()?;
+            (cursor.page_size, Some(cursor.output_index))
+        } else {
+            (query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
+        };
+
+        Ok(TransactionOutputsPagination {
+            page_size: page_size.min(config.max_page_size),
+            cursor,
+            include_outputs: query.include_outputs,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionBlocksPagination {
+    pub page_size: usize,
+    pub cursor: Option<BlockId>,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct TransactionBlocksPaginationQuery {
+    pub page_size: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct TransactionBlocksCursor {
+    pub block_id: BlockId,
+    pub page_size: usize,
+}
+
+impl FromStr for TransactionBlocksCursor {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('.').collect();
+        Ok(match parts[..] {
+            [b, ps] => TransactionBlocksCursor {
+                block_id: b.parse().map_err(RequestError::from)?,
+                page_size: ps.parse().map_err(RequestError::from)?,
+            },
+            _ => return Err(ApiError::from(RequestError::BadPagingState)),
+        })
+    }
+}
+
+impl Display for TransactionBlocksCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.block_id.to_hex(), self.page_size)
+    }
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for TransactionBlocksPagination {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<TransactionBlocksPaginationQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+
+        let (page_size, cursor) = if let Some(cursor) = query.cursor {
+            let cursor: TransactionBlocksCursor = cursor.parse()?;
+            (cursor.page_size, Some(cursor.block_id))
+        } else {
+            (query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
+        };
+
+        Ok(TransactionBlocksPagination {
+            page_size: page_size.min(config.max_page_size),
+            cursor,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MilestoneOutputsPagination {
+    pub page_size: usize,
+    pub cursor: Option<OutputId>,
+    pub include_outputs: bool,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct MilestoneOutputsPaginationQuery {
+    pub page_size: Option<usize>,
+    pub cursor: Option<String>,
+    pub include_outputs: bool,
+}
+
+#[derive(Clone)]
+pub struct MilestoneOutputsCursor {
+    pub output_id: OutputId,
+    pub page_size: usize,
+}
+
+impl FromStr for MilestoneOutputsCursor {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('.').collect();
+        Ok(match parts[..] {
+            [o, ps] => MilestoneOutputsCursor {
+                output_id: o.parse().map_err(RequestError::from)?,
+                page_size: ps.parse().map_err(RequestError::from)?,
+            },
+            _ => return Err(ApiError::from(RequestError::BadPagingState)),
+        })
+    }
+}
+
+impl Display for MilestoneOutputsCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.output_id.to_hex(), self.page_size)
+    }
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for MilestoneOutputsPagination {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<MilestoneOutputsPaginationQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
+
+        let (page_size, cursor) = if let Some(cursor) = query.cursor {
+            let cursor: MilestoneOutputsCursor = cursor.parse()?;
+            (cursor.page_size, Some(cursor.output_id))
+        } else {
+            (query.page_size.unwrap_or(DEFAULT_PAGE_SIZE), None)
+        };
+
+        Ok(MilestoneOutputsPagination {
+            page_size: page_size.min(config.max_page_size),
+            cursor,
+            include_outputs: query.include_outputs,
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use axum::{extract::RequestParts, http::Request};
@@ -475,6 +1167,97 @@ mod test {
         let cursor = format!("{output_id_str}.{is_spent_str}.{page_size_str}",);
         let parsed: LedgerUpdatesByMilestoneCursor = cursor.parse().unwrap();
         assert_eq!(parsed.to_string(), cursor);
+
+        let cursor = format!("{output_id_str}.{is_spent_str}.nft.{page_size_str}",);
+        let parsed: LedgerUpdatesByMilestoneCursor = cursor.parse().unwrap();
+        assert_eq!(parsed.output_kind, Some("nft"));
+        assert_eq!(parsed.to_string(), cursor);
+    }
+
+    #[test]
+    fn ledger_diff_cursor_from_to_str() {
+        let output_id_str = "0xfa0de75d225cca2799395e5fc340702fc7eac821d2bdd79911126f131ae097a20100";
+        let page_size_str = "1337";
+
+        let cursor = format!("{output_id_str}.{page_size_str}",);
+        let parsed: LedgerDiffCursor = cursor.parse().unwrap();
+        assert_eq!(parsed.to_string(), cursor);
+    }
+
+    #[test]
+    fn outputs_by_condition_cursor_from_to_str() {
+        let output_id_str = "0xfa0de75d225cca2799395e5fc340702fc7eac821d2bdd79911126f131ae097a20100";
+        let cursor = format!("{output_id_str}.1337");
+        let parsed: OutputsByConditionCursor = cursor.parse().unwrap();
+        assert_eq!(parsed.page_size, 1337);
+        assert_eq!(parsed.to_string(), cursor);
+    }
+
+    #[test]
+    fn unlock_condition_kind_parsed() {
+        assert_eq!(
+            parse_unlock_condition_kind("storage_deposit_return").unwrap(),
+            UnlockConditionKind::StorageDepositReturn
+        );
+        assert_eq!(
+            parse_unlock_condition_kind("governor_address").unwrap(),
+            UnlockConditionKind::GovernorAddress
+        );
+        assert!(matches!(
+            parse_unlock_condition_kind("storageDepositReturn"),
+            Err(RequestError::InvalidUnlockCondition(_))
+        ));
+    }
+
+    #[test]
+    fn spent_outputs_by_address_cursor_from_to_str() {
+        let output_id_str = "0xfa0de75d225cca2799395e5fc340702fc7eac821d2bdd79911126f131ae097a20100";
+        let cursor = format!("164338324.{output_id_str}.1337");
+        let parsed: SpentOutputsByAddressCursor = cursor.parse().unwrap();
+        assert_eq!(parsed.milestone_index, 164338324);
+        assert_eq!(parsed.to_string(), cursor);
+    }
+
+    #[test]
+    fn largest_outputs_cursor_from_to_str() {
+        let output_id_str = "0xfa0de75d225cca2799395e5fc340702fc7eac821d2bdd79911126f131ae097a20100";
+        let cursor = format!("1000000.{output_id_str}.50");
+        let parsed: LargestOutputsCursor = cursor.parse().unwrap();
+        assert_eq!(parsed.amount, TokenAmount(1000000));
+        assert_eq!(parsed.to_string(), cursor);
+    }
+
+    #[test]
+    fn maturing_outputs_cursor_from_to_str() {
+        let output_id_str = "0xfa0de75d225cca2799395e5fc340702fc7eac821d2bdd79911126f131ae097a20100";
+        let cursor = format!("1700000000.{output_id_str}.50");
+        let parsed: MaturingOutputsCursor = cursor.parse().unwrap();
+        assert_eq!(parsed.unlockable_at, 1700000000.into());
+        assert_eq!(parsed.to_string(), cursor);
+    }
+
+    #[test]
+    fn transaction_outputs_cursor_from_to_str() {
+        let cursor = "3.50";
+        let parsed: TransactionOutputsCursor = cursor.parse().unwrap();
+        assert_eq!(parsed.output_index, 3);
+        assert_eq!(parsed.to_string(), cursor);
+    }
+
+    #[test]
+    fn transaction_blocks_cursor_from_to_str() {
+        let cursor = "0xf532a53545103276b46876c473846d98648ee418468bce76df4868648dd73e5d.50";
+        let parsed: TransactionBlocksCursor = cursor.parse().unwrap();
+        assert_eq!(parsed.page_size, 50);
+        assert_eq!(parsed.to_string(), cursor);
+    }
+
+    #[test]
+    fn milestone_outputs_cursor_from_to_str() {
+        let cursor = "0xfa0de75d225cca2799395e5fc340702fc7eac821d2bdd79911126f131ae097a20100.50";
+        let parsed: MilestoneOutputsCursor = cursor.parse().unwrap();
+        assert_eq!(parsed.page_size, 50);
+        assert_eq!(parsed.to_string(), cursor);
     }
 
     #[tokio::test]
@@ -492,7 +1275,8 @@ mod test {
             LedgerUpdatesByAddressPagination {
                 page_size: 1000,
                 sort: Default::default(),
-                cursor: Default::default()
+                cursor: Default::default(),
+                full_history_summary: Default::default(),
             }
         );
 
@@ -509,9 +1293,31 @@ mod test {
                 .await
                 .unwrap(),
             LedgerUpdatesByMilestonePagination {
+                output_kind: None,
                 page_size: 1000,
                 cursor: Default::default()
             }
         );
     }
+
+    #[tokio::test]
+    async fn ledger_diff_range_checked() {
+        async fn extract(query: &str) -> Result<LedgerDiffPagination, ApiError> {
+            let mut req = RequestParts::new(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/ledger/diff?{query}"))
+                    .extension(ApiConfigData::try_from(ApiConfig::default()).unwrap())
+                    .body(())
+                    .unwrap(),
+            );
+            LedgerDiffPagination::from_request(&mut req).await
+        }
+
+        let pagination = extract("from=10&to=1010").await.unwrap();
+        assert_eq!((pagination.from, pagination.to), (10.into(), 1010.into()));
+        assert!(extract("from=10&to=1011").await.is_err());
+        assert!(extract("from=10&to=10").await.is_err());
+        assert!(extract("from=10").await.is_err());
+    }
 }
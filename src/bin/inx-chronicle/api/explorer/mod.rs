@@ -5,4 +5,4 @@ mod extractors;
 mod responses;
 mod routes;
 
-pub use self::routes::routes;
+pub use self::{responses::AMOUNT_FORMAT, routes::routes};
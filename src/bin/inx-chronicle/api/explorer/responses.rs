@@ -1,31 +1,92 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::ops::Range;
+use std::{
+    collections::BTreeMap,
+    ops::{Range, RangeInclusive},
+};
 
 use chronicle::{
     db::mongodb::collections::{
-        DistributionStat, LedgerUpdateByAddressRecord, LedgerUpdateByMilestoneRecord, MilestoneResult,
+        DistributionStat, LedgerDiffRecord, LedgerUpdateByAddressRecord,
+        LedgerUpdateByMilestoneRecord, MilestoneResult, SpentOutputResult,
     },
     model::{
         tangle::{MilestoneIndex, MilestoneTimestamp},
-        utxo::Address,
+        utxo::{Address, Feature, Output},
     },
 };
-use serde::{Deserialize, Serialize};
+use iota_types::{api::core::response::OutputWithMetadataResponse, block::protocol::dto::ProtocolParametersDto};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::api::{
+    config::AmountFormat,
+    responses::{impl_success_response, NftDetailsDto},
+};
+
+tokio::task_local! {
+    /// The format of the token amounts in the response to the current request.
+    pub static AMOUNT_FORMAT: AmountFormat;
+}
+
+/// A decimal token amount, which is represented as a string or as a number depending on the [`AMOUNT_FORMAT`] of the
+/// current request. Strings are used if no format is set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AmountDto(String);
+
+impl From<String> for AmountDto {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
 
-use crate::api::responses::impl_success_response;
+impl Serialize for AmountDto {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if AMOUNT_FORMAT.try_with(|format| *format) == Ok(AmountFormat::Number) {
+            if let Ok(n) = self.0.parse::<u64>() {
+                return serializer.serialize_u64(n);
+            } else if let Ok(n) = self.0.parse::<i64>() {
+                return serializer.serialize_i64(n);
+            }
+        }
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for AmountDto {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            String(String),
+            Number(serde_json::Number),
+        }
+        Ok(Self(match Repr::deserialize(deserializer)? {
+            Repr::String(s) => s,
+            Repr::Number(n) => n.to_string(),
+        }))
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LedgerUpdatesByAddressResponse {
     pub address: String,
+    pub summary: LedgerUpdatesSummaryDto,
     pub items: Vec<LedgerUpdateByAddressDto>,
     pub cursor: Option<String>,
 }
 
 impl_success_response!(LedgerUpdatesByAddressResponse);
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerUpdatesSummaryDto {
+    pub update_count: usize,
+    pub net_balance_change: AmountDto,
+    pub full_history: bool,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LedgerUpdateByAddressDto {
@@ -74,16 +135,193 @@ impl From<LedgerUpdateByMilestoneRecord> for LedgerUpdateByMilestoneDto {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerDiffResponse {
+    pub from: MilestoneIndex,
+    pub to: MilestoneIndex,
+    pub items: Vec<LedgerDiffDto>,
+    pub cursor: Option<String>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(LedgerDiffResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerDiffDto {
+    pub output_id: String,
+    pub address: Address,
+    pub is_spent: bool,
+}
+
+impl From<LedgerDiffRecord> for LedgerDiffDto {
+    fn from(value: LedgerDiffRecord) -> Self {
+        Self {
+            output_id: value.output_id.to_hex(),
+            address: value.address,
+            is_spent: value.is_spent,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatestMilestoneResponse {
+    pub milestone_id: String,
+    pub index: MilestoneIndex,
+    pub timestamp: MilestoneTimestamp,
+}
+
+impl_success_response!(LatestMilestoneResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneReceiptResponse {
+    pub milestone_index: MilestoneIndex,
+    /// The index of the legacy milestone in which the funds were migrated.
+    pub migrated_at: MilestoneIndex,
+    /// Whether this is the last receipt for `migrated_at`.
+    pub last: bool,
+    pub funds: Vec<MigratedFundsEntryDto>,
+    pub transaction: TreasuryTransactionDto,
+}
+
+impl_success_response!(MilestoneReceiptResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigratedFundsEntryDto {
+    pub tail_transaction_hash: String,
+    pub address: String,
+    pub deposit: AmountDto,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreasuryTransactionDto {
+    pub input_milestone_id: String,
+    pub output_amount: AmountDto,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BalanceResponse {
-    pub total_balance: String,
-    pub sig_locked_balance: String,
+    pub total_balance: AmountDto,
+    pub sig_locked_balance: AmountDto,
+    /// The total amount held of each native token, keyed by token id. Only present if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub native_tokens: Option<BTreeMap<String, String>>,
+    /// Whether the address owns more outputs than are read for a single request, so that the balance is incomplete.
+    pub truncated: bool,
     pub ledger_index: MilestoneIndex,
 }
 
 impl_success_response!(BalanceResponse);
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBalanceDto {
+    pub total_balance: AmountDto,
+    pub sig_locked_balance: AmountDto,
+    /// Whether the address owns more outputs than are read for a single request, so that the balance is incomplete.
+    pub truncated: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalancesResponse {
+    /// The balances of the requested addresses, keyed by address. Addresses without outputs have a zero balance.
+    pub balances: BTreeMap<String, AddressBalanceDto>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(BalancesResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChangedSinceResponse {
+    pub changed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_balance: Option<AmountDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sig_locked_balance: Option<AmountDto>,
+    /// Whether the address owns more outputs than are read for a single request, so that the balance is incomplete.
+    pub truncated: bool,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(BalanceChangedSinceResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressSummaryResponse {
+    pub address: String,
+    pub basic_outputs: usize,
+    pub alias_outputs: usize,
+    pub nft_outputs: usize,
+    pub foundry_outputs: usize,
+    pub total_balance: AmountDto,
+    /// Whether the address owns more outputs than are read for a single request, so that the summary is incomplete.
+    pub truncated: bool,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(AddressSummaryResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySizeResponse {
+    pub address: String,
+    pub count: usize,
+    /// Whether `count` is the exact number of ledger updates, or a lower bound.
+    pub exact: bool,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(HistorySizeResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpentOutputsByAddressResponse {
+    pub address: String,
+    pub items: Vec<SpentOutputDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(SpentOutputsByAddressResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpentOutputDto {
+    pub output_id: String,
+    pub output: iota_types::block::output::dto::OutputDto,
+    pub booked_milestone_index: MilestoneIndex,
+    pub spending_transaction_id: String,
+    pub spent_milestone_index: MilestoneIndex,
+    pub spent_milestone_timestamp: MilestoneTimestamp,
+    /// The decoded immutable features, only included for NFT outputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft: Option<NftDetailsDto>,
+}
+
+impl SpentOutputDto {
+    /// Creates the DTO of a spent output, encoding the NFT issuer with the given bech32 HRP.
+    pub fn new(res: SpentOutputResult, hrp: &str) -> Self {
+        Self {
+            output_id: res.output_id.to_hex(),
+            nft: NftDetailsDto::from_output(&res.output, hrp),
+            output: res.output.into(),
+            booked_milestone_index: res.booked.milestone_index,
+            spending_transaction_id: res.spent_metadata.transaction_id.to_hex(),
+            spent_milestone_index: res.spent_metadata.spent.milestone_index,
+            spent_milestone_timestamp: res.spent_metadata.spent.milestone_timestamp,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockChildrenResponse {
@@ -95,6 +333,33 @@ pub struct BlockChildrenResponse {
 
 impl_success_response!(BlockChildrenResponse);
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockConfirmationState {
+    /// The block was referenced by a milestone and did not conflict.
+    Confirmed,
+    /// The block was referenced by a milestone but its transaction conflicted.
+    Conflicting,
+    /// The block was seen, but its referencing milestone has not been synced yet, or no milestone has referenced it.
+    Pending,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockConfirmationResponse {
+    pub block_id: String,
+    pub state: BlockConfirmationState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referenced_by_milestone_index: Option<MilestoneIndex>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict_reason: Option<u8>,
+    /// Whether the block is only stored because unreferenced blocks are kept, i.e. no synced milestone references it.
+    pub unreferenced: bool,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(BlockConfirmationResponse);
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MilestonesResponse {
@@ -137,19 +402,217 @@ impl From<MilestoneResult> for MilestoneDto {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedOutputResponse {
+    pub output_id: String,
+    pub output: NormalizedOutputDto,
+    /// The decoded immutable features, only included for NFT outputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft: Option<NftDetailsDto>,
+    pub is_spent: bool,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(NormalizedOutputResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputBlockResponse {
+    pub output_id: String,
+    pub block_id: String,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(OutputBlockResponse);
+
+/// An output with its unlock conditions and features flattened into named fields, so that its shape does not depend
+/// on the output kind or on how outputs are stored.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedOutputDto {
+    pub kind: String,
+    pub amount: AmountDto,
+    pub native_token_count: usize,
+    /// The address in control of the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timelock_until: Option<MilestoneTimestamp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<ExpirationDto>,
+    /// The amount that has to be returned by a storage deposit return unlock condition.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdr_amount: Option<AmountDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdr_return_address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<Address>,
+    /// The hex encoded tag feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// The hex encoded metadata feature. Immutable metadata takes precedence.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpirationDto {
+    pub return_address: Address,
+    pub timestamp: MilestoneTimestamp,
+}
+
+impl From<Output> for NormalizedOutputDto {
+    fn from(output: Output) -> Self {
+        let mut res = Self {
+            kind: output.kind().to_string(),
+            amount: output.amount().0.to_string().into(),
+            address: output.owning_address().copied(),
+            ..Default::default()
+        };
+        let (native_tokens, timelock, expiration, sdruc, features, immutable_features) = match &output {
+            Output::Treasury(_) => return res,
+            Output::Basic(basic) => (
+                &basic.native_tokens,
+                basic.timelock_unlock_condition,
+                basic.expiration_unlock_condition,
+                basic.storage_deposit_return_unlock_condition,
+                &basic.features,
+                None,
+            ),
+            Output::Alias(alias) => (
+                &alias.native_tokens,
+                None,
+                None,
+                None,
+                &alias.features,
+                Some(&alias.immutable_features),
+            ),
+            Output::Foundry(foundry) => (
+                &foundry.native_tokens,
+                None,
+                None,
+                None,
+                &foundry.features,
+                Some(&foundry.immutable_features),
+            ),
+            Output::Nft(nft) => (
+                &nft.native_tokens,
+                nft.timelock_unlock_condition,
+                nft.expiration_unlock_condition,
+                nft.storage_deposit_return_unlock_condition,
+                &nft.features,
+                Some(&nft.immutable_features),
+            ),
+        };
+        res.native_token_count = native_tokens.len();
+        res.timelock_until = timelock.map(|uc| uc.timestamp);
+        res.expiration = expiration.map(|uc| ExpirationDto {
+            return_address: uc.return_address,
+            timestamp: uc.timestamp,
+        });
+        res.sdr_amount = sdruc.map(|uc| uc.amount.0.to_string().into());
+        res.sdr_return_address = sdruc.map(|uc| uc.return_address);
+        // Immutable features come last, so that they take precedence.
+        for feature in features
+            .iter()
+            .chain(immutable_features.into_iter().flat_map(|f| f.iter()))
+        {
+            match feature {
+                Feature::Sender { address } => res.sender = Some(*address),
+                Feature::Issuer { address } => res.issuer = Some(*address),
+                Feature::Metadata { data } => res.metadata = Some(prefix_hex::encode(data.as_ref())),
+                Feature::Tag { data } => res.tag = Some(prefix_hex::encode(data.as_ref())),
+            }
+        }
+        res
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolParametersResponse {
+    pub parameters: ProtocolParametersDto,
+    /// The milestone index at which the parameters became active.
+    pub activated_at: MilestoneIndex,
+    pub milestone_index: MilestoneIndex,
+}
+
+impl_success_response!(ProtocolParametersResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitteeResponse {
+    /// The public keys that signed the milestone, as hex strings.
+    pub public_keys: Vec<String>,
+    /// The number of signatures that a milestone requires.
+    pub threshold: u32,
+    /// The milestone index at which the newest of the keys became active.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_since: Option<MilestoneIndex>,
+    /// The milestone index at which the committee was observed.
+    pub observed_at: MilestoneIndex,
+}
+
+impl_success_response!(CommitteeResponse);
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RichestAddressesResponse {
     pub top: Vec<AddressStatDto>,
+    /// The addresses that were left out of the richlist.
+    pub excluded_addresses: Vec<String>,
     pub ledger_index: MilestoneIndex,
 }
 
 impl_success_response!(RichestAddressesResponse);
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LargestOutputsResponse {
+    pub outputs: Vec<LargestOutputDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(LargestOutputsResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LargestOutputDto {
+    pub output_id: String,
+    pub amount: AmountDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaturingOutputsResponse {
+    pub outputs: Vec<MaturingOutputDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(MaturingOutputsResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaturingOutputDto {
+    pub output_id: String,
+    /// The unix time at which the timelock of the output expires.
+    pub unlockable_at: u32,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AddressStatDto {
     pub address: String,
-    pub balance: String,
+    pub balance: AmountDto,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -161,20 +624,230 @@ pub struct TokenDistributionResponse {
 
 impl_success_response!(TokenDistributionResponse);
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageDepositResponse {
+    pub storage_deposit: AmountDto,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(StorageDepositResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputsByIdsResponse {
+    /// The requested outputs with their metadata, keyed by output id.
+    pub outputs: BTreeMap<String, OutputWithMetadataResponse>,
+    /// The decoded immutable features of the requested NFT outputs, keyed by output id.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub nfts: BTreeMap<String, NftDetailsDto>,
+    /// The requested output ids for which no output was found.
+    pub missing: Vec<String>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(OutputsByIdsResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputsMetadataByIdsResponse {
+    /// The metadata of the requested outputs, keyed by output id.
+    pub metadata: BTreeMap<String, crate::api::core::OutputMetadataResponse>,
+    /// The requested output ids for which no output was found.
+    pub missing: Vec<String>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(OutputsMetadataByIdsResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputsByConditionResponse {
+    pub condition: String,
+    /// The ids of the unspent outputs that carry the unlock condition.
+    pub items: Vec<String>,
+    pub cursor: Option<String>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(OutputsByConditionResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatusResponse {
+    /// The contiguous ranges of milestones stored in the database.
+    pub completed: Vec<RangeInclusive<MilestoneIndex>>,
+    /// The ranges of milestones missing from the database.
+    pub gaps: Vec<RangeInclusive<MilestoneIndex>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_confirmed_index: Option<MilestoneIndex>,
+    pub sync_percentage: f64,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(SyncStatusResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenIssuerResponse {
+    pub token_id: String,
+    pub foundry_output_id: String,
+    pub alias_id: String,
+    pub alias_output_id: String,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(TokenIssuerResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftAddressResponse {
+    pub nft_id: String,
+    /// The bech32 NFT address derived from the id.
+    pub address: String,
+}
+
+impl_success_response!(NftAddressResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasAddressResponse {
+    pub alias_id: String,
+    /// The bech32 alias address derived from the id.
+    pub address: String,
+}
+
+impl_success_response!(AliasAddressResponse);
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DistributionStatDto {
-    pub range: Range<u64>,
+    pub range: Range<AmountDto>,
     pub address_count: String,
-    pub total_balance: String,
+    pub total_balance: AmountDto,
 }
 
 impl From<DistributionStat> for DistributionStatDto {
     fn from(s: DistributionStat) -> Self {
         Self {
-            range: 10_u64.pow(s.index)..10_u64.pow(s.index + 1),
+            range: 10_u64.pow(s.index).to_string().into()..10_u64.pow(s.index + 1).to_string().into(),
             address_count: s.address_count.to_string(),
-            total_balance: s.total_balance,
+            total_balance: s.total_balance.into(),
         }
     }
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneOutputsResponse {
+    pub milestone_index: MilestoneIndex,
+    pub items: Vec<MilestoneOutputDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl_success_response!(MilestoneOutputsResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneOutputDto {
+    pub output_id: String,
+    /// The output itself, only included if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<iota_types::block::output::dto::OutputDto>,
+    /// The decoded immutable features, only included along with NFT outputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft: Option<NftDetailsDto>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionOutputsResponse {
+    pub transaction_id: String,
+    pub items: Vec<TransactionOutputDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(TransactionOutputsResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBlocksResponse {
+    pub transaction_id: String,
+    pub blocks: Vec<TransactionBlockDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub ledger_index: MilestoneIndex,
+}
+
+impl_success_response!(TransactionBlocksResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBlockDto {
+    pub block_id: String,
+    pub state: BlockConfirmationState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referenced_by_milestone_index: Option<MilestoneIndex>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionOutputDto {
+    pub output_id: String,
+    pub is_spent: bool,
+    /// The output itself, only included if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<iota_types::block::output::dto::OutputDto>,
+    /// The decoded immutable features, only included along with NFT outputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft: Option<NftDetailsDto>,
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_amount_format() {
+        let summary = LedgerUpdatesSummaryDto {
+            update_count: 2,
+            net_balance_change: "-100".to_string().into(),
+            full_history: true,
+        };
+        let distribution = DistributionStatDto::from(DistributionStat {
+            index: 0,
+            address_count: 5,
+            total_balance: "18446744073709551615".to_string(),
+        });
+
+        assert_eq!(
+            serde_json::to_value(&summary).unwrap(),
+            json!({ "updateCount": 2, "netBalanceChange": "-100", "fullHistory": true })
+        );
+        AMOUNT_FORMAT.sync_scope(AmountFormat::Number, || {
+            assert_eq!(
+                serde_json::to_value(&summary).unwrap(),
+                json!({ "updateCount": 2, "netBalanceChange": -100, "fullHistory": true })
+            );
+            assert_eq!(
+                serde_json::to_value(&distribution).unwrap(),
+                json!({
+                    "range": { "start": 1, "end": 10 },
+                    "addressCount": "5",
+                    "totalBalance": 18446744073709551615u64,
+                })
+            );
+        });
+        AMOUNT_FORMAT.sync_scope(AmountFormat::String, || {
+            assert_eq!(
+                serde_json::to_value(&distribution).unwrap()["totalBalance"],
+                json!("18446744073709551615")
+            );
+        });
+    }
+}
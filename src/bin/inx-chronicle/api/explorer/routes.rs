@@ -1,58 +1,117 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::str::FromStr;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    str::FromStr,
+};
 
-use axum::{extract::Path, routing::get, Extension};
+use axum::{
+    extract::{Json, Path},
+    routing::{get, post},
+    Extension,
+};
 use chronicle::{
     db::{
         mongodb::collections::{
-            BlockCollection, LedgerUpdateCollection, MilestoneCollection, OutputCollection, ProtocolUpdateCollection,
+            AddressBalanceResult, AddressOutputCollection, ApplicationStateCollection, BalanceResult, BlockCollection,
+            ConfigurationUpdateCollection, LedgerUpdateCollection, MilestoneCollection, OutputCollection,
+            OutputWithMetadataResult, ProtocolUpdateCollection, UnreferencedBlockCollection,
         },
         MongoDb,
     },
     model::{
-        payload::{MilestoneId, MilestonePayload, TaggedDataPayload, TransactionPayload, TreasuryTransactionPayload},
+        metadata::{BlockMetadata, LedgerInclusionState},
+        payload::{
+            transaction::output::{AliasId, FoundryId, NftId},
+            MilestoneId, MilestoneOption, MilestonePayload, TaggedDataPayload, TransactionId, TransactionPayload,
+            TreasuryTransactionPayload,
+        },
         tangle::MilestoneIndex,
-        utxo::Address,
-        BlockId,
+        utxo::{Address, OutputId},
+        BlockId, Signature,
     },
 };
-use futures::{StreamExt, TryStreamExt};
+use futures::{Stream, StreamExt, TryStreamExt};
+use iota_types::api::core::response::OutputWithMetadataResponse;
+use primitive_types::U256;
 
 use super::{
     extractors::{
-        BlocksByMilestoneCursor, BlocksByMilestoneIdPagination, BlocksByMilestoneIndexPagination, LedgerIndex,
-        LedgerUpdatesByAddressCursor, LedgerUpdatesByAddressPagination, LedgerUpdatesByMilestoneCursor,
-        LedgerUpdatesByMilestonePagination, MilestonesCursor, MilestonesPagination, RichestAddressesQuery,
+        parse_unlock_condition_kind, BalanceQuery, BlocksByMilestoneCursor, BlocksByMilestoneIdPagination,
+        BlocksByMilestoneIndexPagination, HistorySizeQuery, LargestOutputsCursor, LargestOutputsPagination,
+        LedgerDiffCursor, LedgerDiffPagination, LedgerIndex, LedgerUpdatesByAddressCursor,
+        LedgerUpdatesByAddressPagination, LedgerUpdatesByMilestoneCursor, LedgerUpdatesByMilestonePagination,
+        MaturingOutputsCursor, MaturingOutputsPagination, MilestoneOutputsCursor, MilestoneOutputsPagination,
+        MilestonesCursor, MilestonesPagination, OutputsByConditionCursor, OutputsByConditionPagination,
+        RichestAddressesQuery, SpentOutputsByAddressCursor, SpentOutputsByAddressPagination, TransactionBlocksCursor,
+        TransactionBlocksPagination, TransactionOutputsCursor, TransactionOutputsPagination,
     },
     responses::{
-        AddressStatDto, BalanceResponse, BlockChildrenResponse, BlockPayloadTypeDto, BlocksByMilestoneResponse,
-        LedgerUpdatesByAddressResponse, LedgerUpdatesByMilestoneResponse, MilestonesResponse, RichestAddressesResponse,
-        TokenDistributionResponse,
+        AddressBalanceDto, AddressStatDto, AddressSummaryResponse, AliasAddressResponse, BalanceChangedSinceResponse,
+        BalanceResponse, BalancesResponse, BlockChildrenResponse, BlockConfirmationResponse, BlockConfirmationState,
+        BlockPayloadTypeDto, BlocksByMilestoneResponse, CommitteeResponse, HistorySizeResponse, LargestOutputDto,
+        LargestOutputsResponse, LatestMilestoneResponse, LedgerDiffResponse, LedgerUpdatesByAddressResponse,
+        LedgerUpdatesByMilestoneResponse, LedgerUpdatesSummaryDto, MaturingOutputDto, MaturingOutputsResponse,
+        MigratedFundsEntryDto, MilestoneOutputDto, MilestoneOutputsResponse, MilestoneReceiptResponse,
+        MilestonesResponse, NftAddressResponse, NormalizedOutputResponse,
+        OutputBlockResponse, OutputsByConditionResponse, OutputsByIdsResponse, OutputsMetadataByIdsResponse,
+        ProtocolParametersResponse, RichestAddressesResponse, SpentOutputDto, SpentOutputsByAddressResponse,
+        StorageDepositResponse,
+        SyncStatusResponse, TokenDistributionResponse, TokenIssuerResponse, TransactionBlockDto,
+        TransactionBlocksResponse, TransactionOutputDto, TransactionOutputsResponse, TreasuryTransactionDto,
     },
 };
 use crate::api::{
-    error::{CorruptStateError, MissingError, RequestError},
+    core::{
+        create_output_metadata_response, create_output_metadata_with_input_index_response,
+        create_protocol_parameters_response,
+    },
+    denylist::check_denylist,
+    error::{CorruptStateError, MissingError, NotSyncedError, RequestError},
     extractors::Pagination,
+    responses::NftDetailsDto,
     router::Router,
-    ApiResult,
+    routes::latest_bech32_hrp,
+    ApiConfigData, ApiResult,
 };
 
 pub fn routes() -> Router {
     Router::new()
+        .route(
+            "/addresses/:address/changed-since/:milestone_index",
+            get(balance_changed_since),
+        )
+        .route("/addresses/:address/history-size", get(history_size))
+        .route("/addresses/:address/maturing-outputs", get(maturing_outputs))
+        .route("/addresses/:address/spent", get(spent_outputs_by_address))
+        .route("/addresses/:address/summary", get(address_summary))
+        .route("/aliases/:alias_id/address", get(alias_address))
         .route("/balance/:address", get(balance))
-        .route("/blocks/:block_id/children", get(block_children))
+        .route("/balances", post(balances))
+        .route("/committee", get(committee))
+        .nest(
+            "/blocks/:block_id",
+            Router::new()
+                .route("/children", get(block_children))
+                .route("/confirmation", get(block_confirmation)),
+        )
         .nest(
             "/milestones",
             Router::new()
                 .route("/", get(milestones))
+                .route("/latest", get(latest_milestone))
                 .route("/:milestone_id/blocks", get(blocks_by_milestone_id))
-                .route("/by-index/:milestone_index/blocks", get(blocks_by_milestone_index)),
+                .route("/:milestone_id/consumed-outputs", get(consumed_outputs_by_milestone))
+                .route("/:milestone_id/created-outputs", get(created_outputs_by_milestone))
+                .route("/by-index/:milestone_index/blocks", get(blocks_by_milestone_index))
+                .route("/by-index/:milestone_index/receipt", get(milestone_receipt)),
         )
         .nest(
             "/ledger",
             Router::new()
+                .route("/diff", get(ledger_diff))
+                .route("/largest-outputs", get(largest_outputs))
                 .route("/richest-addresses", get(richest_addresses_ledger_analytics))
                 .route("/token-distribution", get(token_distribution_ledger_analytics))
                 .nest(
@@ -62,18 +121,36 @@ pub fn routes() -> Router {
                         .route("/by-milestone/:milestone_id", get(ledger_updates_by_milestone)),
                 ),
         )
+        .route("/nfts/:nft_id/address", get(nft_address))
+        .route("/outputs/by-condition/:condition", get(outputs_by_condition))
+        .route("/outputs/by-ids", post(outputs_by_ids))
+        .route("/outputs/metadata/by-ids", post(outputs_metadata_by_ids))
+        .route("/outputs/:output_id", get(normalized_output))
+        .route("/outputs/:output_id/block", get(output_block))
+        .route(
+            "/protocol/by-milestone/:milestone_index",
+            get(protocol_parameters_by_milestone),
+        )
+        .route("/sync-status", get(sync_status))
+        .route("/tokens/:token_id/issuer", get(token_issuer))
+        .route("/transactions/:transaction_id/blocks", get(transaction_blocks))
+        .route("/transactions/:transaction_id/outputs", get(transaction_outputs))
+        .nest("/utils", Router::new().route("/storage-deposit", post(storage_deposit)))
 }
 
 async fn ledger_updates_by_address(
     database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
     Path(address): Path<String>,
     LedgerUpdatesByAddressPagination {
         page_size,
         sort,
         cursor,
+        full_history_summary,
     }: LedgerUpdatesByAddressPagination,
 ) -> ApiResult<LedgerUpdatesByAddressResponse> {
     let address_dto = Address::from_str(&address).map_err(RequestError::from)?;
+    check_denylist(&config, &address_dto).await?;
 
     let mut record_stream = database
         .collection::<LedgerUpdateCollection>()
@@ -87,12 +164,7 @@ async fn ledger_updates_by_address(
         .await?;
 
     // Take all of the requested records first
-    let items = record_stream
-        .by_ref()
-        .take(page_size)
-        .map_ok(Into::into)
-        .try_collect()
-        .await?;
+    let records = record_stream.by_ref().take(page_size).try_collect::<Vec<_>>().await?;
 
     // If any record is left, use it to make the cursor
     let cursor = record_stream.try_next().await?.map(|rec| {
@@ -105,13 +177,42 @@ async fn ledger_updates_by_address(
         .to_string()
     });
 
-    Ok(LedgerUpdatesByAddressResponse { address, items, cursor })
+    let page_updates = records
+        .iter()
+        .map(|rec| (rec.output_id, rec.is_spent))
+        .collect::<Vec<_>>();
+    let summary = database
+        .collection::<LedgerUpdateCollection>()
+        .get_ledger_update_summary(
+            &address_dto,
+            if full_history_summary {
+                None
+            } else {
+                Some(page_updates.as_slice())
+            },
+        )
+        .await?;
+
+    Ok(LedgerUpdatesByAddressResponse {
+        address,
+        summary: LedgerUpdatesSummaryDto {
+            update_count: summary.update_count,
+            net_balance_change: summary.net_balance_change.into(),
+            full_history: full_history_summary,
+        },
+        items: records.into_iter().map(Into::into).collect(),
+        cursor,
+    })
 }
 
 async fn ledger_updates_by_milestone(
     database: Extension<MongoDb>,
     Path(milestone_id): Path<String>,
-    LedgerUpdatesByMilestonePagination { page_size, cursor }: LedgerUpdatesByMilestonePagination,
+    LedgerUpdatesByMilestonePagination {
+        output_kind,
+        page_size,
+        cursor,
+    }: LedgerUpdatesByMilestonePagination,
 ) -> ApiResult<LedgerUpdatesByMilestoneResponse> {
     let milestone_id = MilestoneId::from_str(&milestone_id).map_err(RequestError::from)?;
 
@@ -125,7 +226,7 @@ async fn ledger_updates_by_milestone(
 
     let mut record_stream = database
         .collection::<LedgerUpdateCollection>()
-        .get_ledger_updates_by_milestone(milestone_index, page_size + 1, cursor)
+        .get_ledger_updates_by_milestone(milestone_index, output_kind, page_size + 1, cursor)
         .await?;
 
     // Take all of the requested records first
@@ -142,6 +243,7 @@ async fn ledger_updates_by_milestone(
             output_id: rec.output_id,
             page_size,
             is_spent: rec.is_spent,
+            output_kind,
         }
         .to_string()
     });
@@ -153,22 +255,458 @@ async fn ledger_updates_by_milestone(
     })
 }
 
-async fn balance(database: Extension<MongoDb>, Path(address): Path<String>) -> ApiResult<BalanceResponse> {
+async fn ledger_diff(
+    database: Extension<MongoDb>,
+    LedgerDiffPagination {
+        from,
+        to,
+        page_size,
+        cursor,
+    }: LedgerDiffPagination,
+) -> ApiResult<LedgerDiffResponse> {
     let ledger_index = database
         .collection::<MilestoneCollection>()
         .get_ledger_index()
         .await?
-        .ok_or(MissingError::NoResults)?;
+        .ok_or(NotSyncedError)?;
+    // The diff would silently miss the updates of milestones that are not synced yet.
+    if to > ledger_index {
+        return Err(RequestError::BadMilestoneRange.into());
+    }
+
+    let mut record_stream = database
+        .collection::<LedgerUpdateCollection>()
+        // Get one extra record so that we can create the cursor.
+        .get_ledger_diff(from, to, page_size + 1, cursor)
+        .await?;
+
+    // Take all of the requested records first
+    let items = record_stream
+        .by_ref()
+        .take(page_size)
+        .map_ok(Into::into)
+        .try_collect()
+        .await?;
+
+    // If any record is left, use it to make the cursor
+    let cursor = record_stream.try_next().await?.map(|rec| {
+        LedgerDiffCursor {
+            output_id: rec.output_id,
+            page_size,
+        }
+        .to_string()
+    });
+
+    Ok(LedgerDiffResponse {
+        from,
+        to,
+        items,
+        cursor,
+        ledger_index,
+    })
+}
+
+async fn balance(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Path(address): Path<String>,
+    BalanceQuery { native_tokens }: BalanceQuery,
+) -> ApiResult<BalanceResponse> {
     let address = Address::from_str(&address).map_err(RequestError::from)?;
-    let res = database
-        .collection::<OutputCollection>()
-        .get_address_balance(address, ledger_index)
+    check_denylist(&config, &address).await?;
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let res = get_address_balance(&database, &config, address, ledger_index)
         .await?
         .ok_or(MissingError::NoResults)?;
+    let native_tokens = if native_tokens {
+        Some(
+            database
+                .collection::<OutputCollection>()
+                .get_address_native_token_balances(address, ledger_index, address_output_limit(&config))
+                .await?
+                .map_ok(|res| {
+                    (
+                        iota_types::block::output::TokenId::from(res.token_id).to_string(),
+                        res.amounts
+                            .into_iter()
+                            .map(U256::from)
+                            .fold(U256::zero(), U256::saturating_add)
+                            .to_string(),
+                    )
+                })
+                .try_collect()
+                .await?,
+        )
+    } else {
+        None
+    };
 
     Ok(BalanceResponse {
-        total_balance: res.total_balance,
-        sig_locked_balance: res.sig_locked_balance,
+        truncated: is_truncated(&config, res.output_count),
+        total_balance: res.total_balance.into(),
+        sig_locked_balance: res.sig_locked_balance.into(),
+        native_tokens,
+        ledger_index,
+    })
+}
+
+/// Sums the balance of an address, reading from the address outputs index unless it is disabled.
+async fn get_address_balance(
+    database: &MongoDb,
+    config: &ApiConfigData,
+    address: Address,
+    ledger_index: MilestoneIndex,
+) -> Result<Option<BalanceResult>, mongodb::error::Error> {
+    if config.address_index_enabled {
+        database
+            .collection::<AddressOutputCollection>()
+            .get_address_balance(address, ledger_index, address_output_limit(config))
+            .await
+    } else {
+        database
+            .collection::<OutputCollection>()
+            .get_address_balance(address, ledger_index, address_output_limit(config))
+            .await
+    }
+}
+
+/// The number of outputs that are read for a single address. This is one more than the configured maximum, so that
+/// an address that owns exactly the maximum is not reported as truncated.
+fn address_output_limit(config: &ApiConfigData) -> Option<usize> {
+    config.max_address_outputs.map(|max| max.get() + 1)
+}
+
+/// Whether an address owns more outputs than the configured maximum, so that the result is incomplete.
+fn is_truncated(config: &ApiConfigData, output_count: usize) -> bool {
+    config.max_address_outputs.map_or(false, |max| output_count > max.get())
+}
+
+/// Sums the balances of multiple addresses, reading from the address outputs index unless it is disabled. Addresses
+/// that do not own any outputs are omitted.
+async fn get_address_balances(
+    database: &MongoDb,
+    config: &ApiConfigData,
+    addresses: &[Address],
+    ledger_index: MilestoneIndex,
+) -> Result<Vec<AddressBalanceResult>, mongodb::error::Error> {
+    if config.address_index_enabled {
+        database
+            .collection::<AddressOutputCollection>()
+            .get_address_balances(addresses, ledger_index, address_output_limit(config))
+            .await?
+            .try_collect()
+            .await
+    } else {
+        database
+            .collection::<OutputCollection>()
+            .get_address_balances(addresses, ledger_index, address_output_limit(config))
+            .await?
+            .try_collect()
+            .await
+    }
+}
+
+async fn balances(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Json(addresses): Json<Vec<String>>,
+) -> ApiResult<BalancesResponse> {
+    if addresses.len() > config.max_page_size {
+        return Err(RequestError::TooManyAddresses {
+            requested: addresses.len(),
+            max: config.max_page_size,
+        }
+        .into());
+    }
+    let mut parsed = HashMap::with_capacity(addresses.len());
+    for address in addresses {
+        let key = Address::from_str(&address).map_err(RequestError::from)?;
+        if parsed.contains_key(&key) {
+            return Err(RequestError::DuplicateAddress(address).into());
+        }
+        parsed.insert(key, address);
+    }
+    let addresses = parsed;
+    for address in addresses.keys() {
+        check_denylist(&config, address).await?;
+    }
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+
+    let mut balances = addresses
+        .values()
+        .map(|address| {
+            (
+                address.clone(),
+                AddressBalanceDto {
+                    total_balance: 0.to_string().into(),
+                    sig_locked_balance: 0.to_string().into(),
+                    truncated: false,
+                },
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+    let results = get_address_balances(
+        &database,
+        &config,
+        &addresses.keys().copied().collect::<Vec<_>>(),
+        ledger_index,
+    )
+    .await?;
+    for res in results {
+        if let Some(address) = addresses.get(&res.address) {
+            balances.insert(
+                address.clone(),
+                AddressBalanceDto {
+                    truncated: is_truncated(&config, res.output_count),
+                    total_balance: res.total_balance.into(),
+                    sig_locked_balance: res.sig_locked_balance.into(),
+                },
+            );
+        }
+    }
+
+    Ok(BalancesResponse { balances, ledger_index })
+}
+
+async fn balance_changed_since(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Path((address, milestone_index)): Path<(String, MilestoneIndex)>,
+) -> ApiResult<BalanceChangedSinceResponse> {
+    let address = Address::from_str(&address).map_err(RequestError::from)?;
+    check_denylist(&config, &address).await?;
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let changed = database
+        .collection::<LedgerUpdateCollection>()
+        .has_ledger_updates_since(&address, milestone_index, ledger_index)
+        .await?;
+    let (total_balance, sig_locked_balance, truncated) = if changed {
+        let res = get_address_balance(&database, &config, address, ledger_index)
+            .await?
+            .ok_or(MissingError::NoResults)?;
+        (
+            Some(res.total_balance.into()),
+            Some(res.sig_locked_balance.into()),
+            is_truncated(&config, res.output_count),
+        )
+    } else {
+        (None, None, false)
+    };
+
+    Ok(BalanceChangedSinceResponse {
+        changed,
+        total_balance,
+        sig_locked_balance,
+        truncated,
+        ledger_index,
+    })
+}
+
+async fn address_summary(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Path(address): Path<String>,
+) -> ApiResult<AddressSummaryResponse> {
+    let address_dto = Address::from_str(&address).map_err(RequestError::from)?;
+    check_denylist(&config, &address_dto).await?;
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let res = if config.address_index_enabled {
+        database
+            .collection::<AddressOutputCollection>()
+            .get_address_summary(address_dto, ledger_index, address_output_limit(&config))
+            .await?
+    } else {
+        database
+            .collection::<OutputCollection>()
+            .get_address_summary(address_dto, ledger_index, address_output_limit(&config))
+            .await?
+    };
+
+    Ok(AddressSummaryResponse {
+        truncated: is_truncated(
+            &config,
+            res.basic_count + res.alias_count + res.nft_count + res.foundry_count,
+        ),
+        address,
+        basic_outputs: res.basic_count,
+        alias_outputs: res.alias_count,
+        nft_outputs: res.nft_count,
+        foundry_outputs: res.foundry_count,
+        total_balance: res.total_balance.into(),
+        ledger_index,
+    })
+}
+
+/// Counting the ledger updates of an address stops at this number unless an exact count is requested.
+const HISTORY_SIZE_ESTIMATE_LIMIT: usize = 10_000;
+
+async fn history_size(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Path(address): Path<String>,
+    HistorySizeQuery { exact }: HistorySizeQuery,
+) -> ApiResult<HistorySizeResponse> {
+    let address_dto = Address::from_str(&address).map_err(RequestError::from)?;
+    check_denylist(&config, &address_dto).await?;
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let limit = (!exact).then(|| HISTORY_SIZE_ESTIMATE_LIMIT);
+    let count = database
+        .collection::<LedgerUpdateCollection>()
+        .count_ledger_updates_by_address(&address_dto, ledger_index, limit)
+        .await?;
+
+    Ok(HistorySizeResponse {
+        address,
+        count,
+        exact: exact || count < HISTORY_SIZE_ESTIMATE_LIMIT,
+        ledger_index,
+    })
+}
+
+/// Gets the timelocked outputs of an address that become unlockable within a time range, so that clients can show
+/// upcoming funds.
+async fn maturing_outputs(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Path(address): Path<String>,
+    MaturingOutputsPagination {
+        page_size,
+        cursor,
+        unlockable_after,
+        unlockable_before,
+        ledger_index,
+    }: MaturingOutputsPagination,
+) -> ApiResult<MaturingOutputsResponse> {
+    let address = Address::from_str(&address).map_err(RequestError::from)?;
+    check_denylist(&config, &address).await?;
+    let ledger_index = resolve_ledger_index(&database, ledger_index).await?;
+    let unlockable_after = match unlockable_after {
+        Some(unlockable_after) => unlockable_after,
+        None => database
+            .collection::<MilestoneCollection>()
+            .get_milestone_timestamp(ledger_index)
+            .await?
+            .ok_or(MissingError::NoResults)?,
+    };
+
+    let mut records = database
+        .collection::<OutputCollection>()
+        // Get one extra record so that we can create the cursor.
+        .get_maturing_outputs(
+            address,
+            ledger_index,
+            unlockable_after,
+            unlockable_before,
+            page_size + 1,
+            cursor,
+        )
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let cursor = if records.len() > page_size {
+        records.pop().map(|rec| {
+            MaturingOutputsCursor {
+                unlockable_at: rec.unlockable_at,
+                output_id: rec.output_id,
+                page_size,
+            }
+            .to_string()
+        })
+    } else {
+        None
+    };
+
+    Ok(MaturingOutputsResponse {
+        outputs: records
+            .into_iter()
+            .map(|rec| MaturingOutputDto {
+                output_id: rec.output_id.to_hex(),
+                unlockable_at: rec.unlockable_at.0,
+            })
+            .collect(),
+        cursor,
+        ledger_index,
+    })
+}
+
+async fn spent_outputs_by_address(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Path(address): Path<String>,
+    SpentOutputsByAddressPagination {
+        page_size,
+        sort,
+        cursor,
+    }: SpentOutputsByAddressPagination,
+) -> ApiResult<SpentOutputsByAddressResponse> {
+    let address_dto = Address::from_str(&address).map_err(RequestError::from)?;
+    check_denylist(&config, &address_dto).await?;
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+
+    // Get one extra record so that we can create the cursor.
+    let mut record_stream = if config.address_index_enabled {
+        database
+            .collection::<AddressOutputCollection>()
+            .get_spent_outputs_by_address(address_dto, page_size + 1, cursor, sort, ledger_index)
+            .await?
+            .boxed()
+    } else {
+        database
+            .collection::<OutputCollection>()
+            .get_spent_outputs_by_address(address_dto, page_size + 1, cursor, sort, ledger_index)
+            .await?
+            .boxed()
+    };
+
+    let hrp = latest_bech32_hrp(&database).await?;
+
+    // Take all of the requested records first
+    let items = record_stream
+        .by_ref()
+        .take(page_size)
+        .map_ok(|res| SpentOutputDto::new(res, &hrp))
+        .try_collect()
+        .await?;
+
+    // If any record is left, use it to make the cursor
+    let cursor = record_stream.try_next().await?.map(|rec| {
+        SpentOutputsByAddressCursor {
+            milestone_index: rec.spent_metadata.spent.milestone_index,
+            output_id: rec.output_id,
+            page_size,
+        }
+        .to_string()
+    });
+
+    Ok(SpentOutputsByAddressResponse {
+        address,
+        items,
+        cursor,
         ledger_index,
     })
 }
@@ -211,11 +749,83 @@ async fn block_children(
     })
 }
 
+async fn block_confirmation(
+    database: Extension<MongoDb>,
+    Path(block_id): Path<String>,
+) -> ApiResult<BlockConfirmationResponse> {
+    let block_id = BlockId::from_str(&block_id).map_err(RequestError::from)?;
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    // Blocks that were never seen do not have a document, which is distinct from a pending block.
+    let metadata = match database
+        .collection::<BlockCollection>()
+        .get_block_metadata(&block_id)
+        .await?
+    {
+        Some(metadata) => metadata,
+        None => {
+            // Blocks that no synced milestone references are only stored if the INX worker is configured to do so.
+            if database
+                .collection::<UnreferencedBlockCollection>()
+                .contains_block(&block_id)
+                .await?
+            {
+                return Ok(BlockConfirmationResponse {
+                    block_id: block_id.to_hex(),
+                    state: BlockConfirmationState::Pending,
+                    referenced_by_milestone_index: None,
+                    conflict_reason: None,
+                    unreferenced: true,
+                    ledger_index,
+                });
+            }
+            Err(MissingError::NotFound)?
+        }
+    };
+
+    let (state, referenced_by_milestone_index) = confirmation_state(&metadata, ledger_index);
+
+    Ok(BlockConfirmationResponse {
+        block_id: block_id.to_hex(),
+        state,
+        referenced_by_milestone_index,
+        conflict_reason: (state == BlockConfirmationState::Conflicting).then(|| metadata.conflict_reason as u8),
+        unreferenced: false,
+        ledger_index,
+    })
+}
+
+/// Gets the confirmation state of a block at the ledger index, along with the milestone that referenced it if it is
+/// not pending.
+fn confirmation_state(
+    metadata: &BlockMetadata,
+    ledger_index: MilestoneIndex,
+) -> (BlockConfirmationState, Option<MilestoneIndex>) {
+    // A block is only considered confirmed once its referencing milestone has been fully synced.
+    if metadata.referenced_by_milestone_index > ledger_index {
+        (BlockConfirmationState::Pending, None)
+    } else if metadata.inclusion_state == LedgerInclusionState::Conflicting {
+        (
+            BlockConfirmationState::Conflicting,
+            Some(metadata.referenced_by_milestone_index),
+        )
+    } else {
+        (
+            BlockConfirmationState::Confirmed,
+            Some(metadata.referenced_by_milestone_index),
+        )
+    }
+}
+
 async fn milestones(
     database: Extension<MongoDb>,
     MilestonesPagination {
         start_timestamp,
         end_timestamp,
+        filter,
         sort,
         page_size,
         cursor,
@@ -223,7 +833,7 @@ async fn milestones(
 ) -> ApiResult<MilestonesResponse> {
     let mut record_stream = database
         .collection::<MilestoneCollection>()
-        .get_milestones(start_timestamp, end_timestamp, sort, page_size + 1, cursor)
+        .get_milestones(start_timestamp, end_timestamp, filter, sort, page_size + 1, cursor)
         .await?;
 
     // Take all of the requested records first
@@ -246,14 +856,83 @@ async fn milestones(
     Ok(MilestonesResponse { items, cursor })
 }
 
-async fn blocks_by_milestone_index(
+async fn latest_milestone(database: Extension<MongoDb>) -> ApiResult<LatestMilestoneResponse> {
+    let milestone_collection = database.collection::<MilestoneCollection>();
+    let newest = milestone_collection
+        .get_newest_milestone()
+        .await?
+        .ok_or(MissingError::NoResults)?;
+    let milestone_id = milestone_collection
+        .get_milestone_id(newest.milestone_index)
+        .await?
+        .ok_or(MissingError::NoResults)?;
+    Ok(LatestMilestoneResponse {
+        milestone_id: milestone_id.to_hex(),
+        index: newest.milestone_index,
+        timestamp: newest.milestone_timestamp,
+    })
+}
+
+async fn milestone_receipt(
     database: Extension<MongoDb>,
     Path(milestone_index): Path<MilestoneIndex>,
-    BlocksByMilestoneIndexPagination {
-        sort,
-        page_size,
-        cursor,
-    }: BlocksByMilestoneIndexPagination,
+) -> ApiResult<MilestoneReceiptResponse> {
+    let payload = database
+        .collection::<MilestoneCollection>()
+        .get_milestone_payload(milestone_index)
+        .await?
+        .ok_or(MissingError::NotFound)?;
+    let (migrated_at, last, funds, transaction) = payload
+        .essence
+        .options
+        .into_vec()
+        .into_iter()
+        .find_map(|option| match option {
+            MilestoneOption::Receipt {
+                migrated_at,
+                last,
+                funds,
+                transaction,
+            } => Some((migrated_at, last, funds, transaction)),
+            _ => None,
+        })
+        .ok_or(MissingError::NotFound)?;
+
+    let hrp = database
+        .collection::<ProtocolUpdateCollection>()
+        .get_protocol_parameters_for_ledger_index(milestone_index)
+        .await?
+        .ok_or(CorruptStateError::ProtocolParams)?
+        .parameters
+        .bech32_hrp;
+
+    Ok(MilestoneReceiptResponse {
+        milestone_index,
+        migrated_at,
+        last,
+        funds: funds
+            .iter()
+            .map(|entry| MigratedFundsEntryDto {
+                tail_transaction_hash: prefix_hex::encode(entry.tail_transaction_hash),
+                address: iota_types::block::address::Address::from(entry.address).to_bech32(hrp.clone()),
+                deposit: entry.amount.to_string().into(),
+            })
+            .collect(),
+        transaction: TreasuryTransactionDto {
+            input_milestone_id: transaction.input_milestone_id.to_hex(),
+            output_amount: transaction.output_amount.to_string().into(),
+        },
+    })
+}
+
+async fn blocks_by_milestone_index(
+    database: Extension<MongoDb>,
+    Path(milestone_index): Path<MilestoneIndex>,
+    BlocksByMilestoneIndexPagination {
+        sort,
+        page_size,
+        cursor,
+    }: BlocksByMilestoneIndexPagination,
 ) -> ApiResult<BlocksByMilestoneResponse> {
     let mut record_stream = database
         .collection::<BlockCollection>()
@@ -298,14 +977,7 @@ async fn blocks_by_milestone_id(
         cursor,
     }: BlocksByMilestoneIdPagination,
 ) -> ApiResult<BlocksByMilestoneResponse> {
-    let milestone_id = MilestoneId::from_str(&milestone_id).map_err(RequestError::from)?;
-    let milestone_index = database
-        .collection::<MilestoneCollection>()
-        .get_milestone_payload_by_id(&milestone_id)
-        .await?
-        .ok_or(MissingError::NoResults)?
-        .essence
-        .index;
+    let milestone_index = resolve_milestone_index(&database, &milestone_id).await?;
     blocks_by_milestone_index(
         database,
         Path(milestone_index),
@@ -318,14 +990,106 @@ async fn blocks_by_milestone_id(
     .await
 }
 
+/// Resolves a milestone given either by id or by index. Addressing milestones by index saves explorers a lookup of the
+/// milestone id.
+async fn resolve_milestone_index(database: &MongoDb, milestone_id: &str) -> ApiResult<MilestoneIndex> {
+    Ok(match milestone_id.parse::<MilestoneIndex>() {
+        Ok(milestone_index) => milestone_index,
+        Err(_) => {
+            let milestone_id = MilestoneId::from_str(milestone_id).map_err(RequestError::from)?;
+            database
+                .collection::<MilestoneCollection>()
+                .get_milestone_payload_by_id(&milestone_id)
+                .await?
+                .ok_or(MissingError::NoResults)?
+                .essence
+                .index
+        }
+    })
+}
+
+async fn created_outputs_by_milestone(
+    database: Extension<MongoDb>,
+    Path(milestone_id): Path<String>,
+    pagination: MilestoneOutputsPagination,
+) -> ApiResult<MilestoneOutputsResponse> {
+    let milestone_index = resolve_milestone_index(&database, &milestone_id).await?;
+    let record_stream = database
+        .collection::<OutputCollection>()
+        // Get one extra record so that we can create the cursor.
+        .get_outputs_created_at(milestone_index, pagination.cursor, pagination.page_size + 1)
+        .await?;
+    milestone_outputs_response(&database, milestone_index, record_stream, pagination).await
+}
+
+async fn consumed_outputs_by_milestone(
+    database: Extension<MongoDb>,
+    Path(milestone_id): Path<String>,
+    pagination: MilestoneOutputsPagination,
+) -> ApiResult<MilestoneOutputsResponse> {
+    let milestone_index = resolve_milestone_index(&database, &milestone_id).await?;
+    let record_stream = database
+        .collection::<OutputCollection>()
+        // Get one extra record so that we can create the cursor.
+        .get_outputs_consumed_at(milestone_index, pagination.cursor, pagination.page_size + 1)
+        .await?;
+    milestone_outputs_response(&database, milestone_index, record_stream, pagination).await
+}
+
+async fn milestone_outputs_response(
+    database: &MongoDb,
+    milestone_index: MilestoneIndex,
+    record_stream: impl Stream<Item = Result<OutputWithMetadataResult, mongodb::error::Error>>,
+    MilestoneOutputsPagination {
+        page_size,
+        include_outputs,
+        ..
+    }: MilestoneOutputsPagination,
+) -> ApiResult<MilestoneOutputsResponse> {
+    futures::pin_mut!(record_stream);
+    let hrp = if include_outputs {
+        Some(latest_bech32_hrp(database).await?)
+    } else {
+        None
+    };
+
+    // Take all of the requested records first
+    let items = record_stream
+        .by_ref()
+        .take(page_size)
+        .map_ok(|res| MilestoneOutputDto {
+            output_id: res.metadata.output_id.to_hex(),
+            nft: hrp.as_deref().and_then(|hrp| NftDetailsDto::from_output(&res.output, hrp)),
+            output: include_outputs.then(|| res.output.into()),
+        })
+        .try_collect()
+        .await?;
+
+    // If any record is left, use it to make the cursor
+    let cursor = record_stream.try_next().await?.map(|rec| {
+        MilestoneOutputsCursor {
+            output_id: rec.metadata.output_id,
+            page_size,
+        }
+        .to_string()
+    });
+
+    Ok(MilestoneOutputsResponse {
+        milestone_index,
+        items,
+        cursor,
+    })
+}
+
 async fn richest_addresses_ledger_analytics(
     database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
     RichestAddressesQuery { top, ledger_index }: RichestAddressesQuery,
 ) -> ApiResult<RichestAddressesResponse> {
     let ledger_index = resolve_ledger_index(&database, ledger_index).await?;
     let res = database
         .collection::<OutputCollection>()
-        .get_richest_addresses(ledger_index, top)
+        .get_richest_addresses(ledger_index, top, &config.richlist_excluded_addresses)
         .await?;
 
     let hrp = database
@@ -342,9 +1106,68 @@ async fn richest_addresses_ledger_analytics(
             .into_iter()
             .map(|stat| AddressStatDto {
                 address: iota_types::block::address::Address::from(stat.address).to_bech32(hrp.clone()),
-                balance: stat.balance,
+                balance: stat.balance.into(),
+            })
+            .collect(),
+        excluded_addresses: config
+            .richlist_excluded_addresses
+            .iter()
+            .map(|address| iota_types::block::address::Address::from(*address).to_bech32(hrp.clone()))
+            .collect(),
+        ledger_index,
+    })
+}
+
+async fn largest_outputs(
+    database: Extension<MongoDb>,
+    LargestOutputsPagination {
+        limit,
+        cursor,
+        ledger_index,
+    }: LargestOutputsPagination,
+) -> ApiResult<LargestOutputsResponse> {
+    let ledger_index = resolve_ledger_index(&database, ledger_index).await?;
+    let mut records = database
+        .collection::<OutputCollection>()
+        // Get one extra record so that we can create the cursor.
+        .get_largest_outputs(ledger_index, limit + 1, cursor)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let cursor = if records.len() > limit {
+        records.pop().map(|rec| {
+            LargestOutputsCursor {
+                amount: rec.amount,
+                output_id: rec.output_id,
+                limit,
+            }
+            .to_string()
+        })
+    } else {
+        None
+    };
+
+    let hrp = database
+        .collection::<ProtocolUpdateCollection>()
+        .get_protocol_parameters_for_ledger_index(ledger_index)
+        .await?
+        .ok_or(CorruptStateError::ProtocolParams)?
+        .parameters
+        .bech32_hrp;
+
+    Ok(LargestOutputsResponse {
+        outputs: records
+            .into_iter()
+            .map(|rec| LargestOutputDto {
+                output_id: rec.output_id.to_hex(),
+                amount: rec.amount.0.to_string().into(),
+                address: rec
+                    .address
+                    .map(|address| iota_types::block::address::Address::from(address).to_bech32(hrp.clone())),
             })
             .collect(),
+        cursor,
         ledger_index,
     })
 }
@@ -375,6 +1198,539 @@ async fn resolve_ledger_index(database: &MongoDb, ledger_index: Option<Milestone
             .collection::<MilestoneCollection>()
             .get_ledger_index()
             .await?
-            .ok_or(MissingError::NoResults)?
+            .ok_or(NotSyncedError)?
+    })
+}
+
+/// Gets the public keys that signed the newest milestone, along with the signature threshold and the milestone at which
+/// the keys became active according to the node configuration.
+async fn committee(database: Extension<MongoDb>) -> ApiResult<CommitteeResponse> {
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let payload = database
+        .collection::<MilestoneCollection>()
+        .get_milestone_payload(ledger_index)
+        .await?
+        .ok_or(CorruptStateError::Milestone)?;
+    let node_config = database
+        .collection::<ConfigurationUpdateCollection>()
+        .get_node_configuration_for_ledger_index(ledger_index)
+        .await?
+        .ok_or(CorruptStateError::NodeConfig)?
+        .config;
+
+    let public_keys = payload
+        .signatures
+        .iter()
+        .map(|signature| match signature {
+            Signature::Ed25519 { public_key, .. } => prefix_hex::encode(public_key.as_ref()),
+        })
+        .collect::<Vec<_>>();
+    // A key range with an end of zero does not expire.
+    let effective_since = node_config
+        .milestone_key_ranges
+        .iter()
+        .filter(|range| range.start <= ledger_index && (range.end.0 == 0 || ledger_index <= range.end))
+        .filter(|range| {
+            public_keys.iter().any(|key| {
+                key.trim_start_matches("0x")
+                    .eq_ignore_ascii_case(range.public_key.trim_start_matches("0x"))
+            })
+        })
+        .map(|range| range.start)
+        .max();
+
+    Ok(CommitteeResponse {
+        public_keys,
+        threshold: node_config.milestone_public_key_count,
+        effective_since,
+        observed_at: ledger_index,
+    })
+}
+
+/// Summarizes which milestones are stored in the database and how far the sync has progressed towards the node's
+/// confirmed milestone.
+async fn sync_status(database: Extension<MongoDb>) -> ApiResult<SyncStatusResponse> {
+    let milestones = database.collection::<MilestoneCollection>();
+    let ledger_index = milestones.get_ledger_index().await?.ok_or(NotSyncedError)?;
+    let start_index = match database
+        .collection::<ApplicationStateCollection>()
+        .get_starting_index()
+        .await?
+    {
+        Some(starting_index) => starting_index.milestone_index,
+        None => {
+            milestones
+                .get_oldest_milestone()
+                .await?
+                .ok_or(MissingError::NoResults)?
+                .milestone_index
+        }
+    };
+    let node_confirmed_index = database
+        .collection::<ApplicationStateCollection>()
+        .get_node_confirmed_index()
+        .await?;
+    let end_index = node_confirmed_index.map_or(ledger_index, |index| index.max(ledger_index));
+
+    let sync_data = milestones.get_sync_data(start_index..=end_index).await?;
+    let synced = sync_data
+        .completed
+        .iter()
+        .map(|range| (range.end().0 - range.start().0 + 1) as f64)
+        .sum::<f64>();
+    let total = (end_index.0.saturating_sub(start_index.0) + 1) as f64;
+
+    Ok(SyncStatusResponse {
+        completed: sync_data.completed,
+        gaps: sync_data.gaps,
+        node_confirmed_index,
+        sync_percentage: synced / total * 100.0,
+        ledger_index,
+    })
+}
+
+/// Resolves the alias that controls the foundry which can mint the given native token.
+async fn token_issuer(database: Extension<MongoDb>, Path(token_id): Path<String>) -> ApiResult<TokenIssuerResponse> {
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    // The id of a native token is the id of the foundry that controls its supply.
+    let foundry_id = FoundryId::from_str(&token_id).map_err(RequestError::from)?;
+    let foundry_output_id = database
+        .collection::<OutputCollection>()
+        .get_indexed_output_by_id(foundry_id, ledger_index)
+        .await?
+        .ok_or(MissingError::NoResults)?
+        .output_id;
+    let foundry_id = iota_types::block::output::FoundryId::from(foundry_id);
+    let alias_id = *foundry_id.alias_address().alias_id();
+    let alias_output_id = database
+        .collection::<OutputCollection>()
+        .get_indexed_output_by_id(AliasId::from(alias_id), ledger_index)
+        .await?
+        .ok_or(MissingError::NoResults)?
+        .output_id;
+
+    Ok(TokenIssuerResponse {
+        token_id: foundry_id.to_string(),
+        foundry_output_id: foundry_output_id.to_hex(),
+        alias_id: alias_id.to_string(),
+        alias_output_id: alias_output_id.to_hex(),
+        ledger_index,
+    })
+}
+
+/// Derives the alias address that outputs owned by the alias with the given id are locked to.
+async fn alias_address(database: Extension<MongoDb>, Path(alias_id): Path<String>) -> ApiResult<AliasAddressResponse> {
+    let alias_id = iota_types::block::output::AliasId::from(AliasId::from_str(&alias_id).map_err(RequestError::from)?);
+    let hrp = latest_bech32_hrp(&database).await?;
+
+    Ok(AliasAddressResponse {
+        alias_id: alias_id.to_string(),
+        address: iota_types::block::address::Address::from(iota_types::block::address::AliasAddress::new(alias_id))
+            .to_bech32(hrp),
+    })
+}
+
+/// Derives the NFT address that outputs owned by the NFT with the given id are locked to.
+async fn nft_address(database: Extension<MongoDb>, Path(nft_id): Path<String>) -> ApiResult<NftAddressResponse> {
+    let nft_id = iota_types::block::output::NftId::from(NftId::from_str(&nft_id).map_err(RequestError::from)?);
+    let hrp = latest_bech32_hrp(&database).await?;
+
+    Ok(NftAddressResponse {
+        nft_id: nft_id.to_string(),
+        address: iota_types::block::address::Address::from(iota_types::block::address::NftAddress::new(nft_id))
+            .to_bech32(hrp),
+    })
+}
+
+async fn outputs_by_condition(
+    database: Extension<MongoDb>,
+    Path(condition): Path<String>,
+    OutputsByConditionPagination { page_size, cursor }: OutputsByConditionPagination,
+) -> ApiResult<OutputsByConditionResponse> {
+    let kind = parse_unlock_condition_kind(&condition)?;
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+
+    let mut record_stream = database
+        .collection::<OutputCollection>()
+        // Get one extra record so that we can create the cursor.
+        .get_outputs_by_unlock_condition(kind, ledger_index, page_size + 1, cursor)
+        .await?;
+
+    // Take all of the requested records first
+    let items = record_stream
+        .by_ref()
+        .take(page_size)
+        .map_ok(|rec| rec.output_id.to_hex())
+        .try_collect()
+        .await?;
+
+    // If any record is left, use it to make the cursor
+    let cursor = record_stream.try_next().await?.map(|rec| {
+        OutputsByConditionCursor {
+            output_id: rec.output_id,
+            page_size,
+        }
+        .to_string()
+    });
+
+    Ok(OutputsByConditionResponse {
+        condition,
+        items,
+        cursor,
+        ledger_index,
+    })
+}
+
+/// Gets multiple outputs along with their metadata in one request, so that clients do not need a request per output.
+async fn outputs_by_ids(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Json(output_ids): Json<Vec<String>>,
+) -> ApiResult<OutputsByIdsResponse> {
+    if output_ids.len() > config.max_page_size {
+        return Err(RequestError::TooManyOutputIds {
+            requested: output_ids.len(),
+            max: config.max_page_size,
+        }
+        .into());
+    }
+    let output_ids = output_ids
+        .iter()
+        .map(|output_id| OutputId::from_str(output_id))
+        .collect::<Result<HashSet<_>, _>>()
+        .map_err(RequestError::from)?
+        .into_iter()
+        .collect::<Vec<_>>();
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+
+    let hrp = latest_bech32_hrp(&database).await?;
+
+    let mut nfts = BTreeMap::new();
+    let outputs = database
+        .collection::<OutputCollection>()
+        .get_outputs_with_metadata(&output_ids, ledger_index)
+        .await?
+        .map_ok(|res| {
+            let output_id = res.metadata.output_id.to_hex();
+            if let Some(nft) = NftDetailsDto::from_output(&res.output, &hrp) {
+                nfts.insert(output_id.clone(), nft);
+            }
+            (
+                output_id,
+                OutputWithMetadataResponse {
+                    metadata: create_output_metadata_response(res.metadata, ledger_index),
+                    output: res.output.into(),
+                },
+            )
+        })
+        .try_collect::<BTreeMap<_, _>>()
+        .await?;
+
+    let mut missing = output_ids
+        .iter()
+        .map(OutputId::to_hex)
+        .filter(|output_id| !outputs.contains_key(output_id))
+        .collect::<Vec<_>>();
+    missing.sort();
+
+    Ok(OutputsByIdsResponse {
+        outputs,
+        nfts,
+        missing,
+        ledger_index,
+    })
+}
+
+/// Returns only the metadata of multiple outputs, for clients that track the confirmation of many outputs and do not
+/// need the outputs themselves.
+async fn outputs_metadata_by_ids(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Json(output_ids): Json<Vec<String>>,
+) -> ApiResult<OutputsMetadataByIdsResponse> {
+    if output_ids.len() > config.max_page_size {
+        return Err(RequestError::TooManyOutputIds {
+            requested: output_ids.len(),
+            max: config.max_page_size,
+        }
+        .into());
+    }
+    let output_ids = output_ids
+        .iter()
+        .map(|output_id| OutputId::from_str(output_id))
+        .collect::<Result<HashSet<_>, _>>()
+        .map_err(RequestError::from)?
+        .into_iter()
+        .collect::<Vec<_>>();
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+
+    let metadata = database
+        .collection::<OutputCollection>()
+        .get_outputs_metadata(&output_ids, ledger_index)
+        .await?
+        .map_ok(|res| {
+            (
+                res.output_id.to_hex(),
+                create_output_metadata_with_input_index_response(res, ledger_index),
+            )
+        })
+        .try_collect::<BTreeMap<_, _>>()
+        .await?;
+
+    let mut missing = output_ids
+        .iter()
+        .map(OutputId::to_hex)
+        .filter(|output_id| !metadata.contains_key(output_id))
+        .collect::<Vec<_>>();
+    missing.sort();
+
+    Ok(OutputsMetadataByIdsResponse {
+        metadata,
+        missing,
+        ledger_index,
+    })
+}
+
+async fn normalized_output(
+    database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
+    Path(output_id): Path<String>,
+) -> ApiResult<NormalizedOutputResponse> {
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let output_id = OutputId::from_str(&output_id).map_err(RequestError::from)?;
+    let res = config
+        .output_cache
+        .get_output_with_metadata(&database, &output_id, ledger_index)
+        .await?
+        .ok_or(MissingError::NoResults)?;
+
+    let hrp = latest_bech32_hrp(&database).await?;
+
+    Ok(NormalizedOutputResponse {
+        output_id: output_id.to_hex(),
+        nft: NftDetailsDto::from_output(&res.output, &hrp),
+        output: res.output.into(),
+        is_spent: res.metadata.spent_metadata.is_some(),
+        ledger_index,
+    })
+}
+
+/// Gets the block that created an output, which is the block that included the transaction of the output.
+async fn output_block(database: Extension<MongoDb>, Path(output_id): Path<String>) -> ApiResult<OutputBlockResponse> {
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let output_id = OutputId::from_str(&output_id).map_err(RequestError::from)?;
+    let metadata = database
+        .collection::<OutputCollection>()
+        .get_output_metadata(&output_id, ledger_index)
+        .await?
+        .ok_or(MissingError::NotFound)?;
+
+    Ok(OutputBlockResponse {
+        output_id: output_id.to_hex(),
+        block_id: metadata.block_id.to_hex(),
+        ledger_index,
+    })
+}
+
+async fn protocol_parameters_by_milestone(
+    database: Extension<MongoDb>,
+    Path(milestone_index): Path<MilestoneIndex>,
+) -> ApiResult<ProtocolParametersResponse> {
+    let update = database
+        .collection::<ProtocolUpdateCollection>()
+        .get_protocol_parameters_for_ledger_index(milestone_index)
+        .await?
+        .ok_or(MissingError::NoResults)?;
+
+    Ok(ProtocolParametersResponse {
+        parameters: create_protocol_parameters_response(update.parameters),
+        activated_at: update.tangle_index,
+        milestone_index,
+    })
+}
+
+/// Computes the minimum storage deposit of an output template according to the current rent structure.
+async fn storage_deposit(
+    database: Extension<MongoDb>,
+    Json(output): Json<iota_types::block::output::dto::OutputDto>,
+) -> ApiResult<StorageDepositResponse> {
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let protocol_params = iota_types::block::protocol::ProtocolParameters::try_from(
+        database
+            .collection::<ProtocolUpdateCollection>()
+            .get_protocol_parameters_for_ledger_index(ledger_index)
+            .await?
+            .ok_or(CorruptStateError::ProtocolParams)?
+            .parameters,
+    )?;
+
+    // The amount of the template is not verified, as it does not affect the byte cost of the output.
+    let output = iota_types::block::output::Output::try_from_dto_unverified(&output).map_err(RequestError::from)?;
+    if output.is_treasury() {
+        return Err(
+            RequestError::UnsupportedOutputTemplate("treasury outputs do not require a storage deposit").into(),
+        );
+    }
+    let storage_deposit = iota_types::block::output::Rent::rent_cost(&output, protocol_params.rent_structure());
+
+    Ok(StorageDepositResponse {
+        storage_deposit: storage_deposit.to_string().into(),
+        ledger_index,
+    })
+}
+
+/// Gets all blocks that contain a transaction, so that reattachments of the transaction can be inspected.
+async fn transaction_blocks(
+    database: Extension<MongoDb>,
+    Path(transaction_id): Path<String>,
+    TransactionBlocksPagination { page_size, cursor }: TransactionBlocksPagination,
+) -> ApiResult<TransactionBlocksResponse> {
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let transaction_id = TransactionId::from_str(&transaction_id).map_err(RequestError::from)?;
+
+    let mut record_stream = database
+        .collection::<BlockCollection>()
+        // Get one extra record so that we can create the cursor.
+        .get_blocks_for_transaction(&transaction_id, page_size + 1, cursor)
+        .await?;
+
+    // Take all of the requested records first
+    let blocks = record_stream
+        .by_ref()
+        .take(page_size)
+        .map_ok(|res| {
+            let (state, referenced_by_milestone_index) = confirmation_state(&res.metadata, ledger_index);
+            TransactionBlockDto {
+                block_id: res.block_id.to_hex(),
+                state,
+                referenced_by_milestone_index,
+            }
+        })
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    if blocks.is_empty() && cursor.is_none() {
+        return Err(MissingError::NoResults.into());
+    }
+
+    // If any record is left, use it to make the cursor
+    let cursor = record_stream.try_next().await?.map(|rec| {
+        TransactionBlocksCursor {
+            block_id: rec.block_id,
+            page_size,
+        }
+        .to_string()
+    });
+
+    Ok(TransactionBlocksResponse {
+        transaction_id: transaction_id.to_hex(),
+        blocks,
+        cursor,
+        ledger_index,
+    })
+}
+
+async fn transaction_outputs(
+    database: Extension<MongoDb>,
+    Path(transaction_id): Path<String>,
+    TransactionOutputsPagination {
+        page_size,
+        cursor,
+        include_outputs,
+    }: TransactionOutputsPagination,
+) -> ApiResult<TransactionOutputsResponse> {
+    let ledger_index = database
+        .collection::<MilestoneCollection>()
+        .get_ledger_index()
+        .await?
+        .ok_or(NotSyncedError)?;
+    let transaction_id = TransactionId::from_str(&transaction_id).map_err(RequestError::from)?;
+
+    let mut record_stream = database
+        .collection::<OutputCollection>()
+        .get_outputs_by_transaction_id(
+            transaction_id,
+            cursor.unwrap_or_default(),
+            // Get one extra record so that we can create the cursor.
+            page_size + 1,
+            ledger_index,
+        )
+        .await?;
+    let hrp = if include_outputs {
+        Some(latest_bech32_hrp(&database).await?)
+    } else {
+        None
+    };
+
+    // Take all of the requested records first
+    let items = record_stream
+        .by_ref()
+        .take(page_size)
+        .map_ok(|res| TransactionOutputDto {
+            output_id: res.metadata.output_id.to_hex(),
+            is_spent: matches!(
+                res.metadata.spent_metadata,
+                Some(spent) if spent.spent.milestone_index <= ledger_index
+            ),
+            nft: hrp.as_deref().and_then(|hrp| NftDetailsDto::from_output(&res.output, hrp)),
+            output: include_outputs.then(|| res.output.into()),
+        })
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    if items.is_empty() && cursor.is_none() {
+        return Err(MissingError::NoResults.into());
+    }
+
+    // If any record is left, use it to make the cursor
+    let cursor = record_stream.try_next().await?.map(|rec| {
+        TransactionOutputsCursor {
+            output_index: rec.metadata.output_id.index,
+            page_size,
+        }
+        .to_string()
+    });
+
+    Ok(TransactionOutputsResponse {
+        transaction_id: transaction_id.to_hex(),
+        items,
+        cursor,
+        ledger_index,
     })
 }
@@ -0,0 +1,81 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chronicle::model::tangle::MilestoneIndex;
+
+/// Debounces the health status, so that brief pauses in milestone delivery do not flap it.
+///
+/// The tracker keeps a rolling window of the fresh milestones that arrived within the grace period. Chronicle is
+/// reported as healthy while the newest milestone is fresh, or while the window still holds an arrival, so it only
+/// becomes unhealthy once the staleness persists beyond the grace period. Chronicle is unhealthy until the first fresh
+/// milestone arrives.
+#[derive(Clone, Debug, Default)]
+pub struct HealthTracker {
+    grace_period: Duration,
+    arrivals: Arc<Mutex<VecDeque<(MilestoneIndex, Instant)>>>,
+}
+
+impl HealthTracker {
+    pub fn new(grace_period: Duration) -> Self {
+        Self {
+            grace_period,
+            arrivals: Default::default(),
+        }
+    }
+
+    /// Records the newest milestone and whether it is fresh, and returns whether Chronicle should be reported as
+    /// healthy.
+    pub fn observe(&self, newest: Option<MilestoneIndex>, fresh: bool) -> bool {
+        self.observe_at(newest, fresh, Instant::now())
+    }
+
+    fn observe_at(&self, newest: Option<MilestoneIndex>, fresh: bool, now: Instant) -> bool {
+        // Panic: The lock is never held across a panic.
+        let mut arrivals = self.arrivals.lock().unwrap();
+        if let (Some(index), true) = (newest, fresh) {
+            if arrivals.back().map_or(true, |&(last, _)| last != index) {
+                arrivals.push_back((index, now));
+            }
+        }
+        while arrivals
+            .front()
+            .map_or(false, |&(_, at)| now.saturating_duration_since(at) > self.grace_period)
+        {
+            arrivals.pop_front();
+        }
+        fresh || !arrivals.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::HealthTracker;
+
+    #[test]
+    fn test_grace_period() {
+        let tracker = HealthTracker::new(Duration::from_secs(60));
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        // Never healthy before a fresh milestone arrived.
+        assert!(!tracker.observe_at(Some(1.into()), false, start));
+        assert!(tracker.observe_at(Some(2.into()), true, start));
+        assert!(tracker.observe_at(Some(3.into()), true, at(10)));
+        // Observing the same milestone again does not count as an arrival.
+        assert!(tracker.observe_at(Some(3.into()), true, at(60)));
+        assert!(tracker.observe_at(Some(3.into()), false, at(70)));
+        assert!(!tracker.observe_at(Some(3.into()), false, at(71)));
+        assert!(tracker.observe_at(Some(4.into()), true, at(90)));
+
+        let tracker = HealthTracker::new(Duration::ZERO);
+        assert!(tracker.observe_at(Some(1.into()), true, start));
+        assert!(!tracker.observe_at(Some(1.into()), false, at(1)));
+    }
+}
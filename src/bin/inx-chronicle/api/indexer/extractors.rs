@@ -5,21 +5,30 @@ use std::{fmt::Display, str::FromStr};
 
 use async_trait::async_trait;
 use axum::{
-    extract::{FromRequest, Query},
+    extract::{FromRequest, Query, RequestParts},
     Extension,
 };
 use chronicle::{
-    db::mongodb::collections::{AliasOutputsQuery, BasicOutputsQuery, FoundryOutputsQuery, NftOutputsQuery, SortOrder},
+    db::mongodb::collections::{
+        AliasOutputsQuery, BasicOutputsQuery, FoundryOutputsQuery, LedgerIndexKind, NftOutputsQuery, OutputCondition,
+        SortOrder,
+    },
     model::{
         tangle::MilestoneIndex,
-        utxo::{Address, OutputId, Tag},
+        utxo::{
+            Address, AliasAddress, AliasOutput, BasicOutput, FoundryOutput, NativeTokenId, NftOutput, OutputId, Tag,
+        },
     },
 };
 use mongodb::bson;
 use primitive_types::U256;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
 
-use crate::api::{config::ApiConfigData, error::RequestError, ApiError, DEFAULT_PAGE_SIZE};
+use crate::api::{
+    config::{ApiConfigData, CursorFormat},
+    error::RequestError,
+    ApiError, DEFAULT_PAGE_SIZE,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct IndexedOutputsPagination<Q>
@@ -31,6 +40,10 @@ where
     pub cursor: Option<(MilestoneIndex, OutputId)>,
     pub sort: SortOrder,
     pub include_spent: bool,
+    /// Whether to return a random sample of matching outputs instead of a page.
+    pub sample: bool,
+    /// The milestone that the ledger index is resolved against.
+    pub ledger_index: LedgerIndexKind,
 }
 
 #[derive(Clone)]
@@ -40,6 +53,41 @@ pub struct IndexedOutputsCursor {
     pub page_size: usize,
 }
 
+impl IndexedOutputsCursor {
+    /// The number of hex characters that encode the milestone index in the node format.
+    const NODE_MILESTONE_INDEX_LEN: usize = 8;
+
+    /// Encodes the cursor in the given format.
+    pub fn encode(&self, format: CursorFormat) -> String {
+        match format {
+            CursorFormat::Chronicle => self.to_string(),
+            // The node concatenates the big-endian milestone index and the output id bytes into one hex string.
+            CursorFormat::Node => format!(
+                "{:08x}{}.{}",
+                self.milestone_index.0,
+                self.output_id.to_hex().trim_start_matches("0x"),
+                self.page_size
+            ),
+        }
+    }
+
+    fn from_node_str(position: &str, page_size: &str) -> Result<Self, ApiError> {
+        if position.len() <= Self::NODE_MILESTONE_INDEX_LEN
+            || !position.is_char_boundary(Self::NODE_MILESTONE_INDEX_LEN)
+        {
+            return Err(ApiError::from(RequestError::BadPagingState));
+        }
+        let (ms, o) = position.split_at(Self::NODE_MILESTONE_INDEX_LEN);
+        Ok(IndexedOutputsCursor {
+            milestone_index: u32::from_str_radix(ms, 16)
+                .map_err(|_| RequestError::BadPagingState)?
+                .into(),
+            output_id: format!("0x{o}").parse().map_err(RequestError::from)?,
+            page_size: page_size.parse().map_err(RequestError::from)?,
+        })
+    }
+}
+
 impl FromStr for IndexedOutputsCursor {
     type Err = ApiError;
 
@@ -51,6 +99,7 @@ impl FromStr for IndexedOutputsCursor {
                 output_id: o.parse().map_err(RequestError::from)?,
                 page_size: ps.parse().map_err(RequestError::from)?,
             },
+            [position, ps] => Self::from_node_str(position, ps)?,
             _ => return Err(ApiError::from(RequestError::BadPagingState)),
         })
     }
@@ -68,13 +117,124 @@ impl Display for IndexedOutputsCursor {
     }
 }
 
+/// Parses the query string, naming the first parameter that is not supported for the requested output kind if the
+/// query contains one.
+async fn parse_query<Q: DeserializeOwned, B: Send>(
+    req: &mut RequestParts<B>,
+    output_kind: &'static str,
+) -> Result<Q, RequestError> {
+    match Query::<Q>::from_request(req).await {
+        Ok(Query(query)) => Ok(query),
+        Err(e) => {
+            let parameters = struct_fields::<Q>();
+            // The parameter names are percent-decoded the same way as by the `Query` extractor.
+            let unsupported = req
+                .uri()
+                .query()
+                .and_then(|query| serde_urlencoded::from_str::<Vec<(String, String)>>(query).ok())
+                .and_then(|pairs| {
+                    pairs
+                        .into_iter()
+                        .map(|(parameter, _)| parameter)
+                        .find(|parameter| !parameter.is_empty() && !parameters.contains(&parameter.as_str()))
+                });
+            Err(match unsupported {
+                Some(parameter) => RequestError::UnsupportedQueryParameter { parameter, output_kind },
+                None => e.into(),
+            })
+        }
+    }
+}
+
+/// Parses a bech32 address that must be an alias address.
+fn parse_alias_address(address: &str) -> Result<AliasAddress, RequestError> {
+    match Address::from_str(address)? {
+        Address::Alias(alias_address) => Ok(alias_address),
+        _ => Err(RequestError::NotAnAliasAddress(address.to_string())),
+    }
+}
+
+/// Parses a comma separated list of [`OutputCondition`]s, of which an output has to match at least one.
+///
+/// Only this fixed set of conditions can be combined, which keeps the resulting `$or` query free of user input.
+fn parse_any_of(conditions: &str) -> Result<Vec<OutputCondition>, RequestError> {
+    let mut res = Vec::new();
+    for condition in conditions.split(',').map(str::trim) {
+        let condition = match condition {
+            "nativeTokens" => OutputCondition::NativeTokens,
+            "storageDepositReturn" => OutputCondition::StorageDepositReturn,
+            "timelock" => OutputCondition::Timelock,
+            "expiration" => OutputCondition::Expiration,
+            _ => return Err(RequestError::InvalidOutputCondition(condition.to_string())),
+        };
+        if !res.contains(&condition) {
+            res.push(condition);
+        }
+    }
+    Ok(res)
+}
+
+/// Gets the (renamed) field names of a struct from its `Deserialize` implementation.
+fn struct_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldsDeserializer<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de, 'a> Deserializer<'de> for FieldsDeserializer<'a> {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: serde::de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: serde::de::Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(serde::de::Error::custom("fields captured"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+            unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    T::deserialize(FieldsDeserializer(&mut fields)).ok();
+    fields
+}
+
+#[derive(Copy, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct LedgerIndexQuery {
+    /// The milestone that the ledger index is resolved against.
+    pub ledger_index: LedgerIndexKind,
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for LedgerIndexQuery {
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<LedgerIndexQuery>::from_request(req)
+            .await
+            .map_err(RequestError::from)?;
+        Ok(query)
+    }
+}
+
 #[derive(Clone, Deserialize, Default)]
 #[serde(default, deny_unknown_fields, rename_all = "camelCase")]
 pub struct BasicOutputsPaginationQuery {
     pub address: Option<String>,
+    pub alias_address: Option<String>,
     pub has_native_tokens: Option<bool>,
     pub min_native_token_count: Option<String>,
     pub max_native_token_count: Option<String>,
+    pub native_token_id: Option<String>,
+    pub min_native_token_amount: Option<String>,
     pub has_storage_deposit_return: Option<bool>,
     pub storage_deposit_return_address: Option<String>,
     pub has_timelock: Option<bool>,
@@ -84,26 +244,36 @@ pub struct BasicOutputsPaginationQuery {
     pub expires_before: Option<u32>,
     pub expires_after: Option<u32>,
     pub expiration_return_address: Option<String>,
+    pub any_of: Option<String>,
     pub sender: Option<String>,
     pub tag: Option<String>,
     pub created_before: Option<u32>,
     pub created_after: Option<u32>,
+    pub spent_in_milestone: Option<u32>,
     pub page_size: Option<usize>,
     pub cursor: Option<String>,
     pub sort: Option<String>,
     pub include_spent: Option<bool>,
+    pub sample: Option<bool>,
+    pub ledger_index: Option<LedgerIndexKind>,
 }
 
 #[async_trait]
 impl<B: Send> FromRequest<B> for IndexedOutputsPagination<BasicOutputsQuery> {
     type Rejection = ApiError;
 
-    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let Query(query) = Query::<BasicOutputsPaginationQuery>::from_request(req)
-            .await
-            .map_err(RequestError::from)?;
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let query = parse_query::<BasicOutputsPaginationQuery, _>(req, BasicOutput::KIND).await?;
         let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
 
+        let sample = query.sample.unwrap_or_default();
+        if sample && query.cursor.is_some() {
+            return Err(ApiError::from(RequestError::SampleWithCursor));
+        }
+        if query.min_native_token_amount.is_some() && query.native_token_id.is_none() {
+            return Err(ApiError::from(RequestError::MinNativeTokenAmountWithoutTokenId));
+        }
+
         let (cursor, page_size) = if let Some(cursor) = query.cursor {
             let cursor: IndexedOutputsCursor = cursor.parse()?;
             (Some((cursor.milestone_index, cursor.output_id)), cursor.page_size)
@@ -124,6 +294,7 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<BasicOutputsQuery> {
                     .map(|address| Address::from_str(&address))
                     .transpose()
                     .map_err(RequestError::from)?,
+                alias_address: query.alias_address.as_deref().map(parse_alias_address).transpose()?,
                 has_native_tokens: query.has_native_tokens,
                 min_native_token_count: query
                     .min_native_token_count
@@ -135,6 +306,16 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<BasicOutputsQuery> {
                     .map(|c| U256::from_dec_str(&c))
                     .transpose()
                     .map_err(RequestError::from)?,
+                native_token_id: query
+                    .native_token_id
+                    .map(|token_id| NativeTokenId::from_str(&token_id))
+                    .transpose()
+                    .map_err(RequestError::from)?,
+                min_native_token_amount: query
+                    .min_native_token_amount
+                    .map(|c| U256::from_dec_str(&c))
+                    .transpose()
+                    .map_err(RequestError::from)?,
                 has_storage_deposit_return: query.has_storage_deposit_return,
                 storage_deposit_return_address: query
                     .storage_deposit_return_address
@@ -152,6 +333,12 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<BasicOutputsQuery> {
                     .map(|address| Address::from_str(&address))
                     .transpose()
                     .map_err(RequestError::from)?,
+                any_of: query
+                    .any_of
+                    .as_deref()
+                    .map(parse_any_of)
+                    .transpose()?
+                    .unwrap_or_default(),
                 sender: query
                     .sender
                     .map(|address| Address::from_str(&address))
@@ -164,11 +351,15 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<BasicOutputsQuery> {
                     .map_err(RequestError::from)?,
                 created_before: query.created_before.map(Into::into),
                 created_after: query.created_after.map(Into::into),
+                spent_in_milestone: query.spent_in_milestone.map(Into::into),
             },
             page_size: page_size.min(config.max_page_size),
             cursor,
             sort,
-            include_spent: query.include_spent.unwrap_or_default(),
+            // Outputs spent in a given milestone can only be found if spent outputs are included.
+            include_spent: query.include_spent.unwrap_or_default() || query.spent_in_milestone.is_some(),
+            sample,
+            ledger_index: query.ledger_index.unwrap_or_default(),
         })
     }
 }
@@ -183,24 +374,35 @@ pub struct AliasOutputsPaginationQuery {
     pub has_native_tokens: Option<bool>,
     pub min_native_token_count: Option<String>,
     pub max_native_token_count: Option<String>,
+    pub native_token_id: Option<String>,
+    pub min_native_token_amount: Option<String>,
     pub created_before: Option<u32>,
     pub created_after: Option<u32>,
+    pub spent_in_milestone: Option<u32>,
     pub page_size: Option<usize>,
     pub cursor: Option<String>,
     pub sort: Option<String>,
     pub include_spent: Option<bool>,
+    pub sample: Option<bool>,
+    pub ledger_index: Option<LedgerIndexKind>,
 }
 
 #[async_trait]
 impl<B: Send> FromRequest<B> for IndexedOutputsPagination<AliasOutputsQuery> {
     type Rejection = ApiError;
 
-    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let Query(query) = Query::<AliasOutputsPaginationQuery>::from_request(req)
-            .await
-            .map_err(RequestError::from)?;
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let query = parse_query::<AliasOutputsPaginationQuery, _>(req, AliasOutput::KIND).await?;
         let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
 
+        let sample = query.sample.unwrap_or_default();
+        if sample && query.cursor.is_some() {
+            return Err(ApiError::from(RequestError::SampleWithCursor));
+        }
+        if query.min_native_token_amount.is_some() && query.native_token_id.is_none() {
+            return Err(ApiError::from(RequestError::MinNativeTokenAmountWithoutTokenId));
+        }
+
         let (cursor, page_size) = if let Some(cursor) = query.cursor {
             let cursor: IndexedOutputsCursor = cursor.parse()?;
             (Some((cursor.milestone_index, cursor.output_id)), cursor.page_size)
@@ -247,13 +449,27 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<AliasOutputsQuery> {
                     .map(|c| U256::from_dec_str(&c))
                     .transpose()
                     .map_err(RequestError::from)?,
+                native_token_id: query
+                    .native_token_id
+                    .map(|token_id| NativeTokenId::from_str(&token_id))
+                    .transpose()
+                    .map_err(RequestError::from)?,
+                min_native_token_amount: query
+                    .min_native_token_amount
+                    .map(|c| U256::from_dec_str(&c))
+                    .transpose()
+                    .map_err(RequestError::from)?,
                 created_before: query.created_before.map(Into::into),
                 created_after: query.created_after.map(Into::into),
+                spent_in_milestone: query.spent_in_milestone.map(Into::into),
             },
             page_size: page_size.min(config.max_page_size),
             cursor,
             sort,
-            include_spent: query.include_spent.unwrap_or_default(),
+            // Outputs spent in a given milestone can only be found if spent outputs are included.
+            include_spent: query.include_spent.unwrap_or_default() || query.spent_in_milestone.is_some(),
+            sample,
+            ledger_index: query.ledger_index.unwrap_or_default(),
         })
     }
 }
@@ -265,24 +481,35 @@ pub struct FoundryOutputsPaginationQuery {
     pub has_native_tokens: Option<bool>,
     pub min_native_token_count: Option<String>,
     pub max_native_token_count: Option<String>,
+    pub native_token_id: Option<String>,
+    pub min_native_token_amount: Option<String>,
     pub created_before: Option<u32>,
     pub created_after: Option<u32>,
+    pub spent_in_milestone: Option<u32>,
     pub page_size: Option<usize>,
     pub cursor: Option<String>,
     pub sort: Option<String>,
     pub include_spent: Option<bool>,
+    pub sample: Option<bool>,
+    pub ledger_index: Option<LedgerIndexKind>,
 }
 
 #[async_trait]
 impl<B: Send> FromRequest<B> for IndexedOutputsPagination<FoundryOutputsQuery> {
     type Rejection = ApiError;
 
-    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let Query(query) = Query::<FoundryOutputsPaginationQuery>::from_request(req)
-            .await
-            .map_err(RequestError::from)?;
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let query = parse_query::<FoundryOutputsPaginationQuery, _>(req, FoundryOutput::KIND).await?;
         let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
 
+        let sample = query.sample.unwrap_or_default();
+        if sample && query.cursor.is_some() {
+            return Err(ApiError::from(RequestError::SampleWithCursor));
+        }
+        if query.min_native_token_amount.is_some() && query.native_token_id.is_none() {
+            return Err(ApiError::from(RequestError::MinNativeTokenAmountWithoutTokenId));
+        }
+
         let (cursor, page_size) = if let Some(cursor) = query.cursor {
             let cursor: IndexedOutputsCursor = cursor.parse()?;
             (Some((cursor.milestone_index, cursor.output_id)), cursor.page_size)
@@ -298,11 +525,7 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<FoundryOutputsQuery> {
 
         Ok(IndexedOutputsPagination {
             query: FoundryOutputsQuery {
-                alias_address: query
-                    .alias_address
-                    .map(|address| Address::from_str(&address))
-                    .transpose()
-                    .map_err(RequestError::from)?,
+                alias_address: query.alias_address.as_deref().map(parse_alias_address).transpose()?,
                 has_native_tokens: query.has_native_tokens,
                 min_native_token_count: query
                     .min_native_token_count
@@ -314,13 +537,27 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<FoundryOutputsQuery> {
                     .map(|c| U256::from_dec_str(&c))
                     .transpose()
                     .map_err(RequestError::from)?,
+                native_token_id: query
+                    .native_token_id
+                    .map(|token_id| NativeTokenId::from_str(&token_id))
+                    .transpose()
+                    .map_err(RequestError::from)?,
+                min_native_token_amount: query
+                    .min_native_token_amount
+                    .map(|c| U256::from_dec_str(&c))
+                    .transpose()
+                    .map_err(RequestError::from)?,
                 created_before: query.created_before.map(Into::into),
                 created_after: query.created_after.map(Into::into),
+                spent_in_milestone: query.spent_in_milestone.map(Into::into),
             },
             page_size: page_size.min(config.max_page_size),
             cursor,
             sort,
-            include_spent: query.include_spent.unwrap_or_default(),
+            // Outputs spent in a given milestone can only be found if spent outputs are included.
+            include_spent: query.include_spent.unwrap_or_default() || query.spent_in_milestone.is_some(),
+            sample,
+            ledger_index: query.ledger_index.unwrap_or_default(),
         })
     }
 }
@@ -329,11 +566,14 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<FoundryOutputsQuery> {
 #[serde(default, deny_unknown_fields, rename_all = "camelCase")]
 pub struct NftOutputsPaginationQuery {
     pub address: Option<String>,
+    pub alias_address: Option<String>,
     pub issuer: Option<String>,
     pub sender: Option<String>,
     pub has_native_tokens: Option<bool>,
     pub min_native_token_count: Option<String>,
     pub max_native_token_count: Option<String>,
+    pub native_token_id: Option<String>,
+    pub min_native_token_amount: Option<String>,
     pub has_storage_deposit_return: Option<bool>,
     pub storage_deposit_return_address: Option<String>,
     pub has_timelock: Option<bool>,
@@ -343,25 +583,35 @@ pub struct NftOutputsPaginationQuery {
     pub expires_before: Option<u32>,
     pub expires_after: Option<u32>,
     pub expiration_return_address: Option<String>,
+    pub any_of: Option<String>,
     pub tag: Option<String>,
     pub created_before: Option<u32>,
     pub created_after: Option<u32>,
+    pub spent_in_milestone: Option<u32>,
     pub page_size: Option<usize>,
     pub cursor: Option<String>,
     pub sort: Option<String>,
     pub include_spent: Option<bool>,
+    pub sample: Option<bool>,
+    pub ledger_index: Option<LedgerIndexKind>,
 }
 
 #[async_trait]
 impl<B: Send> FromRequest<B> for IndexedOutputsPagination<NftOutputsQuery> {
     type Rejection = ApiError;
 
-    async fn from_request(req: &mut axum::extract::RequestParts<B>) -> Result<Self, Self::Rejection> {
-        let Query(query) = Query::<NftOutputsPaginationQuery>::from_request(req)
-            .await
-            .map_err(RequestError::from)?;
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let query = parse_query::<NftOutputsPaginationQuery, _>(req, NftOutput::KIND).await?;
         let Extension(config) = Extension::<ApiConfigData>::from_request(req).await?;
 
+        let sample = query.sample.unwrap_or_default();
+        if sample && query.cursor.is_some() {
+            return Err(ApiError::from(RequestError::SampleWithCursor));
+        }
+        if query.min_native_token_amount.is_some() && query.native_token_id.is_none() {
+            return Err(ApiError::from(RequestError::MinNativeTokenAmountWithoutTokenId));
+        }
+
         let (cursor, page_size) = if let Some(cursor) = query.cursor {
             let cursor: IndexedOutputsCursor = cursor.parse()?;
             (Some((cursor.milestone_index, cursor.output_id)), cursor.page_size)
@@ -382,6 +632,7 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<NftOutputsQuery> {
                     .map(|address| Address::from_str(&address))
                     .transpose()
                     .map_err(RequestError::from)?,
+                alias_address: query.alias_address.as_deref().map(parse_alias_address).transpose()?,
                 issuer: query
                     .issuer
                     .map(|address| Address::from_str(&address))
@@ -403,6 +654,16 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<NftOutputsQuery> {
                     .map(|c| U256::from_dec_str(&c))
                     .transpose()
                     .map_err(RequestError::from)?,
+                native_token_id: query
+                    .native_token_id
+                    .map(|token_id| NativeTokenId::from_str(&token_id))
+                    .transpose()
+                    .map_err(RequestError::from)?,
+                min_native_token_amount: query
+                    .min_native_token_amount
+                    .map(|c| U256::from_dec_str(&c))
+                    .transpose()
+                    .map_err(RequestError::from)?,
                 has_storage_deposit_return: query.has_storage_deposit_return,
                 storage_deposit_return_address: query
                     .storage_deposit_return_address
@@ -420,6 +681,12 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<NftOutputsQuery> {
                     .map(|address| Address::from_str(&address))
                     .transpose()
                     .map_err(RequestError::from)?,
+                any_of: query
+                    .any_of
+                    .as_deref()
+                    .map(parse_any_of)
+                    .transpose()?
+                    .unwrap_or_default(),
                 tag: query
                     .tag
                     .map(|tag| Tag::from_str(&tag))
@@ -427,18 +694,22 @@ impl<B: Send> FromRequest<B> for IndexedOutputsPagination<NftOutputsQuery> {
                     .map_err(RequestError::from)?,
                 created_before: query.created_before.map(Into::into),
                 created_after: query.created_after.map(Into::into),
+                spent_in_milestone: query.spent_in_milestone.map(Into::into),
             },
             page_size: page_size.min(config.max_page_size),
             cursor,
             sort,
-            include_spent: query.include_spent.unwrap_or_default(),
+            // Outputs spent in a given milestone can only be found if spent outputs are included.
+            include_spent: query.include_spent.unwrap_or_default() || query.spent_in_milestone.is_some(),
+            sample,
+            ledger_index: query.ledger_index.unwrap_or_default(),
         })
     }
 }
 
 #[cfg(test)]
 mod test {
-    use axum::{extract::RequestParts, http::Request};
+    use axum::http::Request;
 
     use super::*;
     use crate::api::ApiConfig;
@@ -452,6 +723,14 @@ mod test {
         let cursor = format!("{milestone_index}.{output_id_str}.{page_size_str}",);
         let parsed: IndexedOutputsCursor = cursor.parse().unwrap();
         assert_eq!(parsed.to_string(), cursor);
+        assert_eq!(parsed.encode(CursorFormat::Chronicle), cursor);
+
+        let node_cursor = format!("09cb9a94{}.{page_size_str}", &output_id_str[2..]);
+        assert_eq!(parsed.encode(CursorFormat::Node), node_cursor);
+        let parsed: IndexedOutputsCursor = node_cursor.parse().unwrap();
+        assert_eq!(parsed.to_string(), cursor);
+
+        assert!("09cb9594.1337".parse::<IndexedOutputsCursor>().is_err());
     }
 
     #[tokio::test]
@@ -473,8 +752,100 @@ mod test {
                 query: Default::default(),
                 cursor: Default::default(),
                 sort: Default::default(),
-                include_spent: Default::default()
+                include_spent: Default::default(),
+                sample: Default::default(),
+                ledger_index: Default::default(),
             }
         );
     }
+
+    #[tokio::test]
+    async fn ledger_index_kind() {
+        let mut req = RequestParts::new(
+            Request::builder()
+                .method("GET")
+                .uri("/outputs/nft?ledgerIndex=latest")
+                .extension(ApiConfigData::try_from(ApiConfig::default()).unwrap())
+                .body(())
+                .unwrap(),
+        );
+        assert_eq!(
+            IndexedOutputsPagination::<NftOutputsQuery>::from_request(&mut req)
+                .await
+                .unwrap()
+                .ledger_index,
+            LedgerIndexKind::Latest
+        );
+    }
+
+    #[tokio::test]
+    async fn any_of_conditions() {
+        let request = |uri: &str| {
+            RequestParts::new(
+                Request::builder()
+                    .method("GET")
+                    .uri(uri)
+                    .extension(ApiConfigData::try_from(ApiConfig::default()).unwrap())
+                    .body(())
+                    .unwrap(),
+            )
+        };
+        assert_eq!(
+            IndexedOutputsPagination::<BasicOutputsQuery>::from_request(&mut request(
+                "/outputs/basic?anyOf=timelock,expiration,timelock"
+            ))
+            .await
+            .unwrap()
+            .query
+            .any_of,
+            vec![OutputCondition::Timelock, OutputCondition::Expiration]
+        );
+        let err = IndexedOutputsPagination::<BasicOutputsQuery>::from_request(&mut request(
+            "/outputs/basic?anyOf=timelock,$where",
+        ))
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err.error.downcast_ref::<RequestError>(),
+            Some(RequestError::InvalidOutputCondition(condition)) if condition == "$where"
+        ));
+    }
+
+    #[tokio::test]
+    async fn sample_with_cursor_rejected() {
+        let mut req = RequestParts::new(
+            Request::builder()
+                .method("GET")
+                .uri("/outputs/basic?sample=true&cursor=164338324.0xfa0de75d225cca2799395e5fc340702fc7eac821d2bdd79911126f131ae097a20100.100")
+                .extension(ApiConfigData::try_from(ApiConfig::default()).unwrap())
+                .body(())
+                .unwrap(),
+        );
+        let err = IndexedOutputsPagination::<BasicOutputsQuery>::from_request(&mut req)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.error.downcast_ref::<RequestError>(),
+            Some(RequestError::SampleWithCursor)
+        ));
+    }
+
+    #[tokio::test]
+    async fn unsupported_parameter_rejected() {
+        let mut req = RequestParts::new(
+            Request::builder()
+                .method("GET")
+                .uri("/outputs/foundry?hasNativeTokens=true&t%61g=0x01")
+                .extension(ApiConfigData::try_from(ApiConfig::default()).unwrap())
+                .body(())
+                .unwrap(),
+        );
+        let err = IndexedOutputsPagination::<FoundryOutputsQuery>::from_request(&mut req)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.error.downcast_ref::<RequestError>(),
+            Some(RequestError::UnsupportedQueryParameter { parameter, output_kind: "foundry" }) if parameter == "tag"
+        ));
+    }
 }
@@ -4,15 +4,31 @@
 use chronicle::model::tangle::MilestoneIndex;
 use serde::{Deserialize, Serialize};
 
-use crate::api::responses::impl_success_response;
+use crate::api::responses::{impl_success_response, NftDetailsDto};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IndexerOutputsResponse {
     pub ledger_index: MilestoneIndex,
     pub items: Vec<String>,
+    /// The alias, foundry or NFT ids of the outputs in `items`, in the same order. Omitted for basic outputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ids: Option<Vec<String>>,
+    /// The decoded immutable features of the outputs in `items`, in the same order. Only included for NFT outputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nfts: Option<Vec<NftDetailsDto>>,
+    /// The spent status of the outputs in `items`, in the same order. Only included if spent outputs were requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statuses: Option<Vec<OutputStatusDto>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
 }
 
 impl_success_response!(IndexerOutputsResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputStatusDto {
+    pub output_id: String,
+    pub spent: bool,
+}
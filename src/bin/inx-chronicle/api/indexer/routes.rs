@@ -7,20 +7,27 @@ use axum::{extract::Path, routing::get, Extension};
 use chronicle::{
     db::{
         mongodb::collections::{
-            AliasOutputsQuery, BasicOutputsQuery, FoundryOutputsQuery, IndexedId, MilestoneCollection, NftOutputsQuery,
-            OutputCollection,
+            AliasOutputsQuery, BasicOutputsQuery, FoundryOutputsQuery, IndexedId, NftOutputsQuery, OutputCollection,
+            OutputResult, SpentInMilestoneQuery,
         },
         MongoDb,
     },
-    model::utxo::{AliasId, FoundryId, NftId},
+    model::utxo::{Address, AliasAddress, AliasId, FoundryId, NftAddress, NftId},
 };
 use mongodb::bson;
 
-use super::{extractors::IndexedOutputsPagination, responses::IndexerOutputsResponse};
+use super::{
+    extractors::{IndexedOutputsPagination, LedgerIndexQuery},
+    responses::{IndexerOutputsResponse, OutputStatusDto},
+};
 use crate::api::{
-    error::{MissingError, RequestError},
+    config::ApiConfigData,
+    denylist::check_denylist,
+    error::{MissingError, NotSyncedError, RequestError},
     indexer::extractors::IndexedOutputsCursor,
+    responses::NftDetailsDto,
     router::Router,
+    routes::latest_bech32_hrp,
     ApiResult,
 };
 
@@ -52,48 +59,91 @@ pub fn routes() -> Router {
 
 async fn indexed_output_by_id<ID>(
     database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
     Path(id): Path<String>,
+    LedgerIndexQuery { ledger_index }: LedgerIndexQuery,
 ) -> ApiResult<IndexerOutputsResponse>
 where
     ID: Into<IndexedId> + FromStr,
     RequestError: From<ID::Err>,
 {
-    let ledger_index = database
-        .collection::<MilestoneCollection>()
-        .get_ledger_index()
+    let ledger_index = config
+        .ledger_index_cache
+        .get_ledger_index_of_kind(&database, ledger_index)
         .await?
-        .ok_or(MissingError::NoResults)?;
-    let id = ID::from_str(&id).map_err(RequestError::from)?;
+        .ok_or(NotSyncedError)?;
+    let id: IndexedId = ID::from_str(&id).map_err(RequestError::from)?.into();
+    // Alias and NFT IDs are also addresses.
+    let address = match id {
+        IndexedId::Alias(id) => Some(Address::Alias(AliasAddress(id))),
+        IndexedId::Nft(id) => Some(Address::Nft(NftAddress(id))),
+        IndexedId::Foundry(_) => None,
+    };
+    if let Some(address) = address {
+        check_denylist(&config, &address).await?;
+    }
     let res = database
         .collection::<OutputCollection>()
         .get_indexed_output_by_id(id, ledger_index)
         .await?
         .ok_or(MissingError::NoResults)?;
+    let nfts = match res.nft_immutable_features {
+        Some(features) => {
+            let hrp = latest_bech32_hrp(&database).await?;
+            Some(vec![NftDetailsDto::new(&features, &hrp)])
+        }
+        None => None,
+    };
     Ok(IndexerOutputsResponse {
         ledger_index,
         items: vec![res.output_id.to_hex()],
+        ids: Some(vec![id.to_hex()]),
+        nfts,
+        statuses: None,
         cursor: None,
     })
 }
 
 async fn indexed_outputs<Q>(
     database: Extension<MongoDb>,
+    Extension(config): Extension<ApiConfigData>,
     IndexedOutputsPagination {
         query,
         page_size,
         cursor,
         sort,
         include_spent,
+        sample,
+        ledger_index,
     }: IndexedOutputsPagination<Q>,
 ) -> ApiResult<IndexerOutputsResponse>
 where
+    Q: QueryAddresses + SpentInMilestoneQuery,
     bson::Document: From<Q>,
 {
-    let ledger_index = database
-        .collection::<MilestoneCollection>()
-        .get_ledger_index()
+    for address in query.addresses() {
+        check_denylist(&config, &address).await?;
+    }
+    let ledger_index = config
+        .ledger_index_cache
+        .get_ledger_index_of_kind(&database, ledger_index)
         .await?
-        .ok_or(MissingError::NoResults)?;
+        .ok_or(NotSyncedError)?;
+    if sample {
+        // A sample is random, so it is returned in one shot without a cursor.
+        let res = database
+            .collection::<OutputCollection>()
+            .sample_indexed_outputs(query, page_size, include_spent, ledger_index)
+            .await?;
+        return Ok(IndexerOutputsResponse {
+            ledger_index,
+            items: res.outputs.iter().map(|o| o.output_id.to_hex()).collect(),
+            ids: indexed_ids(res.outputs.iter()),
+            nfts: nft_details(&database, res.outputs.iter()).await?,
+            statuses: include_spent.then(|| output_statuses(res.outputs.iter())),
+            cursor: None,
+        });
+    }
     let res = database
         .collection::<OutputCollection>()
         .get_indexed_outputs(
@@ -110,7 +160,11 @@ where
     let mut iter = res.outputs.iter();
 
     // Take all of the requested records first
-    let items = iter.by_ref().take(page_size).map(|o| o.output_id.to_hex()).collect();
+    let page = iter.by_ref().take(page_size).collect::<Vec<_>>();
+    let items = page.iter().map(|o| o.output_id.to_hex()).collect();
+    let ids = indexed_ids(page.iter().copied());
+    let nfts = nft_details(&database, page.iter().copied()).await?;
+    let statuses = include_spent.then(|| output_statuses(page.iter().copied()));
 
     // If any record is left, use it to make the cursor
     let cursor = iter.next().map(|rec| {
@@ -119,12 +173,102 @@ where
             output_id: rec.output_id,
             page_size,
         }
-        .to_string()
+        .encode(config.cursor_format)
     });
 
     Ok(IndexerOutputsResponse {
         ledger_index,
         items,
+        ids,
+        nfts,
+        statuses,
         cursor,
     })
 }
+
+/// Collects whether each of the outputs is spent.
+fn output_statuses<'a>(outputs: impl Iterator<Item = &'a OutputResult>) -> Vec<OutputStatusDto> {
+    outputs
+        .map(|o| OutputStatusDto {
+            output_id: o.output_id.to_hex(),
+            spent: o.is_spent,
+        })
+        .collect()
+}
+
+/// Collects the hex ids of the outputs. Only alias, foundry and NFT outputs have an id.
+fn indexed_ids<'a>(outputs: impl Iterator<Item = &'a OutputResult>) -> Option<Vec<String>> {
+    outputs
+        .map(|o| o.indexed_id.map(|id| id.to_hex()))
+        .collect::<Option<Vec<_>>>()
+        .filter(|ids| !ids.is_empty())
+}
+
+/// Decodes the immutable features of the outputs if they are NFT outputs.
+async fn nft_details<'a>(
+    database: &MongoDb,
+    outputs: impl Iterator<Item = &'a OutputResult>,
+) -> ApiResult<Option<Vec<NftDetailsDto>>> {
+    let features = outputs
+        .map(|o| o.nft_immutable_features.as_deref())
+        .collect::<Option<Vec<_>>>()
+        .filter(|features| !features.is_empty());
+    Ok(match features {
+        Some(features) => {
+            let hrp = latest_bech32_hrp(database).await?;
+            Some(features.into_iter().map(|f| NftDetailsDto::new(f, &hrp)).collect())
+        }
+        None => None,
+    })
+}
+
+/// The addresses that an indexer query filters by, which are checked against the denylist.
+trait QueryAddresses {
+    fn addresses(&self) -> Vec<Address>;
+}
+
+impl QueryAddresses for BasicOutputsQuery {
+    fn addresses(&self) -> Vec<Address> {
+        [
+            self.address,
+            self.alias_address.map(Address::Alias),
+            self.storage_deposit_return_address,
+            self.expiration_return_address,
+            self.sender,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl QueryAddresses for AliasOutputsQuery {
+    fn addresses(&self) -> Vec<Address> {
+        [self.state_controller, self.governor, self.issuer, self.sender]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+impl QueryAddresses for FoundryOutputsQuery {
+    fn addresses(&self) -> Vec<Address> {
+        self.alias_address.map(Address::Alias).into_iter().collect()
+    }
+}
+
+impl QueryAddresses for NftOutputsQuery {
+    fn addresses(&self) -> Vec<Address> {
+        [
+            self.address,
+            self.alias_address.map(Address::Alias),
+            self.issuer,
+            self.sender,
+            self.storage_deposit_return_address,
+            self.expiration_return_address,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use chronicle::{
+    db::{
+        mongodb::collections::{ApplicationStateCollection, LedgerIndexKind, MilestoneCollection},
+        MongoDb,
+    },
+    model::tangle::MilestoneIndex,
+};
+use futures::StreamExt;
+use mongodb::error::Error;
+use tracing::{debug, warn};
+
+/// The time to wait before reopening a change stream that failed.
+const REOPEN_DELAY: Duration = Duration::from_secs(1);
+
+/// The ledger index, kept up to date by following the milestones that are inserted into the database, so that it does
+/// not have to be read on every request.
+#[derive(Clone, Debug, Default)]
+pub struct LedgerIndexCache {
+    ledger_index: Arc<RwLock<Option<MilestoneIndex>>>,
+}
+
+impl LedgerIndexCache {
+    /// Gets the ledger index, reading it from the database if the cache is cold.
+    pub async fn get_ledger_index(&self, database: &MongoDb) -> Result<Option<MilestoneIndex>, Error> {
+        match self.get() {
+            Some(ledger_index) => Ok(Some(ledger_index)),
+            None => database.collection::<MilestoneCollection>().get_ledger_index().await,
+        }
+    }
+
+    /// Gets the ledger index of the given kind. Only the confirmed ledger index is cached, the latest one is read from
+    /// the application state that the INX worker advances as it applies ledger updates.
+    pub async fn get_ledger_index_of_kind(
+        &self,
+        database: &MongoDb,
+        kind: LedgerIndexKind,
+    ) -> Result<Option<MilestoneIndex>, Error> {
+        let confirmed = self.get_ledger_index(database).await?;
+        Ok(match kind {
+            LedgerIndexKind::Confirmed => confirmed,
+            LedgerIndexKind::Latest => confirmed.max(
+                database
+                    .collection::<ApplicationStateCollection>()
+                    .get_latest_ledger_update_index()
+                    .await?,
+            ),
+        })
+    }
+
+    /// Follows the milestones that are inserted into the database until the returned future is dropped. The cache
+    /// stays cold if the database does not support change streams.
+    pub async fn watch(self, database: MongoDb) {
+        let collection = database.collection::<MilestoneCollection>();
+        loop {
+            let mut milestone_indexes = match collection.watch_milestone_indexes().await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("Cannot watch for new milestones, so the ledger index is read on every request: {e}");
+                    return;
+                }
+            };
+            // The cache is only filled once the stream is open, so that no milestone can be missed in between.
+            match collection.get_ledger_index().await {
+                Ok(ledger_index) => self.set(ledger_index),
+                Err(e) => warn!("Cannot read the ledger index: {e}"),
+            }
+            while let Some(milestone_index) = milestone_indexes.next().await {
+                match milestone_index {
+                    Ok(milestone_index) => self.update(milestone_index),
+                    Err(e) => {
+                        warn!("The stream of new milestones failed: {e}");
+                        break;
+                    }
+                }
+            }
+            self.set(None);
+            debug!("Reopening the stream of new milestones.");
+            tokio::time::sleep(REOPEN_DELAY).await;
+        }
+    }
+
+    fn get(&self) -> Option<MilestoneIndex> {
+        // Panic: The lock is never held across a panic.
+        *self.ledger_index.read().unwrap()
+    }
+
+    fn set(&self, ledger_index: Option<MilestoneIndex>) {
+        // Panic: The lock is never held across a panic.
+        *self.ledger_index.write().unwrap() = ledger_index;
+    }
+
+    /// Advances the ledger index to a newly inserted milestone. Milestones that are inserted out of order, e.g. while
+    /// filling gaps, do not move it back.
+    fn update(&self, milestone_index: MilestoneIndex) {
+        // Panic: The lock is never held across a panic.
+        let mut ledger_index = self.ledger_index.write().unwrap();
+        if ledger_index.map_or(true, |ledger_index| milestone_index > ledger_index) {
+            ledger_index.replace(milestone_index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LedgerIndexCache;
+
+    #[test]
+    fn test_ledger_index_only_advances() {
+        let cache = LedgerIndexCache::default();
+        assert_eq!(cache.get(), None);
+        cache.update(10.into());
+        cache.update(8.into());
+        assert_eq!(cache.get(), Some(10.into()));
+        cache.update(11.into());
+        assert_eq!(cache.get(), Some(11.into()));
+        cache.set(None);
+        assert_eq!(cache.get(), None);
+    }
+}
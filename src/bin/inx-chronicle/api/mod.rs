@@ -4,6 +4,9 @@
 //! Contains routes that can be used to access data stored by Chronicle
 //! as well as the health of the application and analytics.
 
+mod access_log;
+#[cfg(feature = "analytics")]
+mod admin;
 mod error;
 mod extractors;
 mod secret_key;
@@ -12,15 +15,19 @@ mod responses;
 mod auth;
 pub mod config;
 mod core;
+mod denylist;
 mod explorer;
+mod health;
 mod indexer;
+mod ledger_index_cache;
+mod output_cache;
 #[cfg(feature = "poi")]
 mod poi;
 mod router;
 mod routes;
 
-use axum::{Extension, Server};
-use chronicle::db::MongoDb;
+use axum::{middleware::from_fn, Extension, Server};
+use chronicle::db::{mongodb::collections::ProtocolUpdateCollection, MongoDb};
 use futures::Future;
 use hyper::Method;
 use tower_http::{
@@ -28,7 +35,7 @@ use tower_http::{
     cors::{Any, CorsLayer},
     trace::TraceLayer,
 };
-use tracing::info;
+use tracing::{info, warn};
 
 pub use self::{
     config::{ApiConfig, ApiConfigData},
@@ -43,6 +50,10 @@ pub const DEFAULT_PAGE_SIZE: usize = 100;
 pub struct ApiWorker {
     db: MongoDb,
     api_data: ApiConfigData,
+    #[cfg(feature = "analytics")]
+    influx_db: Option<chronicle::db::influxdb::InfluxDb>,
+    #[cfg(feature = "analytics")]
+    shared_analytics: crate::cli::analytics::SharedAnalytics,
 }
 
 impl ApiWorker {
@@ -51,17 +62,93 @@ impl ApiWorker {
         Ok(Self {
             db,
             api_data: config.try_into()?,
+            #[cfg(feature = "analytics")]
+            influx_db: None,
+            #[cfg(feature = "analytics")]
+            shared_analytics: Default::default(),
         })
     }
 
+    /// Enables the admin routes that write analytics.
+    #[cfg(feature = "analytics")]
+    pub fn set_influx_db(&mut self, influx_db: &chronicle::db::influxdb::InfluxDb) {
+        self.influx_db.replace(influx_db.clone());
+    }
+
+    /// Serves the analytics state of the INX worker on the admin routes.
+    #[cfg(feature = "analytics")]
+    pub fn set_shared_analytics(&mut self, shared_analytics: &crate::cli::analytics::SharedAnalytics) {
+        self.shared_analytics = shared_analytics.clone();
+    }
+
+    /// Makes sure that the database contains data of the configured network, so that no responses are served for a
+    /// different one.
+    async fn check_network(&self) -> eyre::Result<()> {
+        if self.api_data.network_name.is_none() && self.api_data.bech32_hrp.is_none() {
+            return Ok(());
+        }
+        let latest = match self
+            .db
+            .collection::<ProtocolUpdateCollection>()
+            .get_latest_protocol_parameters()
+            .await?
+        {
+            Some(latest) => latest,
+            None => {
+                warn!("The database is not linked to a network yet, so the configured network cannot be verified.");
+                return Ok(());
+            }
+        };
+        let checks = [
+            (
+                "network name",
+                &self.api_data.network_name,
+                latest.parameters.network_name,
+            ),
+            ("bech32 HRP", &self.api_data.bech32_hrp, latest.parameters.bech32_hrp),
+        ];
+        for (kind, expected, found) in checks {
+            if let Some(expected) = expected {
+                if *expected != found {
+                    return Err(ConfigError::NetworkMismatch {
+                        kind,
+                        expected: expected.clone(),
+                        found,
+                    }
+                    .into());
+                }
+            }
+        }
+        info!("Found matching network in the database.");
+        Ok(())
+    }
+
     pub async fn run(&self, shutdown_handle: impl Future<Output = ()>) -> eyre::Result<()> {
+        self.check_network().await?;
+
         info!("Starting API server on port `{}`", self.api_data.port);
 
+        // The task only reads, so it can be aborted at any point.
+        let ledger_index_watcher = tokio::spawn(self.api_data.ledger_index_cache.clone().watch(self.db.clone()));
+
         let port = self.api_data.port;
-        let routes = routes::routes()
+        let routes = routes::routes(&self.api_data);
+        #[cfg(feature = "analytics")]
+        let routes = {
+            let routes = routes
+                .layer(Extension(admin::RecomputeJobs::default()))
+                .layer(Extension(self.shared_analytics.clone()));
+            match &self.influx_db {
+                Some(influx_db) => routes.layer(Extension(influx_db.clone())),
+                None => routes,
+            }
+        };
+        let routes = routes
             .layer(Extension(self.db.clone()))
-            .layer(Extension(self.api_data.clone()))
             .layer(CatchPanicLayer::new())
+            // The access log sits outside the panic handler, so that requests that panicked are logged as failed.
+            .layer(from_fn(access_log::access_log))
+            .layer(Extension(self.api_data.clone()))
             .layer(TraceLayer::new_for_http())
             .layer(
                 CorsLayer::new()
@@ -75,6 +162,7 @@ impl ApiWorker {
             .serve(routes.into_make_service())
             .with_graceful_shutdown(shutdown_handle)
             .await?;
+        ledger_index_watcher.abort();
 
         Ok(())
     }
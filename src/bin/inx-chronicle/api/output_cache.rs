@@ -0,0 +1,172 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use chronicle::{
+    db::{
+        mongodb::collections::{OutputCollection, OutputWithMetadataResult},
+        MongoDb,
+    },
+    model::{
+        tangle::MilestoneIndex,
+        utxo::OutputId,
+    },
+};
+use lru_cache::LruCache;
+use mongodb::error::Error;
+
+use super::responses::OutputCacheResponse;
+
+/// An output with its metadata, as read at a ledger index.
+#[derive(Clone, Debug)]
+struct CachedOutput {
+    read_at: MilestoneIndex,
+    result: OutputWithMetadataResult,
+}
+
+impl CachedOutput {
+    /// Whether the cached result is the same as the one that would be read from the database at the ledger index.
+    ///
+    /// Outputs themselves never change, but their metadata does once they are spent. The metadata of spent outputs is
+    /// final, while that of unspent outputs is only known to be current at the ledger index it was read at.
+    fn is_valid_at(&self, ledger_index: MilestoneIndex) -> bool {
+        self.result.metadata.booked.milestone_index <= ledger_index
+            && (self.result.metadata.spent_metadata.is_some() || self.read_at == ledger_index)
+    }
+}
+
+/// An LRU cache in front of the output queries of frequently requested outputs.
+#[derive(Clone, Debug)]
+pub struct OutputCache {
+    outputs: Arc<Mutex<LruCache<OutputId, CachedOutput>>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl OutputCache {
+    /// Creates a cache holding up to `capacity` outputs. A capacity of zero disables the cache.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            outputs: Arc::new(Mutex::new(LruCache::new(capacity))),
+            hits: Default::default(),
+            misses: Default::default(),
+        }
+    }
+
+    /// Gets an output with its metadata at the given ledger index, reading it from the database if it is not cached.
+    pub async fn get_output_with_metadata(
+        &self,
+        database: &MongoDb,
+        output_id: &OutputId,
+        ledger_index: MilestoneIndex,
+    ) -> Result<Option<OutputWithMetadataResult>, Error> {
+        if let Some(cached) = self.get(output_id) {
+            if cached.is_valid_at(ledger_index) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Some(cached.result));
+            }
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        self.read(database, output_id, ledger_index).await
+    }
+
+    pub fn stats(&self) -> OutputCacheResponse {
+        // Panic: The lock is never held across a panic.
+        let outputs = self.outputs.lock().unwrap();
+        OutputCacheResponse {
+            capacity: outputs.capacity(),
+            len: outputs.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Reads an output with its metadata from the database and caches it.
+    async fn read(
+        &self,
+        database: &MongoDb,
+        output_id: &OutputId,
+        ledger_index: MilestoneIndex,
+    ) -> Result<Option<OutputWithMetadataResult>, Error> {
+        let result = database
+            .collection::<OutputCollection>()
+            .get_output_with_metadata(output_id, ledger_index)
+            .await?;
+        if let Some(result) = &result {
+            self.insert(
+                *output_id,
+                CachedOutput {
+                    read_at: ledger_index,
+                    result: result.clone(),
+                },
+            );
+        }
+        Ok(result)
+    }
+
+    fn get(&self, output_id: &OutputId) -> Option<CachedOutput> {
+        // Panic: The lock is never held across a panic.
+        self.outputs.lock().unwrap().get_mut(output_id).cloned()
+    }
+
+    fn insert(&self, output_id: OutputId, output: CachedOutput) {
+        // Panic: The lock is never held across a panic.
+        let mut outputs = self.outputs.lock().unwrap();
+        if outputs.capacity() > 0 {
+            outputs.insert(output_id, output);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rand"))]
+mod test {
+    use chronicle::{
+        db::mongodb::collections::{OutputMetadataResult, OutputWithMetadataResult},
+        model::{
+            metadata::SpentMetadata,
+            payload::TransactionId,
+            tangle::MilestoneIndexTimestamp,
+            utxo::{Output, OutputId},
+            BlockId,
+        },
+    };
+
+    use super::CachedOutput;
+
+    #[test]
+    fn test_cached_output_validity() {
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+        let at = |index: u32| MilestoneIndexTimestamp {
+            milestone_index: index.into(),
+            milestone_timestamp: (index * 10).into(),
+        };
+        let mut cached = CachedOutput {
+            read_at: 10.into(),
+            result: OutputWithMetadataResult {
+                output: Output::rand(&protocol_params),
+                metadata: OutputMetadataResult {
+                    output_id: OutputId::rand(),
+                    block_id: BlockId::rand(),
+                    booked: at(5),
+                    spent_metadata: None,
+                },
+            },
+        };
+        assert!(!cached.is_valid_at(4.into()));
+        assert!(cached.is_valid_at(10.into()));
+        // The output may have been spent since it was read.
+        assert!(!cached.is_valid_at(11.into()));
+
+        cached.result.metadata.spent_metadata = Some(SpentMetadata {
+            transaction_id: TransactionId::rand(),
+            spent: at(8),
+            input_index: None,
+        });
+        assert!(cached.is_valid_at(11.into()));
+    }
+}
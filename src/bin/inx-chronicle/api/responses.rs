@@ -1,6 +1,16 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::fmt::Display;
+
+use axum::{
+    http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use chronicle::model::utxo::{Feature, Output};
 use serde::{Deserialize, Serialize};
 
 macro_rules! impl_success_response {
@@ -24,3 +34,148 @@ pub struct RoutesResponse {
 }
 
 impl_success_response!(RoutesResponse);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputCacheResponse {
+    pub capacity: usize,
+    pub len: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl_success_response!(OutputCacheResponse);
+
+/// The decoded immutable features of an NFT output, which are included next to NFT outputs in responses so that their
+/// content can be displayed without decoding the output.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftDetailsDto {
+    /// The bech32 address of the immutable issuer feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// The immutable metadata feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<NftMetadataDto>,
+}
+
+impl NftDetailsDto {
+    /// Decodes the immutable features of an NFT output, encoding the issuer with the given bech32 HRP.
+    pub fn new(immutable_features: &[Feature], hrp: &str) -> Self {
+        let mut res = Self::default();
+        for feature in immutable_features {
+            match feature {
+                Feature::Issuer { address } => {
+                    res.issuer = Some(iota_types::block::address::Address::from(*address).to_bech32(hrp))
+                }
+                Feature::Metadata { data } => res.metadata = Some(NftMetadataDto::from(data.as_ref())),
+                _ => (),
+            }
+        }
+        res
+    }
+
+    /// Decodes the immutable features of the output if it is an NFT output.
+    pub fn from_output(output: &Output, hrp: &str) -> Option<Self> {
+        match output {
+            Output::Nft(nft) => Some(Self::new(&nft.immutable_features, hrp)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftMetadataDto {
+    /// The raw metadata bytes as a hex string.
+    pub hex: String,
+    /// The metadata decoded as UTF-8, if it is valid UTF-8.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utf8: Option<String>,
+    /// The metadata decoded as JSON, if it is valid JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<serde_json::Value>,
+}
+
+impl From<&[u8]> for NftMetadataDto {
+    fn from(data: &[u8]) -> Self {
+        let utf8 = std::str::from_utf8(data).ok();
+        Self {
+            hex: prefix_hex::encode(data),
+            json: utf8.and_then(|s| serde_json::from_str(s).ok()),
+            utf8: utf8.map(ToOwned::to_owned),
+        }
+    }
+}
+
+/// An entity tag identifying the version of a resource that does not change once it has been confirmed.
+///
+/// The tags are weak, since responses may contain fields such as the ledger index that change without the resource
+/// itself changing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ETag(String);
+
+impl ETag {
+    /// Creates a weak entity tag from a value that uniquely identifies the version of the resource.
+    pub fn weak(tag: impl Display) -> Self {
+        Self(format!("W/\"{tag}\""))
+    }
+
+    /// Checks whether the `If-None-Match` header of a request matches this tag, using the weak comparison.
+    pub fn matches(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get_all(IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .any(|tag| tag == "*" || strip_weak(tag) == strip_weak(&self.0))
+    }
+}
+
+fn strip_weak(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+/// A response to a conditional request, which is replaced by `304 Not Modified` if the client already has the tagged
+/// version of the resource.
+#[derive(Clone, Debug)]
+pub enum Conditional<T> {
+    Modified(ETag, T),
+    NotModified(ETag),
+}
+
+impl<T: IntoResponse> IntoResponse for Conditional<T> {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Modified(ETag(etag), res) => ([(ETAG, etag)], res).into_response(),
+            Self::NotModified(ETag(etag)) => (StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn etag_matches() {
+        let etag = ETag::weak("0x01-unspent");
+        let mut headers = HeaderMap::new();
+        assert!(!etag.matches(&headers));
+
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("\"0x01-spent\""));
+        assert!(!etag.matches(&headers));
+
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("\"0x02\", \"0x01-unspent\""));
+        assert!(etag.matches(&headers));
+
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("W/\"0x01-unspent\""));
+        assert!(etag.matches(&headers));
+
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(etag.matches(&headers));
+    }
+}
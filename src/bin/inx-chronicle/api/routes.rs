@@ -3,58 +3,160 @@
 
 use auth_helper::jwt::{BuildValidation, Claims, JsonWebToken, Validation};
 use axum::{
+    error_handling::HandleErrorLayer,
     handler::Handler,
     headers::{authorization::Bearer, Authorization},
-    http::HeaderValue,
-    middleware::from_extractor,
+    http::{HeaderValue, Request},
+    middleware::{from_extractor, from_fn, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Extension, Json, TypedHeader,
 };
 use chronicle::{
-    db::{mongodb::collections::MilestoneCollection, MongoDb},
+    db::{
+        mongodb::collections::{MilestoneCollection, ProtocolUpdateCollection},
+        MongoDb,
+    },
     model::tangle::MilestoneTimestamp,
 };
+use clap::ValueEnum;
 use hyper::StatusCode;
 use regex::RegexSet;
 use serde::Deserialize;
 use time::{Duration, OffsetDateTime};
+use tower::{limit::GlobalConcurrencyLimitLayer, BoxError, ServiceBuilder};
 
 use super::{
     auth::Auth,
-    config::ApiConfigData,
-    error::{ApiError, MissingError, UnimplementedError},
+    config::{AmountFormat, ApiConfigData, ReadPreferenceMode},
+    error::{
+        ApiError, CorruptStateError, MissingError, OverloadedError, RequestError, TimeoutError, UnimplementedError,
+    },
+    explorer::AMOUNT_FORMAT,
     extractors::ListRoutesQuery,
-    responses::RoutesResponse,
+    responses::{OutputCacheResponse, RoutesResponse},
     router::{RouteNode, Router},
     ApiResult, AuthError,
 };
 
 pub(crate) static BYTE_CONTENT_HEADER: HeaderValue = HeaderValue::from_static("application/vnd.iota.serializer-v1");
 
+const READ_PREFERENCE_HEADER: &str = "x-read-preference";
+
+const AMOUNT_FORMAT_HEADER: &str = "x-amount-format";
+
 const ALWAYS_AVAILABLE_ROUTES: &[&str] = &["/health", "/login", "/routes"];
 
 // Similar to Hornet, we enforce that the latest known milestone is newer than 5 minutes. This should give Chronicle
 // sufficient time to catch up with the node that it is connected too. The current milestone interval is 5 seconds.
 const STALE_MILESTONE_DURATION: Duration = Duration::minutes(5);
 
-pub fn routes() -> Router {
+pub fn routes(config: &ApiConfigData) -> Router {
     #[allow(unused_mut)]
     let mut router = Router::new()
         .nest("/core/v2", super::core::routes())
-        .nest("/explorer/v2", super::explorer::routes())
-        .nest("/indexer/v1", super::indexer::routes());
+        .nest(
+            "/explorer/v2",
+            super::explorer::routes().route_layer(from_fn(amount_format)),
+        )
+        .nest("/indexer/v1", super::indexer::routes())
+        .route("/cache/outputs", get(output_cache));
 
     #[cfg(feature = "poi")]
     {
         router = router.nest("/poi/v1", super::poi::routes());
     }
 
-    Router::new()
+    #[cfg(feature = "analytics")]
+    {
+        router = router.nest("/admin", super::admin::routes());
+    }
+
+    let mut router = router
+        .route_layer(from_extractor::<Auth>())
+        .route_layer(from_fn(request_timeout))
+        .route_layer(from_fn(read_preference));
+
+    // The limit is shared by all routes below `/api`, so that the health endpoint stays available when overloaded.
+    if let Some(max_concurrent_requests) = config.max_concurrent_requests {
+        router = router.route_layer(
+            ServiceBuilder::new()
+                .layer(HandleErrorLayer::new(|_: BoxError| async { OverloadedError }))
+                .load_shed()
+                .layer(GlobalConcurrencyLimitLayer::new(max_concurrent_requests)),
+        );
+    }
+
+    let router = Router::new()
         .route("/health", get(health))
         .route("/login", post(login))
         .route("/routes", get(list_routes))
-        .nest("/api", router.route_layer(from_extractor::<Auth>()))
-        .fallback(not_found.into_service())
+        .nest("/api", router);
+
+    // Nested routers cannot have a fallback, so it is added after mounting the routes under the base path.
+    if config.base_path.is_empty() {
+        router
+    } else {
+        Router::new().nest(&config.base_path, router)
+    }
+    .fallback(not_found.into_service())
+}
+
+/// Aborts the request with a `504 Gateway Timeout` if the handler does not produce a response within the configured
+/// duration. Dropping the handler future also cancels any database query it is still awaiting.
+///
+/// The timeout only covers the time until the response head is produced, so response bodies that are streamed to the
+/// client afterwards are not affected.
+async fn request_timeout<B>(req: Request<B>, next: Next<B>) -> Response {
+    let timeout = req
+        .extensions()
+        .get::<ApiConfigData>()
+        .map(|config| config.request_timeout);
+    match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, next.run(req)).await {
+            Ok(res) => res,
+            Err(_) => TimeoutError.into_response(),
+        },
+        None => next.run(req).await,
+    }
+}
+
+/// Routes the database reads of a request to the replica set members named by the `X-Read-Preference` header, or to
+/// the configured ones if the header is absent.
+async fn read_preference<B>(mut req: Request<B>, next: Next<B>) -> Result<Response, ApiError> {
+    let mode = match req.headers().get(READ_PREFERENCE_HEADER) {
+        Some(value) => {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            ReadPreferenceMode::from_str(&value, true).map_err(|_| RequestError::InvalidReadPreference(value.into()))?
+        }
+        None => match req.extensions().get::<ApiConfigData>() {
+            Some(config) => config.read_preference,
+            None => return Ok(next.run(req).await),
+        },
+    };
+    if mode != ReadPreferenceMode::Primary {
+        if let Some(database) = req.extensions().get::<MongoDb>() {
+            let database = database.with_read_preference(mode.into());
+            req.extensions_mut().insert(database);
+        }
+    }
+    Ok(next.run(req).await)
+}
+
+/// Represents the token amounts of a response as JSON numbers instead of strings if the `X-Amount-Format` header, or
+/// the configured default, asks for it.
+async fn amount_format<B>(req: Request<B>, next: Next<B>) -> Result<Response, ApiError> {
+    let format = match req.headers().get(AMOUNT_FORMAT_HEADER) {
+        Some(value) => {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            AmountFormat::from_str(&value, true).map_err(|_| RequestError::InvalidAmountFormat(value.into()))?
+        }
+        None => match req.extensions().get::<ApiConfigData>() {
+            Some(config) => config.amount_format,
+            None => return Ok(next.run(req).await),
+        },
+    };
+    Ok(AMOUNT_FORMAT.scope(format, next.run(req)).await)
 }
 
 #[derive(Deserialize)]
@@ -74,7 +176,7 @@ async fn login(
     )? {
         let jwt = JsonWebToken::new(
             Claims::new(
-                ApiConfigData::ISSUER,
+                &config.jwt_issuer,
                 uuid::Uuid::new_v4().to_string(),
                 ApiConfigData::AUDIENCE,
             )?
@@ -116,7 +218,7 @@ async fn list_routes(
 
         jwt.validate(
             Validation::default()
-                .with_issuer(ApiConfigData::ISSUER)
+                .with_issuer(&config.jwt_issuer)
                 .with_audience(ApiConfigData::AUDIENCE)
                 .validate_nbf(true),
             config.jwt_secret_key.as_ref(),
@@ -137,32 +239,30 @@ async fn list_routes(
     Ok(RoutesResponse { routes })
 }
 
-pub async fn is_healthy(database: &MongoDb) -> ApiResult<bool> {
-    {
-        let newest = match database
-            .collection::<MilestoneCollection>()
-            .get_newest_milestone()
-            .await?
-        {
-            Some(last) => last,
-            None => return Ok(false),
-        };
-
-        if !is_new_enough(newest.milestone_timestamp) {
-            return Ok(false);
-        }
-    }
+/// Reports how many output requests were answered from the output cache.
+async fn output_cache(Extension(config): Extension<ApiConfigData>) -> OutputCacheResponse {
+    config.output_cache.stats()
+}
+
+pub async fn is_healthy(database: &MongoDb, config: &ApiConfigData) -> ApiResult<bool> {
+    let newest = database
+        .collection::<MilestoneCollection>()
+        .get_newest_milestone()
+        .await?;
+    let fresh = newest.map_or(false, |newest| is_new_enough(newest.milestone_timestamp));
 
-    Ok(true)
+    Ok(config
+        .health_tracker
+        .observe(newest.map(|newest| newest.milestone_index), fresh))
 }
 
-pub async fn health(database: Extension<MongoDb>) -> StatusCode {
+pub async fn health(database: Extension<MongoDb>, Extension(config): Extension<ApiConfigData>) -> StatusCode {
     let handle_error = |ApiError { error, .. }| {
         tracing::error!("An error occured during health check: {error}");
         false
     };
 
-    if is_healthy(&database).await.unwrap_or_else(handle_error) {
+    if is_healthy(&database, &config).await.unwrap_or_else(handle_error) {
         StatusCode::OK
     } else {
         StatusCode::SERVICE_UNAVAILABLE
@@ -176,3 +276,15 @@ pub async fn not_found() -> MissingError {
 pub async fn not_implemented() -> UnimplementedError {
     UnimplementedError
 }
+
+/// Gets the bech32 HRP of the latest protocol parameters, which is used for addresses that are derived from ids or
+/// decoded from output features.
+pub(crate) async fn latest_bech32_hrp(database: &MongoDb) -> ApiResult<String> {
+    Ok(database
+        .collection::<ProtocolUpdateCollection>()
+        .get_latest_protocol_parameters()
+        .await?
+        .ok_or(CorruptStateError::ProtocolParams)?
+        .parameters
+        .bech32_hrp)
+}
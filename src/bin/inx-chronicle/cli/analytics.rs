@@ -1,10 +1,13 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
 
 use chronicle::{
-    analytics::{Analytic, AnalyticsInterval, IntervalAnalytic},
+    analytics::{Analytic, AnalyticsError, AnalyticsInterval, IntervalAnalytic, MeasurementSchema, TeeSink},
     db::{
         influxdb::{
             config::{all_analytics, all_interval_analytics, IntervalAnalyticsChoice},
@@ -13,13 +16,15 @@ use chronicle::{
         mongodb::collections::{MilestoneCollection, OutputCollection},
         MongoDb,
     },
-    model::{protocol::ProtocolParameters, tangle::MilestoneIndex},
+    model::{tangle::MilestoneIndex, ProtocolParameters},
     tangle::{InputSource, Tangle},
 };
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use futures::TryStreamExt;
-use time::{Date, OffsetDateTime};
-use tracing::{debug, info};
+use serde::Serialize;
+use time::{Date, OffsetDateTime, UtcOffset};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
 
 use crate::config::ChronicleConfig;
 
@@ -47,6 +52,11 @@ pub struct FillAnalyticsCommand {
     /// The inclusive ending date (YYYY-MM-DD).
     #[arg(long, value_parser = parse_date)]
     end_date: Option<Date>,
+    /// Resume per-milestone analytics after the most recent point written to the given measurement. If the
+    /// measurement contains no data, the starting milestone or date is used instead. If it is already caught up, no
+    /// per-milestone analytics are computed.
+    #[arg(long, value_name = "MEASUREMENT")]
+    resume_from: Option<String>,
     /// The number of parallel tasks to use when filling per-milestone analytics.
     #[arg(short, long, default_value_t = 1)]
     num_tasks: usize,
@@ -56,9 +66,16 @@ pub struct FillAnalyticsCommand {
     /// The input source to use for filling per-milestone analytics.
     #[arg(short, long, value_name = "INPUT_SOURCE", default_value = "mongo-db")]
     input_source: InputSourceChoice,
-    /// The interval to use for interval analytics.
+    /// The number of blocks fetched per round-trip when streaming a milestone cone. Larger values trade memory for
+    /// fewer round-trips. Only applies to the `mongo-db` input source; defaults to the driver's batch size.
+    #[arg(long)]
+    cone_batch_size: Option<u32>,
+    /// The interval to use for interval analytics: `day`, `week`, `month`, `year` or a day count like `14d`.
     #[arg(long, default_value = "day")]
     interval: AnalyticsInterval,
+    /// The offset from UTC (+HH:MM) at which dates begin, for the start and end dates and interval analytics.
+    #[arg(long, value_parser = parse_utc_offset, default_value = "+00:00")]
+    interval_utc_offset: UtcOffset,
     /// The number of parallel tasks to use when filling interval analytics.
     #[arg(long, default_value_t = 1)]
     num_interval_tasks: usize,
@@ -74,6 +91,13 @@ fn parse_date(s: &str) -> eyre::Result<Date> {
     )?)
 }
 
+fn parse_utc_offset(s: &str) -> eyre::Result<UtcOffset> {
+    Ok(UtcOffset::parse(
+        s,
+        time::macros::format_description!("[offset_hour sign:mandatory]:[offset_minute]"),
+    )?)
+}
+
 impl FillAnalyticsCommand {
     pub async fn handle(&self, config: &ChronicleConfig) -> eyre::Result<()> {
         let Self {
@@ -81,24 +105,64 @@ impl FillAnalyticsCommand {
             end_milestone,
             start_date,
             end_date,
+            resume_from,
             num_tasks,
             analytics,
             input_source,
+            cone_batch_size,
             interval,
+            interval_utc_offset,
             interval_analytics,
             num_interval_tasks,
         } = self;
         tracing::info!("Connecting to database using hosts: `{}`.", config.mongodb.hosts_str()?);
         let db = MongoDb::connect(&config.mongodb).await?;
-        let start_milestone = if let Some(index) = start_milestone {
+        let influx_db = InfluxDb::connect(&config.influxdb).await?;
+        // Resuming at the end of the range means that there is nothing left to compute, which is not an error.
+        let mut caught_up = false;
+        let resume_milestone = if let Some(measurement) = resume_from {
+            if let Some(ts) = influx_db.get_latest_analytics_timestamp(measurement).await? {
+                let latest = db
+                    .collection::<MilestoneCollection>()
+                    .find_last_milestone(ts)
+                    .await?
+                    .ok_or_else(|| {
+                        eyre::eyre!("No milestone found for the latest `{measurement}` timestamp {}.", ts.0)
+                    })?
+                    .milestone_index;
+                let newest = db
+                    .collection::<MilestoneCollection>()
+                    .get_newest_milestone()
+                    .await?
+                    .ok_or_else(|| eyre::eyre!("No milestones in database."))?
+                    .milestone_index;
+                if latest >= newest {
+                    info!("Analytics in `{measurement}` are already caught up with milestone {latest}.");
+                    caught_up = true;
+                    None
+                } else {
+                    info!("Resuming analytics after milestone {latest}, found in `{measurement}`.");
+                    Some(latest + 1)
+                }
+            } else {
+                info!("No data found in `{measurement}`, using the configured start.");
+                None
+            }
+        } else {
+            None
+        };
+        let start_milestone = if let Some(index) = resume_milestone.or(*start_milestone) {
             let ts = db
                 .collection::<MilestoneCollection>()
-                .get_milestone_timestamp(*index)
+                .get_milestone_timestamp(index)
                 .await?
                 .ok_or_else(|| eyre::eyre!("Could not find requested milestone {}.", index))?;
             index.with_timestamp(ts)
         } else if let Some(start_date) = start_date {
-            let ts = start_date.midnight().assume_utc().unix_timestamp();
+            let ts = start_date
+                .midnight()
+                .assume_offset(*interval_utc_offset)
+                .unix_timestamp();
             db.collection::<MilestoneCollection>()
                 .find_first_milestone((ts as u32).into())
                 .await?
@@ -114,6 +178,7 @@ impl FillAnalyticsCommand {
             start_date.unwrap_or(
                 OffsetDateTime::try_from(start_milestone.milestone_timestamp)
                     .unwrap()
+                    .to_offset(*interval_utc_offset)
                     .date(),
             ),
         );
@@ -125,7 +190,12 @@ impl FillAnalyticsCommand {
                 .ok_or_else(|| eyre::eyre!("Could not find requested milestone {}.", index))?;
             index.with_timestamp(ts)
         } else if let Some(end_date) = end_date {
-            let ts = end_date.next_day().unwrap().midnight().assume_utc().unix_timestamp();
+            let ts = end_date
+                .next_day()
+                .unwrap()
+                .midnight()
+                .assume_offset(*interval_utc_offset)
+                .unix_timestamp();
             db.collection::<MilestoneCollection>()
                 .find_last_milestone((ts as u32).into())
                 .await?
@@ -141,19 +211,25 @@ impl FillAnalyticsCommand {
             end_date.unwrap_or(
                 OffsetDateTime::try_from(end_milestone.milestone_timestamp)
                     .unwrap()
+                    .to_offset(*interval_utc_offset)
                     .date(),
             ),
         );
         if end_milestone < start_milestone {
-            eyre::bail!("No milestones in range: {start_milestone}..={end_milestone}.");
+            if resume_milestone.is_none() {
+                eyre::bail!("No milestones in range: {start_milestone}..={end_milestone}.");
+            }
+            info!("Analytics are already caught up with milestone {end_milestone}.");
+            caught_up = true;
         }
         if end_date < start_date {
             eyre::bail!("No dates in range: {start_date}..={end_date}.");
         }
-        let influx_db = InfluxDb::connect(&config.influxdb).await?;
-
         tokio::try_join!(
             async {
+                if caught_up {
+                    return Ok(());
+                }
                 match input_source {
                     #[cfg(feature = "inx")]
                     InputSourceChoice::Inx => {
@@ -162,7 +238,7 @@ impl FillAnalyticsCommand {
                         fill_analytics(
                             &db,
                             &influx_db,
-                            &inx,
+                            &Tangle::from(inx).with_cone_batch_size(*cone_batch_size),
                             start_milestone,
                             end_milestone,
                             *num_tasks,
@@ -174,7 +250,7 @@ impl FillAnalyticsCommand {
                         fill_analytics(
                             &db,
                             &influx_db,
-                            &db,
+                            &Tangle::from(db.clone()).with_cone_batch_size(*cone_batch_size),
                             start_milestone,
                             end_milestone,
                             *num_tasks,
@@ -191,6 +267,7 @@ impl FillAnalyticsCommand {
                 start_date,
                 end_date,
                 *interval,
+                *interval_utc_offset,
                 *num_interval_tasks,
                 interval_analytics
             )
@@ -199,6 +276,51 @@ impl FillAnalyticsCommand {
     }
 }
 
+/// Print a JSON description of the InfluxDb measurements written by each analytic.
+///
+/// The schema is derived from the measurement types using the configured amount format. The number of token
+/// distribution fields of `address-balance` depends on the token supply, which is taken from the given protocol
+/// parameters or the default protocol parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Parser)]
+pub struct AnalyticsSchemaCommand {
+    /// Select a subset of analytics to describe.
+    #[arg(long, value_enum, default_values_t = all_analytics())]
+    analytics: Vec<AnalyticsChoice>,
+    /// The token supply used to derive the token distribution fields.
+    #[arg(long)]
+    token_supply: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct AnalyticSchema {
+    analytic: String,
+    measurement: MeasurementSchema,
+}
+
+impl AnalyticsSchemaCommand {
+    pub fn handle(&self, config: &ChronicleConfig) -> eyre::Result<()> {
+        let mut protocol_params = ProtocolParameters::from(iota_types::block::protocol::ProtocolParameters::default());
+        if let Some(token_supply) = self.token_supply {
+            protocol_params.token_supply = token_supply;
+        }
+        let mut schemas = self
+            .analytics
+            .iter()
+            .map(|choice| AnalyticSchema {
+                analytic: choice
+                    .to_possible_value()
+                    .map(|value| value.get_name().to_string())
+                    .unwrap_or_default(),
+                measurement: Analytic::measurement_schema(choice, &protocol_params, &config.influxdb),
+            })
+            .collect::<Vec<_>>();
+        schemas.sort_by(|a, b| a.analytic.cmp(&b.analytic));
+        schemas.dedup_by(|a, b| a.analytic == b.analytic);
+        println!("{}", serde_json::to_string_pretty(&schemas)?);
+        Ok(())
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
 pub enum InputSourceChoice {
     MongoDb,
@@ -209,12 +331,16 @@ pub enum InputSourceChoice {
 pub async fn fill_analytics<I: 'static + InputSource + Clone>(
     db: &MongoDb,
     influx_db: &InfluxDb,
-    input_source: &I,
+    tangle: &Tangle<I>,
     start_milestone: MilestoneIndex,
     end_milestone: MilestoneIndex,
     num_tasks: usize,
     analytics: &[AnalyticsChoice],
 ) -> eyre::Result<()> {
+    if num_tasks > 1 && analytics.contains(&AnalyticsChoice::MilestoneSizeTotals) {
+        eyre::bail!("The `milestone-size-totals` analytic must be filled in order using a single task.");
+    }
+
     let mut join_set = tokio::task::JoinSet::new();
 
     let chunk_size = (end_milestone.0 - start_milestone.0) / num_tasks as u32;
@@ -223,12 +349,14 @@ pub async fn fill_analytics<I: 'static + InputSource + Clone>(
     let analytics_choices = analytics.iter().copied().collect::<HashSet<_>>();
     info!("Computing the following analytics: {analytics_choices:?}");
 
+    let sink = TeeSink::from_config(influx_db)?;
     let mut chunk_start_milestone = start_milestone;
 
     for i in 0..num_tasks {
         let db = db.clone();
         let influx_db = influx_db.clone();
-        let tangle = Tangle::from(input_source.clone());
+        let sink = sink.clone();
+        let tangle = tangle.clone();
         let analytics_choices = analytics_choices.clone();
 
         let actual_chunk_size = chunk_size + (i < remainder as usize) as u32;
@@ -248,8 +376,9 @@ pub async fn fill_analytics<I: 'static + InputSource + Clone>(
                 let start_time = std::time::Instant::now();
 
                 if let Some(milestone) = milestone_stream.try_next().await? {
-                    // Check if the protocol params changed (or we just started)
-                    if !matches!(&state, Some(state) if state.prev_protocol_params == milestone.protocol_params) {
+                    // Initialize the analytics if we just started. Changes to the protocol parameters are handled by
+                    // the analytics themselves.
+                    if state.is_none() {
                         // Only get the ledger state for milestones after the genesis since it requires
                         // getting the previous milestone data.
                         let ledger_state = if milestone.at.milestone_index.0 > 0 {
@@ -262,19 +391,22 @@ pub async fn fill_analytics<I: 'static + InputSource + Clone>(
                             panic!("There should be no milestone with index 0.");
                         };
 
-                        let analytics = analytics_choices
-                            .iter()
-                            .map(|choice| Analytic::init(choice, &milestone.protocol_params, &ledger_state))
-                            .collect::<Vec<_>>();
-                        state = Some(AnalyticsState {
-                            analytics,
-                            prev_protocol_params: milestone.protocol_params.clone(),
-                        });
+                        let analytics = futures::future::try_join_all(analytics_choices.iter().map(|choice| {
+                            Analytic::init(
+                                choice,
+                                &milestone.protocol_params,
+                                &ledger_state,
+                                &influx_db,
+                                milestone.at.milestone_index,
+                            )
+                        }))
+                        .await?;
+                        state = Some(AnalyticsState { analytics });
                     }
 
                     // Unwrap: safe because we guarantee it is initialized above
                     milestone
-                        .update_analytics(&mut state.as_mut().unwrap().analytics, &influx_db)
+                        .update_analytics(&mut state.as_mut().unwrap().analytics, &sink)
                         .await?;
 
                     let elapsed = start_time.elapsed();
@@ -311,23 +443,32 @@ pub async fn fill_analytics<I: 'static + InputSource + Clone>(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn fill_interval_analytics(
     db: &MongoDb,
     influx_db: &InfluxDb,
     start_date: Date,
     end_date: Date,
     interval: AnalyticsInterval,
+    offset: UtcOffset,
     num_tasks: usize,
     analytics: &[IntervalAnalyticsChoice],
 ) -> eyre::Result<()> {
+    let resolution = influx_db.config().timestamp_resolution;
+    if resolution.period() > interval.to_duration(&start_date).whole_seconds() as u64 {
+        eyre::bail!("The timestamp resolution `{resolution:?}` is coarser than the {interval} interval.");
+    }
+
     let mut join_set = tokio::task::JoinSet::new();
 
     let analytics_choices = analytics.iter().copied().collect::<HashSet<_>>();
     info!("Computing the following {interval} analytics for {start_date}..{end_date}: {analytics_choices:?}",);
 
+    let sink = TeeSink::from_config(influx_db)?;
+
     for i in 0..num_tasks {
         let db = db.clone();
-        let influx_db = influx_db.clone();
+        let sink = sink.clone();
         let analytics_choices = analytics_choices.clone();
         let mut date = start_date;
         for _ in 0..i {
@@ -343,14 +484,16 @@ pub async fn fill_interval_analytics(
             while date < end_date {
                 let start_time = std::time::Instant::now();
 
-                db.update_interval_analytics(&mut analytics, &influx_db, date, interval)
-                    .await?;
-
-                let elapsed = start_time.elapsed().as_millis();
-                info!(
-                    "Task {i} finished {interval} analytics for {date}..{} in {elapsed}ms.",
-                    interval.end_date(&date)
-                );
+                let res = db
+                    .update_interval_analytics(&mut analytics, &sink, date, interval, offset)
+                    .await;
+                if skip_interval_out_of_range(res)? {
+                    let elapsed = start_time.elapsed().as_millis();
+                    info!(
+                        "Task {i} finished {interval} analytics for {date}..{} in {elapsed}ms.",
+                        interval.end_date(&date)
+                    );
+                }
                 for _ in 0..num_tasks {
                     date = interval.end_date(&date);
                     if date >= end_date {
@@ -368,7 +511,103 @@ pub async fn fill_interval_analytics(
     Ok(())
 }
 
+/// Logs and skips an interval that lies outside of the stored milestones, so that the remaining intervals are still
+/// filled. Returns whether the interval was computed.
+fn skip_interval_out_of_range(res: eyre::Result<()>) -> eyre::Result<bool> {
+    match res {
+        Ok(()) => Ok(true),
+        Err(e) => match e.downcast_ref::<AnalyticsError>() {
+            Some(e @ AnalyticsError::IntervalOutOfRange { .. }) => {
+                warn!("Skipping interval: {e}");
+                Ok(false)
+            }
+            _ => Err(e),
+        },
+    }
+}
+
 pub struct AnalyticsState {
     pub analytics: Vec<Analytic>,
-    pub prev_protocol_params: ProtocolParameters,
+}
+
+/// A summary of the state of the running analytics after a milestone.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "api"), allow(dead_code))]
+pub struct AnalyticsSnapshot {
+    pub milestone_index: MilestoneIndex,
+    /// The state of each analytic, or `null` if it does not carry state across milestones.
+    pub analytics: BTreeMap<String, serde_json::Value>,
+}
+
+/// The analytics of the INX worker along with the latest milestone that they analyzed.
+#[cfg_attr(not(feature = "api"), allow(dead_code))]
+pub struct RunningAnalytics {
+    pub milestone_index: MilestoneIndex,
+    pub state: AnalyticsState,
+}
+
+/// The analytics of the INX worker, which are shared with the API for diagnostics. Summarizing their state can be
+/// expensive, so an [`AnalyticsSnapshot`] is only taken when it is requested.
+#[derive(Clone, Default)]
+#[cfg_attr(not(any(feature = "api", feature = "inx")), allow(dead_code))]
+pub struct SharedAnalytics(Arc<Mutex<Option<RunningAnalytics>>>);
+
+impl SharedAnalytics {
+    /// Summarizes the state of the analytics, if any milestone has been analyzed yet.
+    #[cfg(feature = "api")]
+    pub async fn snapshot(&self) -> Option<AnalyticsSnapshot> {
+        let running = self.0.lock().await;
+        let running = running.as_ref()?;
+        let analytics = running
+            .state
+            .analytics
+            .iter()
+            .filter_map(|analytic| {
+                analytic
+                    .choice()
+                    .to_possible_value()
+                    .map(|value| (value.get_name().to_string(), analytic.state().unwrap_or_default()))
+            })
+            .collect();
+        Some(AnalyticsSnapshot {
+            milestone_index: running.milestone_index,
+            analytics,
+        })
+    }
+
+    /// Locks the analytics so that they can be updated with a milestone.
+    #[cfg(feature = "inx")]
+    pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, Option<RunningAnalytics>> {
+        self.0.lock().await
+    }
+}
+
+impl std::fmt::Debug for SharedAnalytics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedAnalytics").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use time::macros::{date, datetime};
+
+    use super::*;
+
+    #[test]
+    fn skips_interval_out_of_range() {
+        let out_of_range = AnalyticsError::IntervalOutOfRange {
+            interval: AnalyticsInterval::Day,
+            start: date!(2023 - 01 - 01),
+            end: date!(2023 - 01 - 02),
+            first: datetime!(2023-02-01 0:00 UTC),
+            last: datetime!(2023-03-01 0:00 UTC),
+        };
+        let no_milestones = AnalyticsError::NoStoredMilestones {
+            interval: AnalyticsInterval::Day,
+        };
+        assert!(skip_interval_out_of_range(Ok(())).unwrap());
+        assert!(!skip_interval_out_of_range(Err(out_of_range.into())).unwrap());
+        assert!(skip_interval_out_of_range(Err(no_milestones.into())).is_err());
+    }
 }
@@ -1,6 +1,8 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::num::NonZeroUsize;
+
 use api::ApiConfig;
 use clap::{Args, Parser};
 
@@ -11,15 +13,74 @@ pub struct ApiArgs {
     /// API listening port.
     #[arg(long, value_name = "PORT", default_value_t = api::DEFAULT_PORT)]
     pub api_port: u16,
+    /// The path prefix under which the API is served, e.g. `/chronicle`.
+    #[arg(long, value_name = "PATH", default_value = api::DEFAULT_BASE_PATH)]
+    pub base_path: String,
     /// CORS setting.
     #[arg(long = "allow-origin", value_name = "IP", default_value = api::DEFAULT_ALLOW_ORIGINS)]
     pub allow_origins: Vec<String>,
     /// Public API routes.
     #[arg(long = "public-route", value_name = "ROUTE", default_value = api::DEFAULT_PUBLIC_ROUTES)]
     pub public_routes: Vec<String>,
-    /// Maximum number of results returned by a single API call.
+    /// Maximum number of results returned by a single API call. This also caps the number of outputs in a random
+    /// sample returned by the indexer when `sample=true` is requested.
     #[arg(long, value_name = "SIZE", default_value_t = api::DEFAULT_MAX_PAGE_SIZE)]
     pub max_page_size: usize,
+    /// Maximum duration of a single API request before it is aborted.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, default_value = api::DEFAULT_REQUEST_TIMEOUT)]
+    pub request_timeout: std::time::Duration,
+    /// How long after the last fresh milestone arrived Chronicle is still reported as healthy while the newest
+    /// milestone is stale. This keeps brief pauses in milestone delivery from flapping the health status.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, default_value = api::DEFAULT_UNHEALTHY_GRACE_PERIOD)]
+    pub unhealthy_grace_period: std::time::Duration,
+    /// Maximum number of API requests handled concurrently. Requests beyond the limit are rejected with
+    /// `503 Service Unavailable`. Health checks are not subject to the limit.
+    #[arg(long, value_name = "COUNT")]
+    pub max_concurrent_requests: Option<usize>,
+    /// The format in which the indexer API emits pagination cursors. `node` matches the opaque cursors of the
+    /// node's indexer plugin. Cursors in either format are accepted.
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = api::DEFAULT_CURSOR_FORMAT)]
+    pub cursor_format: api::CursorFormat,
+    /// The members of a MongoDB replica set that API reads are routed to. Requests can override this with the
+    /// `X-Read-Preference` header. Reads from secondaries may return a slightly stale ledger index.
+    #[arg(long, value_name = "MODE", value_enum, default_value_t = api::DEFAULT_READ_PREFERENCE)]
+    pub read_preference: api::ReadPreferenceMode,
+    /// How token amounts are represented in explorer API responses. Requests can override this with the
+    /// `X-Amount-Format` header. Numbers lose precision above 2^53 in JavaScript clients.
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = api::DEFAULT_AMOUNT_FORMAT)]
+    pub api_amount_format: api::AmountFormat,
+    /// The number of recently requested outputs that are kept in memory. Set to `0` to disable the cache.
+    #[arg(long, value_name = "COUNT", default_value_t = api::DEFAULT_OUTPUT_CACHE_SIZE)]
+    pub output_cache_size: usize,
+    /// Read balances from the outputs collection instead of the address outputs index.
+    #[arg(long, default_value_t = !api::DEFAULT_ADDRESS_INDEX_ENABLED)]
+    pub disable_address_index: bool,
+    /// The maximum number of outputs of a single address that the balance and summary endpoints read. Results for
+    /// addresses with more outputs are flagged as truncated.
+    #[arg(long, value_name = "COUNT")]
+    pub max_address_outputs: Option<NonZeroUsize>,
+    /// The location of a file listing addresses, one per line, for which no data is served. The file is reloaded
+    /// when it changes.
+    #[arg(long, value_name = "FILEPATH")]
+    pub address_denylist: Option<String>,
+    /// An address that is left out of the richlist, like that of the treasury or of burned tokens. Can be given
+    /// multiple times.
+    #[arg(long = "richlist-exclude", value_name = "ADDRESS")]
+    pub richlist_excluded_addresses: Vec<String>,
+    /// The name of the network the database is expected to contain. The API refuses to start if the database was
+    /// populated for a different network.
+    #[arg(long, value_name = "NAME", env = "NETWORK_NAME")]
+    pub network_name: Option<String>,
+    /// The bech32 HRP of the network the database is expected to contain. The API refuses to start if the database
+    /// was populated for a network with a different HRP.
+    #[arg(long, value_name = "HRP", env = "BECH32_HRP")]
+    pub bech32_hrp: Option<String>,
+    /// Write one in this many API requests to the access log. Set to `0` to disable sampling.
+    #[arg(long, value_name = "COUNT", default_value_t = api::DEFAULT_ACCESS_LOG_SAMPLE_RATE)]
+    pub access_log_sample_rate: u64,
+    /// Write every API request that fails to the access log, regardless of sampling.
+    #[arg(long, default_value_t = api::DEFAULT_ACCESS_LOG_ERRORS)]
+    pub access_log_errors: bool,
     /// JWT arguments.
     #[command(flatten)]
     pub jwt: JwtArgs,
@@ -33,13 +94,30 @@ impl From<&ApiArgs> for api::ApiConfig {
         Self {
             enabled: !value.disable_api,
             port: value.api_port,
+            base_path: value.base_path.clone(),
             allow_origins: (&value.allow_origins).into(),
             jwt_password: value.jwt.jwt_password.clone(),
             jwt_salt: value.jwt.jwt_salt.clone(),
             jwt_identity_file: value.jwt.jwt_identity.clone(),
             jwt_expiration: value.jwt.jwt_expiration,
+            jwt_issuer: value.jwt.jwt_issuer.clone(),
             max_page_size: value.max_page_size,
             public_routes: value.public_routes.clone(),
+            request_timeout: value.request_timeout,
+            unhealthy_grace_period: value.unhealthy_grace_period,
+            max_concurrent_requests: value.max_concurrent_requests,
+            cursor_format: value.cursor_format,
+            read_preference: value.read_preference,
+            amount_format: value.api_amount_format,
+            output_cache_size: value.output_cache_size,
+            address_index_enabled: !value.disable_address_index,
+            max_address_outputs: value.max_address_outputs,
+            address_denylist_file: value.address_denylist.clone(),
+            richlist_excluded_addresses: value.richlist_excluded_addresses.clone(),
+            network_name: value.network_name.clone(),
+            bech32_hrp: value.bech32_hrp.clone(),
+            access_log_sample_rate: value.access_log_sample_rate,
+            access_log_errors: value.access_log_errors,
         }
     }
 }
@@ -58,6 +136,9 @@ pub struct JwtArgs {
     /// The setting for when the (JWT) token expires.
     #[arg(long, value_name = "DURATION", value_parser = parse_duration, default_value = api::DEFAULT_JWT_EXPIRATION)]
     pub jwt_expiration: std::time::Duration,
+    /// The issuer of JWT tokens, which incoming tokens are validated against.
+    #[arg(long, value_name = "ISSUER", env = "JWT_ISSUER", default_value = api::DEFAULT_JWT_ISSUER)]
+    pub jwt_issuer: String,
 }
 
 fn parse_duration(arg: &str) -> Result<std::time::Duration, humantime::DurationError> {
@@ -73,7 +154,7 @@ impl GenerateJWTCommand {
         use crate::api::ApiConfigData;
         let api_data = ApiConfigData::try_from(config.clone()).expect("invalid API config");
         let claims = auth_helper::jwt::Claims::new(
-            ApiConfigData::ISSUER,
+            &api_data.jwt_issuer,
             uuid::Uuid::new_v4().to_string(),
             ApiConfigData::AUDIENCE,
         )
@@ -1,7 +1,10 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use chronicle::db::influxdb::AnalyticsChoice;
+use std::num::NonZeroU32;
+
+use chronicle::db::influxdb::{AmountFormat, AnalyticsChoice, AnalyticsSinkConfig, TimestampResolution};
+use clap::ValueEnum;
 
 use super::*;
 
@@ -10,10 +13,67 @@ pub struct InfluxAnalyticsArgs {
     /// The Analytics database name.
     #[arg(long, value_name = "NAME", default_value = influxdb::DEFAULT_ANALYTICS_DATABASE_NAME)]
     pub analytics_database_name: String,
+    /// The database name for ledger analytics. If unset, the analytics database will be used.
+    #[arg(long, value_name = "NAME")]
+    pub ledger_analytics_database_name: Option<String>,
+    /// The database name for tangle analytics. If unset, the analytics database will be used.
+    #[arg(long, value_name = "NAME")]
+    pub tangle_analytics_database_name: Option<String>,
     /// Disable InfluxDb time-series analytics writes.
     #[arg(long, default_value_t = !influxdb::DEFAULT_ANALYTICS_ENABLED)]
     pub disable_analytics: bool,
-    /// Select a subset of analytics to compute. If unset, all analytics will be computed.
-    #[arg(long, value_name = "ANALYTICS")]
+    /// Select a subset of analytics to compute. If unset, all analytics will be computed. Also read from the
+    /// `ENABLED_ANALYTICS` environment variable as a comma-separated list, which is overridden by the command line.
+    #[arg(long, value_name = "ANALYTICS", env = "ENABLED_ANALYTICS", value_delimiter = ',')]
     pub analytics: Vec<AnalyticsChoice>,
+    /// Only compute an analytic every Nth milestone, e.g. `holding-time=10`. Sampled series are sparser, and
+    /// analytics of the ledger state report it as of the sampled milestone. `milestone-size-totals` and
+    /// `output-velocity` carry per-milestone state and cannot be sampled.
+    #[arg(long, value_name = "ANALYTIC=N", value_parser = parse_analytics_sampling)]
+    pub analytics_sampling: Vec<(AnalyticsChoice, NonZeroU32)>,
+    /// The format of amount fields in analytics. Scaled amounts are floats, which lose precision for large values.
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = influxdb::DEFAULT_AMOUNT_FORMAT)]
+    pub amount_format: AmountFormat,
+    /// The number of decimals of the base token, used to scale amounts.
+    #[arg(long, value_name = "DECIMALS", default_value_t = influxdb::DEFAULT_BASE_TOKEN_DECIMALS)]
+    pub base_token_decimals: u32,
+    /// The resolution that the timestamps of interval analytics are snapped to. Snapping aligns points to period
+    /// boundaries, and must not be coarser than the interval. Per-milestone analytics keep the exact milestone
+    /// timestamp.
+    #[arg(long, value_name = "RESOLUTION", value_enum, default_value_t = influxdb::DEFAULT_TIMESTAMP_RESOLUTION)]
+    pub timestamp_resolution: TimestampResolution,
+    /// The maximum number of milestones that analytics may lag behind the node on startup. If they lag further,
+    /// the older milestones are skipped. If unset, the whole backlog is processed.
+    #[arg(long, value_name = "MILESTONES")]
+    pub max_analytics_backlog: Option<u32>,
+    /// A destination of analytics measurements, either `influxdb` or `file:PATH` to append them to a file in the
+    /// InfluxDb line protocol. Can be given multiple times to write every measurement to all of them.
+    #[arg(long = "analytics-sink", value_name = "SINK", default_value = influxdb::DEFAULT_ANALYTICS_SINK)]
+    pub analytics_sinks: Vec<AnalyticsSinkConfig>,
+}
+
+fn parse_analytics_sampling(s: &str) -> eyre::Result<(AnalyticsChoice, NonZeroU32)> {
+    let (choice, every) = s
+        .split_once('=')
+        .ok_or_else(|| eyre::eyre!("expected `ANALYTIC=N`, found `{s}`"))?;
+    let analytic = AnalyticsChoice::from_str(choice, true).map_err(|e| eyre::eyre!(e))?;
+    if !analytic.supports_sampling() {
+        eyre::bail!("`{choice}` cannot be sampled");
+    }
+    Ok((analytic, every.parse()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rejects_sampling_of_stateful_analytics() {
+        assert_eq!(
+            parse_analytics_sampling("holding-time=10").unwrap(),
+            (AnalyticsChoice::HoldingTime, NonZeroU32::new(10).unwrap())
+        );
+        assert!(parse_analytics_sampling("milestone-size-totals=10").is_err());
+        assert!(parse_analytics_sampling("output-velocity=10").is_err());
+    }
 }
@@ -39,7 +39,23 @@ impl From<&InfluxDbArgs> for InfluxDbConfig {
             #[cfg(feature = "analytics")]
             analytics_database_name: value.analytics_args.analytics_database_name.clone(),
             #[cfg(feature = "analytics")]
+            ledger_analytics_database_name: value.analytics_args.ledger_analytics_database_name.clone(),
+            #[cfg(feature = "analytics")]
+            tangle_analytics_database_name: value.analytics_args.tangle_analytics_database_name.clone(),
+            #[cfg(feature = "analytics")]
             analytics: value.analytics_args.analytics.clone(),
+            #[cfg(feature = "analytics")]
+            analytics_sampling: value.analytics_args.analytics_sampling.iter().copied().collect(),
+            #[cfg(feature = "analytics")]
+            amount_format: value.analytics_args.amount_format,
+            #[cfg(feature = "analytics")]
+            base_token_decimals: value.analytics_args.base_token_decimals,
+            #[cfg(feature = "analytics")]
+            timestamp_resolution: value.analytics_args.timestamp_resolution,
+            #[cfg(feature = "analytics")]
+            max_analytics_backlog: value.analytics_args.max_analytics_backlog,
+            #[cfg(feature = "analytics")]
+            analytics_sinks: value.analytics_args.analytics_sinks.clone(),
             #[cfg(feature = "metrics")]
             metrics_enabled: !value.metrics_args.disable_metrics,
             #[cfg(feature = "metrics")]
@@ -1,6 +1,9 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::PathBuf;
+
+use chronicle::inx::UnknownOutputPolicy;
 use clap::Args;
 
 use crate::inx::config as inx;
@@ -14,6 +17,26 @@ pub struct InxArgs {
     /// genesis block. If set to `0` Chronicle will start syncing from the most recent milestone it received.
     #[arg(long, value_name = "START", default_value_t = inx::DEFAULT_SYNC_START)]
     pub inx_sync_start: u32,
+    /// A snapshot file of unspent outputs used to bootstrap a fresh database. The snapshot's milestone index must
+    /// match `--inx-sync-start`, and synchronization continues from the following milestone.
+    #[arg(long, value_name = "PATH", env = "INX_GENESIS_SNAPSHOT")]
+    pub inx_genesis_snapshot: Option<PathBuf>,
+    /// How outputs of kinds that are unknown to Chronicle, e.g. those introduced by a future protocol upgrade, are
+    /// treated. Skipped outputs are logged and missing from the ledger.
+    #[arg(long, value_name = "POLICY", value_enum, default_value_t = inx::DEFAULT_UNKNOWN_OUTPUTS)]
+    pub inx_unknown_outputs: UnknownOutputPolicy,
+    /// The memory, in MiB, that blocks of a milestone cone may occupy while they are written to the database. Once
+    /// it is reached, reading the cone pauses until buffered blocks have been written.
+    #[arg(long, value_name = "MIB", default_value_t = inx::DEFAULT_CONE_MEMORY_BUDGET_MIB)]
+    pub inx_cone_memory_budget: usize,
+    /// How milestones are treated whose timestamp is not after that of the previous milestone, e.g. because the node
+    /// delivered a milestone again. Clamped milestones are recorded one second after the previous milestone.
+    #[arg(long, value_name = "POLICY", value_enum, default_value_t = inx::DEFAULT_TIMESTAMP_REGRESSIONS)]
+    pub inx_timestamp_regressions: inx::TimestampRegressionPolicy,
+    /// Store every block the node receives, including blocks that are never referenced by a milestone. These are
+    /// kept in a separate collection until a milestone references them, and orphaned blocks are never removed.
+    #[arg(long, default_value_t = inx::DEFAULT_STORE_UNREFERENCED_BLOCKS)]
+    pub inx_store_unreferenced_blocks: bool,
     /// Disable the INX synchronization workflow.
     #[arg(long, default_value_t = !inx::DEFAULT_ENABLED)]
     pub disable_inx: bool,
@@ -25,6 +48,11 @@ impl From<&InxArgs> for inx::InxConfig {
             enabled: !value.disable_inx,
             url: value.inx_url.clone(),
             sync_start_milestone: value.inx_sync_start.into(),
+            genesis_snapshot: value.inx_genesis_snapshot.clone(),
+            unknown_outputs: value.inx_unknown_outputs,
+            cone_memory_budget: value.inx_cone_memory_budget << 20,
+            timestamp_regressions: value.inx_timestamp_regressions,
+            store_unreferenced_blocks: value.inx_store_unreferenced_blocks,
         }
     }
 }
@@ -1,6 +1,8 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::PathBuf;
+
 use chronicle::db::mongodb::config as mongodb;
 use clap::{Args, Parser, Subcommand};
 
@@ -53,6 +55,23 @@ pub struct MongoDbArgs {
     /// The MongoDb database name.
     #[arg(long, value_name = "NAME", env = "MONGODB_DB_NAME", default_value = mongodb::DEFAULT_DATABASE_NAME)]
     pub mongodb_database_name: String,
+    /// The CA file that the TLS certificate of the MongoDb server is verified against. Enables TLS.
+    #[arg(long, value_name = "PATH", env = "MONGODB_TLS_CA_FILE")]
+    pub mongodb_tls_ca_file: Option<PathBuf>,
+    /// The file holding the client certificate and private key that are presented to the MongoDb server. Enables
+    /// TLS.
+    #[arg(long, value_name = "PATH", env = "MONGODB_TLS_CERT_KEY_FILE")]
+    pub mongodb_tls_cert_key_file: Option<PathBuf>,
+    /// The mechanism used to authenticate with MongoDb. The user and password are taken from the connection string.
+    #[arg(long, value_name = "MECHANISM", value_enum, env = "MONGODB_AUTH_MECHANISM")]
+    pub mongodb_auth_mechanism: Option<mongodb::MongoDbAuthMechanism>,
+    /// The MongoDb database that holds the credentials of the user.
+    #[arg(long, value_name = "NAME", env = "MONGODB_AUTH_SOURCE")]
+    pub mongodb_auth_source: Option<String>,
+    /// Create the blocks collection with zstd compression, which shrinks the stored raw blocks at the cost of some
+    /// CPU. Only applies to a newly created database.
+    #[arg(long, env = "MONGODB_COMPRESS_BLOCKS")]
+    pub mongodb_compress_blocks: bool,
 }
 
 impl From<&MongoDbArgs> for chronicle::db::MongoDbConfig {
@@ -60,6 +79,11 @@ impl From<&MongoDbArgs> for chronicle::db::MongoDbConfig {
         Self {
             conn_str: value.mongodb_conn_str.clone(),
             database_name: value.mongodb_database_name.clone(),
+            tls_ca_file: value.mongodb_tls_ca_file.clone(),
+            tls_cert_key_file: value.mongodb_tls_cert_key_file.clone(),
+            auth_mechanism: value.mongodb_auth_mechanism,
+            auth_source: value.mongodb_auth_source.clone(),
+            compress_blocks: value.mongodb_compress_blocks,
         }
     }
 }
@@ -92,6 +116,10 @@ impl ClArgs {
                 Subcommands::FillAnalytics(cmd) => {
                     cmd.handle(config).await?;
                 }
+                #[cfg(feature = "analytics")]
+                Subcommands::AnalyticsSchema(cmd) => {
+                    cmd.handle(config)?;
+                }
                 #[cfg(debug_assertions)]
                 Subcommands::ClearDatabase { run } => {
                     tracing::info!("Connecting to database using hosts: `{}`.", config.mongodb.hosts_str()?);
@@ -129,6 +157,8 @@ pub enum Subcommands {
     GenerateJWT(api::GenerateJWTCommand),
     #[cfg(feature = "analytics")]
     FillAnalytics(analytics::FillAnalyticsCommand),
+    #[cfg(feature = "analytics")]
+    AnalyticsSchema(analytics::AnalyticsSchemaCommand),
     /// Clear the Chronicle database.
     #[cfg(debug_assertions)]
     ClearDatabase {
@@ -147,3 +177,15 @@ pub enum PostCommand {
     Start,
     Exit,
 }
+
+#[cfg(test)]
+mod test {
+    use clap::CommandFactory;
+
+    use super::ClArgs;
+
+    #[test]
+    fn verify_cli() {
+        ClArgs::command().debug_assert();
+    }
+}
@@ -1,11 +1,26 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use chronicle::model::tangle::MilestoneIndex;
+use std::path::PathBuf;
+
+use chronicle::{inx::UnknownOutputPolicy, model::tangle::MilestoneIndex};
 
 pub const DEFAULT_ENABLED: bool = true;
 pub const DEFAULT_URL: &str = "http://localhost:9029";
 pub const DEFAULT_SYNC_START: u32 = 0;
+pub const DEFAULT_UNKNOWN_OUTPUTS: UnknownOutputPolicy = UnknownOutputPolicy::Fail;
+pub const DEFAULT_CONE_MEMORY_BUDGET_MIB: usize = 256;
+pub const DEFAULT_TIMESTAMP_REGRESSIONS: TimestampRegressionPolicy = TimestampRegressionPolicy::Fail;
+pub const DEFAULT_STORE_UNREFERENCED_BLOCKS: bool = false;
+
+/// How milestones are treated whose timestamp is not after the timestamp of the previous milestone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimestampRegressionPolicy {
+    /// Stop with an error, so that time-based analytics are never computed from out of order timestamps.
+    Fail,
+    /// Log a warning and record the milestone one second after the previous one.
+    Clamp,
+}
 
 /// Configuration for an INX connection.
 #[derive(Clone, Debug)]
@@ -15,6 +30,18 @@ pub struct InxConfig {
     pub url: String,
     /// The milestone at which synchronization should begin.
     pub sync_start_milestone: MilestoneIndex,
+    /// A snapshot file used to populate the ledger of a fresh database instead of reading it from the node.
+    pub genesis_snapshot: Option<PathBuf>,
+    /// How outputs of kinds that Chronicle does not know are treated.
+    pub unknown_outputs: UnknownOutputPolicy,
+    /// The number of bytes of milestone cone blocks that may be buffered while they are written to the database.
+    /// Reading the cone pauses once the budget is reached.
+    pub cone_memory_budget: usize,
+    /// How milestones are treated whose timestamp is not after that of the previous milestone.
+    pub timestamp_regressions: TimestampRegressionPolicy,
+    /// Whether blocks are stored as soon as the node receives them, so that blocks that are never referenced by a
+    /// milestone are kept as well.
+    pub store_unreferenced_blocks: bool,
 }
 
 impl Default for InxConfig {
@@ -23,6 +50,11 @@ impl Default for InxConfig {
             enabled: DEFAULT_ENABLED,
             url: DEFAULT_URL.to_string(),
             sync_start_milestone: DEFAULT_SYNC_START.into(),
+            genesis_snapshot: None,
+            unknown_outputs: DEFAULT_UNKNOWN_OUTPUTS,
+            cone_memory_budget: DEFAULT_CONE_MEMORY_BUDGET_MIB << 20,
+            timestamp_regressions: DEFAULT_TIMESTAMP_REGRESSIONS,
+            store_unreferenced_blocks: DEFAULT_STORE_UNREFERENCED_BLOCKS,
         }
     }
 }
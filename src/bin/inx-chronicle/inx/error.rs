@@ -1,7 +1,7 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use chronicle::model::tangle::MilestoneIndex;
+use chronicle::model::tangle::{MilestoneIndex, MilestoneTimestamp};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -13,11 +13,27 @@ pub enum InxWorkerError {
         found: MilestoneIndex,
         expected: MilestoneIndex,
     },
+    #[error(
+        "milestone `{index}` has timestamp `{}`, which is not after timestamp `{}` of milestone `{previous_index}`",
+        timestamp.0,
+        previous_timestamp.0
+    )]
+    MilestoneTimestampRegression {
+        index: MilestoneIndex,
+        timestamp: MilestoneTimestamp,
+        previous_index: MilestoneIndex,
+        previous_timestamp: MilestoneTimestamp,
+    },
     #[cfg(feature = "analytics")]
     #[error("missing application state")]
     MissingAppState,
     #[error("network changed from previous run. old network name: `{old}`, new network name: `{new}`")]
     NetworkChanged { old: String, new: String },
+    #[error("genesis snapshot is at milestone index `{snapshot}`, but the sync start is configured as `{config}`")]
+    SnapshotIndexMismatch {
+        snapshot: MilestoneIndex,
+        config: MilestoneIndex,
+    },
     #[error("node pruned required milestones between `{start}` and `{end}`")]
     SyncMilestoneGap { start: MilestoneIndex, end: MilestoneIndex },
     #[error("node confirmed milestone index `{node}` is less than index in database `{db}`")]
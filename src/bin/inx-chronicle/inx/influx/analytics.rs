@@ -4,7 +4,7 @@
 use std::collections::HashSet;
 
 use chronicle::{
-    analytics::Analytic,
+    analytics::{Analytic, TeeSink},
     db::{
         influxdb::{AnalyticsChoice, InfluxDb},
         mongodb::collections::{ApplicationStateCollection, OutputCollection},
@@ -15,36 +15,73 @@ use chronicle::{
     tangle::Milestone,
 };
 use futures::TryStreamExt;
+use tracing::warn;
 
 use super::InxWorkerError;
-use crate::{cli::analytics::AnalyticsState, inx::InxWorker};
+use crate::{
+    cli::analytics::{AnalyticsState, RunningAnalytics, SharedAnalytics},
+    inx::InxWorker,
+};
 
 pub struct AnalyticsInfo {
     analytics_choices: HashSet<AnalyticsChoice>,
-    state: Option<AnalyticsState>,
+    analytics: SharedAnalytics,
+    sink: TeeSink,
     pub synced_index: MilestoneIndex,
 }
 
 impl AnalyticsInfo {
-    pub async fn init(db: &MongoDb, influx_db: Option<&InfluxDb>) -> eyre::Result<Option<Self>> {
-        Ok(if let Some(influx_db) = influx_db {
-            Some(Self {
-                analytics_choices: if influx_db.config().analytics.is_empty() {
-                    chronicle::db::influxdb::config::all_analytics()
-                } else {
-                    influx_db.config().analytics.iter().copied().collect()
-                },
-                state: None,
-                synced_index: db
+    pub async fn init(
+        db: &MongoDb,
+        influx_db: Option<&InfluxDb>,
+        shared_analytics: Option<&SharedAnalytics>,
+        start_index: MilestoneIndex,
+    ) -> eyre::Result<Option<Self>> {
+        Ok(
+            if let Some(influx_db) = influx_db.filter(|influx_db| influx_db.config().analytics_enabled) {
+                let mut synced_index = db
                     .collection::<ApplicationStateCollection>()
                     .get_starting_index()
                     .await?
                     .ok_or(InxWorkerError::MissingAppState)?
-                    .milestone_index,
-            })
-        } else {
-            None
-        })
+                    .milestone_index;
+                if let Some(max_backlog) = influx_db.config().max_analytics_backlog {
+                    if let Some(node_confirmed_index) = db
+                        .collection::<ApplicationStateCollection>()
+                        .get_node_confirmed_index()
+                        .await?
+                    {
+                        // The analytics state is initialized from the ledger at the first analyzed milestone, so
+                        // skipping milestones does not corrupt it.
+                        let resume_index = start_index.max(synced_index);
+                        if node_confirmed_index.0.saturating_sub(resume_index.0) > max_backlog {
+                            let skip_to = node_confirmed_index - max_backlog;
+                            warn!(
+                            "Analytics are {} milestones behind the node, which exceeds the maximum backlog of {}. \
+                            Skipping analytics for milestones {}..{}.",
+                            node_confirmed_index.0 - resume_index.0,
+                            max_backlog,
+                            resume_index,
+                            skip_to
+                        );
+                            synced_index = skip_to;
+                        }
+                    }
+                }
+                Some(Self {
+                    analytics_choices: if influx_db.config().analytics.is_empty() {
+                        chronicle::db::influxdb::config::all_analytics()
+                    } else {
+                        influx_db.config().analytics.iter().copied().collect()
+                    },
+                    analytics: shared_analytics.cloned().unwrap_or_default(),
+                    sink: TeeSink::from_config(influx_db)?,
+                    synced_index,
+                })
+            } else {
+                None
+            },
+        )
     }
 }
 
@@ -54,14 +91,17 @@ impl InxWorker {
         milestone: &Milestone<'a, Inx>,
         AnalyticsInfo {
             analytics_choices,
-            state,
+            analytics: shared_analytics,
+            sink,
             ..
         }: &mut AnalyticsInfo,
     ) -> eyre::Result<()> {
         if let (Some(influx_db), analytics_choices) = (&self.influx_db, analytics_choices) {
             if influx_db.config().analytics_enabled {
-                // Check if the protocol params changed (or we just started)
-                if !matches!(&state, Some(state) if state.prev_protocol_params == milestone.protocol_params) {
+                // Initialize the analytics if we just started. Changes to the protocol parameters are handled by the
+                // analytics themselves.
+                let mut running = shared_analytics.lock().await;
+                if running.is_none() {
                     let ledger_state = self
                         .db
                         .collection::<OutputCollection>()
@@ -70,20 +110,26 @@ impl InxWorker {
                         .try_collect::<Vec<_>>()
                         .await?;
 
-                    let analytics = analytics_choices
-                        .iter()
-                        .map(|choice| Analytic::init(choice, &milestone.protocol_params, &ledger_state))
-                        .collect::<Vec<_>>();
-                    *state = Some(AnalyticsState {
-                        analytics,
-                        prev_protocol_params: milestone.protocol_params.clone(),
+                    let analytics = futures::future::try_join_all(analytics_choices.iter().map(|choice| {
+                        Analytic::init(
+                            choice,
+                            &milestone.protocol_params,
+                            &ledger_state,
+                            influx_db,
+                            milestone.at.milestone_index,
+                        )
+                    }))
+                    .await?;
+                    *running = Some(RunningAnalytics {
+                        milestone_index: milestone.at.milestone_index - 1,
+                        state: AnalyticsState { analytics },
                     });
                 }
 
                 // Unwrap: safe because we guarantee it is initialized above
-                milestone
-                    .update_analytics(&mut state.as_mut().unwrap().analytics, influx_db)
-                    .await?;
+                let running = running.as_mut().unwrap();
+                milestone.update_analytics(&mut running.state.analytics, sink).await?;
+                running.milestone_index = milestone.at.milestone_index;
             }
         }
 
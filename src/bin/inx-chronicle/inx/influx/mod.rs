@@ -26,7 +26,7 @@ impl InxWorker {
         #[cfg(all(feature = "analytics", feature = "metrics"))]
         {
             if let Some(influx_db) = &self.influx_db {
-                if influx_db.config().analytics_enabled {
+                if influx_db.config().analytics_enabled && influx_db.config().metrics_enabled {
                     let analytics_elapsed = analytics_start_time.elapsed();
                     influx_db
                         .metrics()
@@ -5,14 +5,16 @@ pub mod config;
 mod error;
 #[cfg(feature = "influx")]
 mod influx;
+mod snapshot;
 
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use chronicle::{
     db::{
         mongodb::collections::{
-            ApplicationStateCollection, BlockCollection, ConfigurationUpdateCollection, LedgerUpdateCollection,
-            MilestoneCollection, OutputCollection, ProtocolUpdateCollection, TreasuryCollection,
+            AddressOutputCollection, ApplicationStateCollection, BlockCollection, ConfigurationUpdateCollection,
+            LedgerUpdateCollection, MilestoneCollection, OutputCollection, ProtocolUpdateCollection,
+            TreasuryCollection, UnreferencedBlockCollection,
         },
         MongoDb,
     },
@@ -20,17 +22,25 @@ use chronicle::{
     model::{
         ledger::{LedgerOutput, LedgerSpent},
         metadata::LedgerInclusionState,
-        payload::Payload,
+        payload::{
+            transaction::{input::Input, TransactionEssence},
+            Payload,
+        },
         tangle::{MilestoneIndex, MilestoneIndexTimestamp},
+        utxo::OutputId,
     },
     tangle::{Milestone, Tangle},
 };
 use eyre::{bail, Result};
 use futures::{StreamExt, TryStreamExt};
 use tokio::{task::JoinSet, try_join};
-use tracing::{debug, info, instrument, trace_span, Instrument};
+use tracing::{debug, info, instrument, trace_span, warn, Instrument};
 
-pub use self::{config::InxConfig, error::InxWorkerError};
+pub use self::{
+    config::{InxConfig, TimestampRegressionPolicy},
+    error::InxWorkerError,
+    snapshot::GenesisSnapshot,
+};
 use crate::migrations::{LatestMigration, Migration};
 
 /// Batch size for insert operations.
@@ -41,6 +51,8 @@ pub struct InxWorker {
     config: InxConfig,
     #[cfg(feature = "influx")]
     influx_db: Option<chronicle::db::influxdb::InfluxDb>,
+    #[cfg(feature = "analytics")]
+    shared_analytics: Option<crate::cli::analytics::SharedAnalytics>,
 }
 
 impl InxWorker {
@@ -51,6 +63,8 @@ impl InxWorker {
             config: inx_config,
             #[cfg(feature = "influx")]
             influx_db: None,
+            #[cfg(feature = "analytics")]
+            shared_analytics: None,
         }
     }
 
@@ -59,6 +73,12 @@ impl InxWorker {
         self.influx_db.replace(influx_db.clone());
     }
 
+    /// Shares the analytics with the API, so that their state can be inspected.
+    #[cfg(feature = "analytics")]
+    pub fn set_shared_analytics(&mut self, shared_analytics: &crate::cli::analytics::SharedAnalytics) {
+        self.shared_analytics.replace(shared_analytics.clone());
+    }
+
     async fn connect(&self) -> Result<Inx> {
         let url = url::Url::parse(&self.config.url)?;
 
@@ -66,22 +86,56 @@ impl InxWorker {
             bail!(InxWorkerError::InvalidAddress(self.config.url.clone()));
         }
 
-        Ok(Inx::connect(self.config.url.clone()).await?)
+        Ok(Inx::connect(self.config.url.clone())
+            .await?
+            .with_unknown_output_policy(self.config.unknown_outputs))
     }
 
     pub async fn run(&mut self) -> Result<()> {
         let (start_index, inx) = self.init().await?;
 
+        // Dropping the set stops listening to blocks once the ledger updates end.
+        let mut unreferenced_blocks = JoinSet::new();
+        if self.config.store_unreferenced_blocks {
+            unreferenced_blocks.spawn(store_unreferenced_blocks(self.db.clone(), inx.clone()));
+        }
+
         let tangle = Tangle::from(inx);
 
         let mut stream = tangle.milestone_stream(start_index..).await?;
 
         #[cfg(feature = "analytics")]
-        let mut analytics_info = influx::analytics::AnalyticsInfo::init(&self.db, self.influx_db.as_ref()).await?;
+        let mut analytics_info = influx::analytics::AnalyticsInfo::init(
+            &self.db,
+            self.influx_db.as_ref(),
+            self.shared_analytics.as_ref(),
+            start_index,
+        )
+        .await?;
 
         debug!("Started listening to ledger updates via INX.");
 
-        while let Some(milestone) = stream.try_next().await? {
+        let mut previous = self
+            .db
+            .collection::<MilestoneCollection>()
+            .get_newest_milestone()
+            .await?;
+
+        loop {
+            let mut milestone = tokio::select! {
+                milestone = stream.try_next() => match milestone? {
+                    Some(milestone) => milestone,
+                    None => break,
+                },
+                Some(res) = unreferenced_blocks.join_next() => {
+                    res??;
+                    continue;
+                }
+            };
+            if let Some(previous) = previous {
+                self.check_milestone_timestamp(&mut milestone, previous)?;
+            }
+            previous = Some(milestone.at);
             self.handle_ledger_update(
                 milestone,
                 #[cfg(feature = "analytics")]
@@ -95,6 +149,37 @@ impl InxWorker {
         Ok(())
     }
 
+    /// Detects milestones whose timestamp is not after that of the previous milestone, and handles them according to
+    /// the configured [`TimestampRegressionPolicy`].
+    fn check_milestone_timestamp(
+        &self,
+        milestone: &mut Milestone<Inx>,
+        previous: MilestoneIndexTimestamp,
+    ) -> Result<()> {
+        if milestone.at.milestone_timestamp.0 > previous.milestone_timestamp.0 {
+            return Ok(());
+        }
+        warn!(
+            "Milestone {} has timestamp {}, which is not after timestamp {} of milestone {}.",
+            milestone.at.milestone_index,
+            milestone.at.milestone_timestamp.0,
+            previous.milestone_timestamp.0,
+            previous.milestone_index
+        );
+        match self.config.timestamp_regressions {
+            TimestampRegressionPolicy::Fail => bail!(InxWorkerError::MilestoneTimestampRegression {
+                index: milestone.at.milestone_index,
+                timestamp: milestone.at.milestone_timestamp,
+                previous_index: previous.milestone_index,
+                previous_timestamp: previous.milestone_timestamp,
+            }),
+            TimestampRegressionPolicy::Clamp => {
+                milestone.at.milestone_timestamp = (previous.milestone_timestamp.0 + 1).into();
+            }
+        }
+        Ok(())
+    }
+
     #[instrument(skip_all, err, level = "trace")]
     async fn init(&mut self) -> Result<(MilestoneIndex, Inx)> {
         info!("Connecting to INX at bind address `{}`.", &self.config.url);
@@ -117,6 +202,8 @@ impl InxWorker {
             node_status.tangle_pruning_index, node_status.confirmed_milestone.milestone_info.milestone_index,
         );
 
+        let mut genesis_snapshot = None;
+
         // Check if there is an unfixable gap in our node data.
         let start_index = if let Some(MilestoneIndexTimestamp {
             milestone_index: latest_milestone,
@@ -140,6 +227,24 @@ impl InxWorker {
             } else {
                 latest_milestone + 1
             }
+        } else if let Some(path) = &self.config.genesis_snapshot {
+            info!("Reading genesis snapshot `{}`.", path.display());
+            let snapshot = GenesisSnapshot::from_file(path)?;
+            if snapshot.milestone_index != self.config.sync_start_milestone {
+                bail!(InxWorkerError::SnapshotIndexMismatch {
+                    snapshot: snapshot.milestone_index,
+                    config: self.config.sync_start_milestone,
+                });
+            }
+            if node_status.tangle_pruning_index.0 > snapshot.milestone_index.0 {
+                bail!(InxWorkerError::SyncMilestoneGap {
+                    start: snapshot.milestone_index + 1,
+                    end: node_status.tangle_pruning_index,
+                });
+            }
+            let start_index = snapshot.milestone_index + 1;
+            genesis_snapshot = Some(snapshot);
+            start_index
         } else {
             self.config
                 .sync_start_milestone
@@ -191,69 +296,19 @@ impl InxWorker {
                 .collection::<ApplicationStateCollection>()
                 .set_last_migration(latest_version)
                 .await?;
-            info!("Reading unspent outputs.");
-            let unspent_output_stream = inx
-                .read_unspent_outputs()
-                .instrument(trace_span!("inx_read_unspent_outputs"))
-                .await?;
-
-            let mut starting_index = None;
-
-            let mut count = 0;
-            let mut tasks = unspent_output_stream
-                .inspect_ok(|_| count += 1)
-                .map(|msg| {
-                    let msg = msg?;
-                    let ledger_index = &msg.ledger_index;
-                    if let Some(index) = starting_index.as_ref() {
-                        if index != ledger_index {
-                            bail!(InxWorkerError::InvalidUnspentOutputIndex {
-                                found: *ledger_index,
-                                expected: *index,
-                            })
-                        }
-                    } else {
-                        starting_index = Some(*ledger_index);
-                    }
-                    Ok(msg)
-                })
-                .map(|res| Ok(res?.output))
-                .try_chunks(INSERT_BATCH_SIZE)
-                // We only care if we had an error, so discard the other data
-                .map_err(|e| e.1)
-                // Convert batches to tasks
-                .try_fold(JoinSet::new(), |mut tasks, batch| async {
-                    let db = self.db.clone();
-                    tasks.spawn(async move { insert_unspent_outputs(&db, &batch).await });
-                    Result::<_>::Ok(tasks)
-                })
-                .await?;
-
-            while let Some(res) = tasks.join_next().await {
-                res??;
-            }
-
-            info!("Inserted {} unspent outputs.", count);
-
-            let starting_index = starting_index.unwrap_or_default();
-
-            // Get the timestamp for the starting index
-            let milestone_timestamp = inx
-                .read_milestone(starting_index.into())
-                .await?
-                .milestone_info
-                .milestone_timestamp
-                .into();
+            let starting_index = if let Some(snapshot) = genesis_snapshot {
+                self.insert_genesis_snapshot(snapshot).await?
+            } else {
+                self.read_unspent_outputs(&mut inx).await?
+            };
 
             info!(
                 "Setting starting index to {} with timestamp {}",
-                starting_index,
-                time::OffsetDateTime::try_from(milestone_timestamp)?
+                starting_index.milestone_index,
+                time::OffsetDateTime::try_from(starting_index.milestone_timestamp)?
                     .format(&time::format_description::well_known::Rfc3339)?
             );
 
-            let starting_index = starting_index.with_timestamp(milestone_timestamp);
-
             self.db
                 .collection::<ApplicationStateCollection>()
                 .set_starting_index(starting_index)
@@ -271,9 +326,95 @@ impl InxWorker {
                 .await?;
         }
 
+        self.db
+            .collection::<ApplicationStateCollection>()
+            .set_node_confirmed_index(node_status.confirmed_milestone.milestone_info.milestone_index)
+            .await?;
+
         Ok((start_index, inx))
     }
 
+    /// Populates the ledger from the unspent outputs reported by the node.
+    async fn read_unspent_outputs(&self, inx: &mut Inx) -> Result<MilestoneIndexTimestamp> {
+        info!("Reading unspent outputs.");
+        let unspent_output_stream = inx
+            .read_unspent_outputs()
+            .instrument(trace_span!("inx_read_unspent_outputs"))
+            .await?;
+
+        let mut starting_index = None;
+
+        let mut count = 0;
+        let mut tasks = unspent_output_stream
+            .inspect_ok(|_| count += 1)
+            .map(|msg| {
+                let msg = msg?;
+                let ledger_index = &msg.ledger_index;
+                if let Some(index) = starting_index.as_ref() {
+                    if index != ledger_index {
+                        bail!(InxWorkerError::InvalidUnspentOutputIndex {
+                            found: *ledger_index,
+                            expected: *index,
+                        })
+                    }
+                } else {
+                    starting_index = Some(*ledger_index);
+                }
+                Ok(msg)
+            })
+            .map(|res| Ok(res?.output))
+            .try_chunks(INSERT_BATCH_SIZE)
+            // We only care if we had an error, so discard the other data
+            .map_err(|e| e.1)
+            // Convert batches to tasks
+            .try_fold(JoinSet::new(), |mut tasks, batch| async {
+                let db = self.db.clone();
+                tasks.spawn(async move { insert_unspent_outputs(&db, &batch).await });
+                Result::<_>::Ok(tasks)
+            })
+            .await?;
+
+        while let Some(res) = tasks.join_next().await {
+            res??;
+        }
+
+        info!("Inserted {} unspent outputs.", count);
+
+        let starting_index = starting_index.unwrap_or_default();
+
+        // Get the timestamp for the starting index
+        let milestone_timestamp = inx
+            .read_milestone(starting_index.into())
+            .await?
+            .milestone_info
+            .milestone_timestamp
+            .into();
+
+        Ok(starting_index.with_timestamp(milestone_timestamp))
+    }
+
+    /// Populates the ledger from a genesis snapshot.
+    async fn insert_genesis_snapshot(&self, mut snapshot: GenesisSnapshot) -> Result<MilestoneIndexTimestamp> {
+        let mut count = 0;
+
+        loop {
+            // The snapshot is read from disk one batch at a time.
+            let batch = tokio::task::block_in_place(|| snapshot.next_batch(INSERT_BATCH_SIZE))?;
+            if batch.is_empty() {
+                break;
+            }
+            count += batch.len();
+            insert_unspent_outputs(&self.db, &batch).await?;
+        }
+
+        info!(
+            "Inserted {} unspent outputs from the genesis snapshot at milestone {}.",
+            count, snapshot.milestone_index
+        );
+
+        Ok(snapshot.at())
+    }
+
     #[instrument(skip_all, fields(milestone_index, created, consumed), err, level = "debug")]
     async fn handle_ledger_update<'a>(
         &mut self,
@@ -291,9 +432,15 @@ impl InxWorker {
             tasks.spawn(async move { insert_unspent_outputs(&db, &batch).await });
         }
 
+        // Spent outputs are only updated once the cone is read, since it contains the spending transactions.
+        let input_indexes = self.handle_cone_stream(&milestone).await?;
+
         for batch in milestone.ledger_updates().consumed_outputs().chunks(INSERT_BATCH_SIZE) {
             let db = self.db.clone();
-            let batch = batch.to_vec();
+            let mut batch = batch.to_vec();
+            for output in batch.iter_mut() {
+                output.spent_metadata.input_index = input_indexes.get(&output.output_id()).copied();
+            }
             tasks.spawn(async move { update_spent_outputs(&db, &batch).await });
         }
 
@@ -301,12 +448,16 @@ impl InxWorker {
             res??;
         }
 
+        self.db
+            .collection::<ApplicationStateCollection>()
+            .set_latest_ledger_update_index(milestone.at.milestone_index)
+            .await?;
+
         // Record the result as part of the current span.
         tracing::Span::current().record("milestone_index", milestone.at.milestone_index.0);
         tracing::Span::current().record("created", milestone.ledger_updates().created_outputs().len());
         tracing::Span::current().record("consumed", milestone.ledger_updates().consumed_outputs().len());
 
-        self.handle_cone_stream(&milestone).await?;
         self.db
             .collection::<ProtocolUpdateCollection>()
             .upsert_protocol_parameters(milestone.at.milestone_index, milestone.protocol_params.clone())
@@ -340,57 +491,122 @@ impl InxWorker {
         Ok(())
     }
 
+    /// Stores the blocks of the milestone cone, and returns the position of every output that an included transaction
+    /// of the cone spends among the inputs of that transaction.
     #[instrument(skip_all, err, level = "trace")]
-    async fn handle_cone_stream<'a>(&mut self, milestone: &Milestone<'a, Inx>) -> Result<()> {
-        let cone_stream = milestone.cone_stream().await?;
-
-        let mut tasks = cone_stream
+    async fn handle_cone_stream<'a>(&mut self, milestone: &Milestone<'a, Inx>) -> Result<HashMap<OutputId, u16>> {
+        let mut cone_stream = milestone
+            .cone_stream()
+            .await?
             .try_chunks(INSERT_BATCH_SIZE)
-            .map_err(|e| e.1)
-            .try_fold(JoinSet::new(), |mut tasks, batch| async {
-                let db = self.db.clone();
-                tasks.spawn(async move {
-                    let payloads = batch
-                        .iter()
-                        .filter_map(|data| {
-                            if data.metadata.inclusion_state == LedgerInclusionState::Included {
-                                if let Some(Payload::TreasuryTransaction(payload)) = &data.block.payload {
-                                    return Some((
-                                        data.metadata.referenced_by_milestone_index,
-                                        payload.input_milestone_id,
-                                        payload.output_amount,
-                                    ));
-                                }
+            .map_err(|e| e.1);
+
+        let mut tasks = JoinSet::new();
+        let mut input_indexes = HashMap::new();
+        // The serialized size of the blocks that are still being written, which approximates their memory usage.
+        let mut buffered = 0;
+        let mut budget_reached = false;
+        while let Some(batch) = cone_stream.try_next().await? {
+            for data in batch.iter() {
+                if data.metadata.inclusion_state == LedgerInclusionState::Included {
+                    if let Some(Payload::Transaction(payload)) = &data.block.payload {
+                        let TransactionEssence::Regular { inputs, .. } = &payload.essence;
+                        for (index, input) in inputs.iter().enumerate() {
+                            if let Input::Utxo(output_id) = input {
+                                input_indexes.insert(*output_id, index as u16);
                             }
-                            None
-                        })
-                        .collect::<Vec<_>>();
-                    if !payloads.is_empty() {
-                        db.collection::<TreasuryCollection>()
-                            .insert_treasury_payloads(payloads)
-                            .await?;
+                        }
                     }
-                    db.collection::<BlockCollection>()
-                        .insert_blocks_with_metadata(batch)
+                }
+            }
+            let batch_size = batch.iter().map(|data| data.raw.len()).sum::<usize>();
+            // Apply backpressure by waiting for writes to finish before reading more of the cone.
+            while buffered + batch_size > self.config.cone_memory_budget && !tasks.is_empty() {
+                if !budget_reached {
+                    budget_reached = true;
+                    warn!(
+                        "Cone of milestone {} exceeds the memory budget of {} bytes, waiting for writes.",
+                        milestone.at.milestone_index, self.config.cone_memory_budget
+                    );
+                }
+                // Unwrap: The set is not empty.
+                buffered -= tasks.join_next().await.unwrap()??;
+            }
+            buffered += batch_size;
+
+            let db = self.db.clone();
+            let store_unreferenced_blocks = self.config.store_unreferenced_blocks;
+            tasks.spawn(async move {
+                let payloads = batch
+                    .iter()
+                    .filter_map(|data| {
+                        if data.metadata.inclusion_state == LedgerInclusionState::Included {
+                            if let Some(Payload::TreasuryTransaction(payload)) = &data.block.payload {
+                                return Some((
+                                    data.metadata.referenced_by_milestone_index,
+                                    payload.input_milestone_id,
+                                    payload.output_amount,
+                                ));
+                            }
+                        }
+                        None
+                    })
+                    .collect::<Vec<_>>();
+                if !payloads.is_empty() {
+                    db.collection::<TreasuryCollection>()
+                        .insert_treasury_payloads(payloads)
                         .await?;
-                    Result::<_>::Ok(())
-                });
-                Ok(tasks)
-            })
-            .await?;
+                }
+                let block_ids =
+                    store_unreferenced_blocks.then(|| batch.iter().map(|data| data.block_id).collect::<Vec<_>>());
+                db.collection::<BlockCollection>()
+                    .insert_blocks_with_metadata(batch)
+                    .await?;
+                // The blocks are only removed once they are stored as referenced blocks.
+                if let Some(block_ids) = block_ids {
+                    db.collection::<UnreferencedBlockCollection>()
+                        .remove_blocks(&block_ids)
+                        .await?;
+                }
+                Result::<_>::Ok(batch_size)
+            });
+        }
 
         while let Some(res) = tasks.join_next().await {
             res??;
         }
 
-        Ok(())
+        Ok(input_indexes)
     }
 }
 
+/// Stores the blocks the node receives until the stream ends. Blocks arrive before any milestone references them, so
+/// they are removed again once the cone of the referencing milestone is stored.
+async fn store_unreferenced_blocks(db: MongoDb, mut inx: Inx) -> Result<()> {
+    let mut stream = inx.listen_to_blocks().await?.ready_chunks(INSERT_BATCH_SIZE);
+    debug!("Started listening to blocks via INX.");
+    while let Some(batch) = stream.next().await {
+        let mut blocks = Vec::with_capacity(batch.len());
+        for msg in batch {
+            let msg = msg?;
+            blocks.push((
+                msg.block_id,
+                msg.block.clone().inner_unverified()?.into(),
+                msg.block.data(),
+            ));
+        }
+        db.collection::<UnreferencedBlockCollection>()
+            .insert_blocks(blocks)
+            .await?;
+    }
+    Ok(())
+}
+
 #[instrument(skip_all, err, fields(num = outputs.len()), level = "trace")]
 async fn insert_unspent_outputs(db: &MongoDb, outputs: &[LedgerOutput]) -> Result<()> {
     let output_collection = db.collection::<OutputCollection>();
     let ledger_collection = db.collection::<LedgerUpdateCollection>();
+    let address_output_collection = db.collection::<AddressOutputCollection>();
     try_join! {
         async {
             output_collection.insert_unspent_outputs(outputs).await?;
@@ -399,6 +615,10 @@ async fn insert_unspent_outputs(db: &MongoDb, outputs: &[LedgerOutput]) -> Resul
         async {
             ledger_collection.insert_unspent_ledger_updates(outputs).await?;
             Ok(())
+        },
+        async {
+            address_output_collection.insert_unspent_outputs(outputs).await?;
+            Ok(())
         }
     }?;
     Ok(())
@@ -408,6 +628,7 @@ async fn insert_unspent_outputs(db: &MongoDb, outputs: &[LedgerOutput]) -> Resul
 async fn update_spent_outputs(db: &MongoDb, outputs: &[LedgerSpent]) -> Result<()> {
     let output_collection = db.collection::<OutputCollection>();
     let ledger_collection = db.collection::<LedgerUpdateCollection>();
+    let address_output_collection = db.collection::<AddressOutputCollection>();
     try_join! {
         async {
             output_collection.update_spent_outputs(outputs).await?;
@@ -416,6 +637,10 @@ async fn update_spent_outputs(db: &MongoDb, outputs: &[LedgerSpent]) -> Result<(
         async {
             ledger_collection.insert_spent_ledger_updates(outputs).await?;
             Ok(())
+        },
+        async {
+            address_output_collection.update_spent_outputs(outputs).await?;
+            Ok(())
         }
     }
     .and(Ok(()))
@@ -0,0 +1,83 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs::File, io::BufReader, path::Path};
+
+use chronicle::model::{
+    ledger::LedgerOutput,
+    tangle::{MilestoneIndex, MilestoneIndexTimestamp, MilestoneTimestamp},
+};
+use mongodb::bson::{self, Bson};
+use serde::Deserialize;
+use serde_json::de::{IoRead, StreamDeserializer};
+
+/// The leading document of a snapshot file.
+#[derive(Deserialize)]
+struct SnapshotHeader {
+    milestone_index: MilestoneIndex,
+    milestone_timestamp: MilestoneTimestamp,
+}
+
+/// A snapshot of the unspent outputs at a given milestone, used to bootstrap the ledger of a fresh database.
+///
+/// The file is a sequence of JSON documents. The first one contains the milestone index and timestamp, and every
+/// following one is an unspent output at that milestone in the same representation that is stored in the database,
+/// written as MongoDB extended JSON. Outputs are read lazily in batches, so the snapshot never has to fit in memory.
+pub struct GenesisSnapshot {
+    pub milestone_index: MilestoneIndex,
+    pub milestone_timestamp: MilestoneTimestamp,
+    outputs: StreamDeserializer<'static, IoRead<BufReader<File>>, Bson>,
+}
+
+impl GenesisSnapshot {
+    /// Opens a snapshot file and reads its header.
+    pub fn from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let mut de = serde_json::Deserializer::from_reader(BufReader::new(File::open(path)?));
+        let SnapshotHeader {
+            milestone_index,
+            milestone_timestamp,
+        } = SnapshotHeader::deserialize(&mut de)?;
+        Ok(Self {
+            milestone_index,
+            milestone_timestamp,
+            outputs: de.into_iter(),
+        })
+    }
+
+    /// Reads up to `batch_size` outputs from the snapshot. An empty batch means that all outputs have been read.
+    pub fn next_batch(&mut self, batch_size: usize) -> eyre::Result<Vec<LedgerOutput>> {
+        self.outputs
+            .by_ref()
+            .take(batch_size)
+            .map(|output| Ok(bson::from_bson(output?)?))
+            .collect()
+    }
+
+    /// The milestone at which the snapshot was taken.
+    pub fn at(&self) -> MilestoneIndexTimestamp {
+        self.milestone_index.with_timestamp(self.milestone_timestamp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_snapshot_in_batches() {
+        let mut snapshot = GenesisSnapshot::from_file("tests/data/genesis_snapshot.json").unwrap();
+        assert_eq!(snapshot.milestone_index, 100);
+        assert_eq!(snapshot.milestone_timestamp, 1672531200.into());
+
+        let batch = snapshot.next_batch(2).unwrap();
+        assert_eq!(
+            batch.iter().map(|output| output.amount().0).collect::<Vec<_>>(),
+            vec![1_000_000, 2_000_000]
+        );
+        let batch = snapshot.next_batch(2).unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].amount().0, 3_000_000);
+        assert!(batch.iter().all(|output| output.rent_structure.num_data_bytes == 100));
+        assert!(snapshot.next_batch(2).unwrap().is_empty());
+    }
+}
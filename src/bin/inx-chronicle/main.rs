@@ -57,40 +57,60 @@ async fn main() -> eyre::Result<()> {
     let (shutdown_signal, _) = tokio::sync::broadcast::channel::<()>(1);
 
     #[cfg(feature = "inx")]
-    if config.inx.enabled {
-        #[cfg(feature = "influx")]
-        #[allow(unused_mut)]
-        let mut influx_required = false;
+    #[allow(unused_variables)]
+    let inx_enabled = config.inx.enabled;
+    #[cfg(not(feature = "inx"))]
+    #[allow(unused_variables)]
+    let inx_enabled = false;
+
+    #[cfg(feature = "influx")]
+    #[allow(unused_mut)]
+    let mut influx_required = false;
+    #[cfg(feature = "analytics")]
+    {
+        // Analytics are written by the INX worker, and recomputed by jobs that are started through the API.
+        #[cfg(feature = "api")]
+        let api_enabled = config.api.enabled;
+        #[cfg(not(feature = "api"))]
+        let api_enabled = false;
+        influx_required |= config.influxdb.analytics_enabled && (inx_enabled || api_enabled);
+    }
+    #[cfg(feature = "metrics")]
+    {
+        // Metrics are only written by the INX worker.
+        influx_required |= config.influxdb.metrics_enabled && inx_enabled;
+    }
+
+    #[cfg(feature = "influx")]
+    #[allow(unused_variables)]
+    let influx_db = if influx_required {
+        info!("Connecting to influx at `{}`", config.influxdb.url);
+        let influx_db = chronicle::db::influxdb::InfluxDb::connect(&config.influxdb).await?;
         #[cfg(feature = "analytics")]
-        {
-            influx_required |= config.influxdb.analytics_enabled;
-        }
+        info!(
+            "Connected to influx database `{}`",
+            influx_db.analytics().database_name()
+        );
         #[cfg(feature = "metrics")]
-        {
-            influx_required |= config.influxdb.metrics_enabled;
-        }
+        info!("Connected to influx database `{}`", influx_db.metrics().database_name());
+        Some(influx_db)
+    } else {
+        None
+    };
 
-        #[cfg(feature = "influx")]
-        let influx_db = if influx_required {
-            info!("Connecting to influx at `{}`", config.influxdb.url);
-            let influx_db = chronicle::db::influxdb::InfluxDb::connect(&config.influxdb).await?;
-            #[cfg(feature = "analytics")]
-            info!(
-                "Connected to influx database `{}`",
-                influx_db.analytics().database_name()
-            );
-            #[cfg(feature = "metrics")]
-            info!("Connected to influx database `{}`", influx_db.metrics().database_name());
-            Some(influx_db)
-        } else {
-            None
-        };
+    #[cfg(feature = "analytics")]
+    #[allow(unused_variables)]
+    let shared_analytics = cli::analytics::SharedAnalytics::default();
 
+    #[cfg(feature = "inx")]
+    if config.inx.enabled {
         let mut worker = inx::InxWorker::new(db.clone(), config.inx.clone());
         #[cfg(feature = "influx")]
         if let Some(influx_db) = &influx_db {
             worker.set_influx_db(influx_db);
         }
+        #[cfg(feature = "analytics")]
+        worker.set_shared_analytics(&shared_analytics);
 
         let mut handle = shutdown_signal.subscribe();
         tasks.spawn(async move {
@@ -105,7 +125,14 @@ async fn main() -> eyre::Result<()> {
     #[cfg(feature = "api")]
     if config.api.enabled {
         use futures::FutureExt;
-        let worker = api::ApiWorker::new(db.clone(), config.api.clone())?;
+        #[allow(unused_mut)]
+        let mut worker = api::ApiWorker::new(db.clone(), config.api.clone())?;
+        #[cfg(feature = "analytics")]
+        if let Some(influx_db) = influx_db.as_ref().filter(|_| config.influxdb.analytics_enabled) {
+            worker.set_influx_db(influx_db);
+        }
+        #[cfg(feature = "analytics")]
+        worker.set_shared_analytics(&shared_analytics);
         let mut handle = shutdown_signal.subscribe();
         tasks.spawn(async move {
             worker.run(handle.recv().then(|_| async {})).await?;
@@ -173,6 +200,7 @@ async fn build_indexes(db: &MongoDb) -> eyre::Result<()> {
     db.create_indexes::<collections::OutputCollection>().await?;
     db.create_indexes::<collections::BlockCollection>().await?;
     db.create_indexes::<collections::LedgerUpdateCollection>().await?;
+    db.create_indexes::<collections::AddressOutputCollection>().await?;
     db.create_indexes::<collections::MilestoneCollection>().await?;
     let end_indexes = db.get_index_names().await?;
     for (collection, indexes) in end_indexes {
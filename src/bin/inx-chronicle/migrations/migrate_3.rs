@@ -0,0 +1,26 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use chronicle::db::{mongodb::collections::AddressOutputCollection, MongoDb, MongoDbCollection};
+use tracing::info;
+
+use super::Migration;
+
+pub struct Migrate;
+
+#[async_trait]
+impl Migration for Migrate {
+    const ID: usize = 3;
+    const APP_VERSION: &'static str = "1.0.0-rc.1";
+    const DATE: time::Date = time::macros::date!(2023 - 05 - 02);
+
+    async fn migrate(db: &MongoDb) -> eyre::Result<()> {
+        info!("Backfilling the {} collection.", AddressOutputCollection::NAME);
+        db.collection::<AddressOutputCollection>()
+            .backfill_from_outputs()
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use chronicle::db::{
+    mongodb::{collections::OutputCollection, VersionedCollectionExt},
+    MongoDb,
+};
+use tracing::info;
+
+use super::Migration;
+
+/// The number of documents that are upgraded with a single update.
+const BATCH_SIZE: usize = 10_000;
+
+pub struct Migrate;
+
+#[async_trait]
+impl Migration for Migrate {
+    const ID: usize = 4;
+    const APP_VERSION: &'static str = "1.0.0-rc.1";
+    const DATE: time::Date = time::macros::date!(2023 - 05 - 09);
+
+    async fn migrate(db: &MongoDb) -> eyre::Result<()> {
+        let count = db
+            .collection::<OutputCollection>()
+            .upgrade_documents(BATCH_SIZE)
+            .await?;
+        info!("Upgraded {count} output documents.");
+
+        Ok(())
+    }
+}
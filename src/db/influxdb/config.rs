@@ -4,6 +4,8 @@
 //! Holds the `InfluxDb` config and its defaults.
 
 use std::collections::HashSet;
+#[cfg(feature = "analytics")]
+use std::{collections::HashMap, num::NonZeroU32};
 
 /// The default InfluxDb URL to connect to.
 pub const DEFAULT_URL: &str = "http://localhost:8086";
@@ -17,6 +19,18 @@ pub const DEFAULT_ANALYTICS_ENABLED: bool = true;
 /// The default name of the analytics database to connect to.
 #[cfg(feature = "analytics")]
 pub const DEFAULT_ANALYTICS_DATABASE_NAME: &str = "chronicle_analytics";
+/// The default format of amount fields in analytics measurements.
+#[cfg(feature = "analytics")]
+pub const DEFAULT_AMOUNT_FORMAT: AmountFormat = AmountFormat::Raw;
+/// The default resolution of the timestamps of analytics measurements.
+#[cfg(feature = "analytics")]
+pub const DEFAULT_TIMESTAMP_RESOLUTION: TimestampResolution = TimestampResolution::Exact;
+/// The default number of decimals of the base token, used to scale amount fields.
+#[cfg(feature = "analytics")]
+pub const DEFAULT_BASE_TOKEN_DECIMALS: u32 = 6;
+/// The default destination of analytics measurements.
+#[cfg(feature = "analytics")]
+pub const DEFAULT_ANALYTICS_SINK: &str = "influxdb";
 /// The default whether to enable influx metrics writes.
 #[cfg(feature = "metrics")]
 pub const DEFAULT_METRICS_ENABLED: bool = true;
@@ -40,9 +54,37 @@ pub struct InfluxDbConfig {
     /// The name of the database to insert analytics.
     #[cfg(feature = "analytics")]
     pub analytics_database_name: String,
+    /// The name of the database to insert ledger analytics, if it differs from the analytics database.
+    #[cfg(feature = "analytics")]
+    pub ledger_analytics_database_name: Option<String>,
+    /// The name of the database to insert tangle analytics, if it differs from the analytics database.
+    #[cfg(feature = "analytics")]
+    pub tangle_analytics_database_name: Option<String>,
     /// The selected analytics to compute.
     #[cfg(feature = "analytics")]
     pub analytics: Vec<AnalyticsChoice>,
+    /// Analytics that are only computed every Nth milestone. Their series only contain points for milestones whose
+    /// index is a multiple of N, so they are sparser than those of other analytics. Analytics that report the ledger
+    /// state report it as of the sampled milestone. Only analytics that
+    /// [support sampling](AnalyticsChoice::supports_sampling) may be sampled.
+    #[cfg(feature = "analytics")]
+    pub analytics_sampling: HashMap<AnalyticsChoice, NonZeroU32>,
+    /// The format of amount fields in analytics measurements.
+    #[cfg(feature = "analytics")]
+    pub amount_format: AmountFormat,
+    /// The number of decimals of the base token, used to scale amount fields.
+    #[cfg(feature = "analytics")]
+    pub base_token_decimals: u32,
+    /// The resolution that the timestamps of interval analytics measurements are snapped to.
+    #[cfg(feature = "analytics")]
+    pub timestamp_resolution: TimestampResolution,
+    /// The maximum number of milestones that analytics are allowed to lag behind the node on startup. Older
+    /// milestones are skipped, which leaves a gap in the analytics series.
+    #[cfg(feature = "analytics")]
+    pub max_analytics_backlog: Option<u32>,
+    /// The destinations that analytics measurements are written to. Every measurement is written to all of them.
+    #[cfg(feature = "analytics")]
+    pub analytics_sinks: Vec<AnalyticsSinkConfig>,
     /// Whether to enable influx metrics writes.
     #[cfg(feature = "metrics")]
     pub metrics_enabled: bool,
@@ -62,7 +104,23 @@ impl Default for InfluxDbConfig {
             #[cfg(feature = "analytics")]
             analytics_database_name: DEFAULT_ANALYTICS_DATABASE_NAME.to_string(),
             #[cfg(feature = "analytics")]
+            ledger_analytics_database_name: None,
+            #[cfg(feature = "analytics")]
+            tangle_analytics_database_name: None,
+            #[cfg(feature = "analytics")]
             analytics: Vec::new(),
+            #[cfg(feature = "analytics")]
+            analytics_sampling: HashMap::new(),
+            #[cfg(feature = "analytics")]
+            amount_format: DEFAULT_AMOUNT_FORMAT,
+            #[cfg(feature = "analytics")]
+            base_token_decimals: DEFAULT_BASE_TOKEN_DECIMALS,
+            #[cfg(feature = "analytics")]
+            timestamp_resolution: DEFAULT_TIMESTAMP_RESOLUTION,
+            #[cfg(feature = "analytics")]
+            max_analytics_backlog: None,
+            #[cfg(feature = "analytics")]
+            analytics_sinks: vec![AnalyticsSinkConfig::InfluxDb],
             #[cfg(feature = "metrics")]
             metrics_enabled: DEFAULT_METRICS_ENABLED,
             #[cfg(feature = "metrics")]
@@ -71,6 +129,114 @@ impl Default for InfluxDbConfig {
     }
 }
 
+#[cfg(feature = "analytics")]
+impl InfluxDbConfig {
+    /// Gets the name of the database to insert analytics of the given category.
+    pub fn analytics_database_name_for(&self, category: AnalyticsCategory) -> &str {
+        match category {
+            AnalyticsCategory::Ledger => self.ledger_analytics_database_name.as_deref(),
+            AnalyticsCategory::Tangle => self.tangle_analytics_database_name.as_deref(),
+        }
+        .unwrap_or(&self.analytics_database_name)
+    }
+}
+
+/// A destination that analytics measurements are written to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnalyticsSinkConfig {
+    /// The InfluxDb analytics databases.
+    InfluxDb,
+    /// A file that measurements are appended to in the InfluxDb line protocol, with timestamps in seconds.
+    File(String),
+}
+
+impl std::str::FromStr for AnalyticsSinkConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s.eq_ignore_ascii_case("influxdb") => Ok(Self::InfluxDb),
+            Some((kind, path)) if kind.eq_ignore_ascii_case("file") && !path.is_empty() => {
+                Ok(Self::File(path.to_string()))
+            }
+            _ => Err(format!("expected `influxdb` or `file:PATH`, found `{s}`")),
+        }
+    }
+}
+
+/// The category of an analytic, which determines the database its measurements are written to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsCategory {
+    /// Analytics of the ledger state, like balances and outputs.
+    Ledger,
+    /// Analytics of the tangle, like blocks, milestones and protocol parameters.
+    Tangle,
+}
+
+/// The format in which amount fields are written to InfluxDb.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum AmountFormat {
+    /// Amounts are written as integers in the smallest unit of the base token.
+    Raw,
+    /// Amounts are written as floats scaled to whole tokens.
+    ///
+    /// Floats only represent integers up to 2^53 exactly, so large amounts lose precision in the lowest digits.
+    Scaled,
+}
+
+/// The resolution that the timestamps of interval analytics measurements are snapped to.
+///
+/// Snapping aligns the points of a series to period boundaries, which reduces jitter in charts. Measurements that are
+/// snapped to the same timestamp overwrite each other, so the resolution must not be coarser than the interval.
+/// Per-milestone measurements are always timestamped with the exact milestone timestamp, since snapping them would
+/// only retain the last milestone of every period.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimestampResolution {
+    /// Measurements are timestamped with the exact milestone timestamp.
+    Exact,
+    /// Timestamps are snapped to the start of the minute.
+    Minute,
+    /// Timestamps are snapped to the start of the hour.
+    Hour,
+    /// Timestamps are snapped to the start of the day (UTC).
+    Day,
+    /// Timestamps are snapped to the start of the week (Monday, UTC).
+    Week,
+}
+
+impl TimestampResolution {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * Self::MINUTE;
+    const DAY: u64 = 24 * Self::HOUR;
+    const WEEK: u64 = 7 * Self::DAY;
+
+    /// The length of a period in seconds, which is zero for exact timestamps.
+    pub fn period(self) -> u64 {
+        match self {
+            Self::Exact => 0,
+            Self::Minute => Self::MINUTE,
+            Self::Hour => Self::HOUR,
+            Self::Day => Self::DAY,
+            Self::Week => Self::WEEK,
+        }
+    }
+
+    /// Snaps a unix timestamp in seconds down to the start of its period.
+    pub fn snap(self, timestamp: u64) -> u64 {
+        // The unix epoch was a Thursday, so weeks that start on Monday are offset by three days.
+        const WEEK_OFFSET: u64 = 3 * TimestampResolution::DAY;
+        match self {
+            Self::Exact => timestamp,
+            Self::Week => {
+                let shifted = timestamp + WEEK_OFFSET;
+                (shifted - shifted % Self::WEEK).saturating_sub(WEEK_OFFSET)
+            }
+            _ => timestamp - timestamp % self.period(),
+        }
+    }
+}
+
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, clap::ValueEnum)]
 pub enum AnalyticsChoice {
@@ -79,16 +245,55 @@ pub enum AnalyticsChoice {
     BaseTokenActivity,
     BlockActivity,
     ActiveAddresses,
+    /// The number of active addresses per kind of output they owned.
+    ActiveAddressesByKind,
+    /// The amounts of spent outputs weighted by the number of days they were held.
+    CoinDaysDestroyed,
+    /// The number of unspent outputs that hold less than their required storage deposit. Any violation points to an
+    /// indexing bug, since the protocol rejects such outputs. Not enabled by default, since it computes the storage
+    /// deposit of every output.
+    DepositViolations,
+    FeatureUsage,
+    /// The distribution of the number of unspent foundries controlled by an alias.
+    FoundryPerAlias,
+    HoldingTime,
     LedgerOutputs,
     LedgerSize,
+    /// An approximation of the median transaction value, estimated with a logarithmic histogram whose estimates are
+    /// within ~4.4% of the true median.
+    MedianTransactionValue,
     MilestoneSize,
+    /// Running totals of [`AnalyticsChoice::MilestoneSize`] across all processed milestones. Not enabled by default,
+    /// since the totals are seeded from the point written for the previous milestone and therefore require milestones
+    /// to be processed in order.
+    MilestoneSizeTotals,
     OutputActivity,
+    OutputsPerAddress,
+    /// The average number of outputs created by a transaction.
+    OutputsPerTransaction,
+    /// The number of unspent outputs per power of ten of their amount.
+    OutputValueHistogram,
+    /// The number of outputs consumed in a milestone relative to the number of unspent outputs at its start.
+    OutputVelocity,
     ProtocolParameters,
+    SupplyLockState,
     TransactionSizeDistribution,
     UnclaimedTokens,
     UnlockConditions,
 }
 
+impl AnalyticsChoice {
+    /// Whether the analytic can be computed only every Nth milestone.
+    ///
+    /// Most analytics either measure each milestone on its own or report the ledger state at the sampled milestone.
+    /// [`AnalyticsChoice::OutputVelocity`] relates the outputs consumed since its last measurement to the ledger at
+    /// that measurement, and [`AnalyticsChoice::MilestoneSizeTotals`] is seeded from the point of the previous
+    /// milestone, so skipped milestones would corrupt both.
+    pub fn supports_sampling(self) -> bool {
+        !matches!(self, Self::MilestoneSizeTotals | Self::OutputVelocity)
+    }
+}
+
 /// Returns a list of trait objects for all analytics.
 pub fn all_analytics() -> HashSet<AnalyticsChoice> {
     // Please keep the alphabetic order.
@@ -97,11 +302,22 @@ pub fn all_analytics() -> HashSet<AnalyticsChoice> {
         AnalyticsChoice::BaseTokenActivity,
         AnalyticsChoice::BlockActivity,
         AnalyticsChoice::ActiveAddresses,
+        AnalyticsChoice::ActiveAddressesByKind,
+        AnalyticsChoice::CoinDaysDestroyed,
+        AnalyticsChoice::FeatureUsage,
+        AnalyticsChoice::FoundryPerAlias,
+        AnalyticsChoice::HoldingTime,
         AnalyticsChoice::LedgerOutputs,
         AnalyticsChoice::LedgerSize,
+        AnalyticsChoice::MedianTransactionValue,
         AnalyticsChoice::MilestoneSize,
         AnalyticsChoice::OutputActivity,
+        AnalyticsChoice::OutputsPerAddress,
+        AnalyticsChoice::OutputsPerTransaction,
+        AnalyticsChoice::OutputValueHistogram,
+        AnalyticsChoice::OutputVelocity,
         AnalyticsChoice::ProtocolParameters,
+        AnalyticsChoice::SupplyLockState,
         AnalyticsChoice::TransactionSizeDistribution,
         AnalyticsChoice::UnclaimedTokens,
         AnalyticsChoice::UnlockConditions,
@@ -4,13 +4,17 @@
 pub mod config;
 mod measurement;
 
+#[cfg(feature = "analytics")]
+use std::collections::HashMap;
 use std::ops::Deref;
 
 use influxdb::{Client, ReadQuery};
 use serde::de::DeserializeOwned;
 
 pub use self::{
-    config::{AnalyticsChoice, InfluxDbConfig},
+    config::{
+        AmountFormat, AnalyticsCategory, AnalyticsChoice, AnalyticsSinkConfig, InfluxDbConfig, TimestampResolution,
+    },
     measurement::InfluxDbMeasurement,
 };
 
@@ -54,6 +58,8 @@ impl Deref for InfluxClient {
 pub struct InfluxDb {
     #[cfg(feature = "analytics")]
     analytics_client: InfluxClient,
+    #[cfg(feature = "analytics")]
+    analytics_category_clients: HashMap<AnalyticsCategory, InfluxClient>,
     #[cfg(feature = "metrics")]
     metrics_client: InfluxClient,
     config: InfluxDbConfig,
@@ -61,21 +67,42 @@ pub struct InfluxDb {
 
 impl InfluxDb {
     /// Create a new influx connection from config.
+    ///
+    /// Only the databases of enabled writes are checked to be reachable, so that a deployment that disables them does
+    /// not need an InfluxDb instance.
     pub async fn connect(config: &InfluxDbConfig) -> Result<Self, influxdb::Error> {
         #[cfg(feature = "analytics")]
         let analytics_client = {
             let client = InfluxClient(
                 Client::new(&config.url, &config.analytics_database_name).with_auth(&config.username, &config.password),
             );
-            client.ping().await?;
+            if config.analytics_enabled {
+                client.ping().await?;
+            }
             client
         };
+        #[cfg(feature = "analytics")]
+        let mut analytics_category_clients = HashMap::new();
+        #[cfg(feature = "analytics")]
+        for category in [AnalyticsCategory::Ledger, AnalyticsCategory::Tangle] {
+            let database_name = config.analytics_database_name_for(category);
+            if database_name != config.analytics_database_name {
+                let client =
+                    InfluxClient(Client::new(&config.url, database_name).with_auth(&config.username, &config.password));
+                if config.analytics_enabled {
+                    client.ping().await?;
+                }
+                analytics_category_clients.insert(category, client);
+            }
+        }
         #[cfg(feature = "metrics")]
         let metrics_client = {
             let client = InfluxClient(
                 Client::new(&config.url, &config.metrics_database_name).with_auth(&config.username, &config.password),
             );
-            client.ping().await?;
+            if config.metrics_enabled {
+                client.ping().await?;
+            }
             client
         };
         Ok(Self {
@@ -83,6 +110,8 @@ impl InfluxDb {
             metrics_client,
             #[cfg(feature = "analytics")]
             analytics_client,
+            #[cfg(feature = "analytics")]
+            analytics_category_clients,
             config: config.clone(),
         })
     }
@@ -93,6 +122,42 @@ impl InfluxDb {
         &self.analytics_client
     }
 
+    /// Get the analytics client for the given category, which falls back to the default analytics client.
+    #[cfg(feature = "analytics")]
+    pub fn analytics_for(&self, category: AnalyticsCategory) -> &InfluxClient {
+        self.analytics_category_clients
+            .get(&category)
+            .unwrap_or(&self.analytics_client)
+    }
+
+    /// Gets the timestamp of the most recent point written to the given analytics measurement, if any.
+    #[cfg(feature = "analytics")]
+    pub async fn get_latest_analytics_timestamp(
+        &self,
+        measurement: &str,
+    ) -> Result<Option<crate::model::tangle::MilestoneTimestamp>, influxdb::Error> {
+        #[derive(serde::Deserialize)]
+        struct Res {
+            time: String,
+        }
+
+        let mut latest = None;
+        // The measurement may live in any of the analytics databases, so check all of them.
+        for client in std::iter::once(&self.analytics_client).chain(self.analytics_category_clients.values()) {
+            let query = ReadQuery::new(format!(
+                "SELECT last(\"milestone_index\") FROM {}",
+                quote_identifier(measurement)
+            ));
+            for Res { time } in client.select::<Res>(query).await? {
+                let timestamp = time::OffsetDateTime::parse(&time, &time::format_description::well_known::Rfc3339)
+                    .map_err(|e| influxdb::Error::DeserializationError { error: e.to_string() })?
+                    .unix_timestamp() as u32;
+                latest = latest.max(Some(timestamp.into()));
+            }
+        }
+        Ok(latest)
+    }
+
     /// Get the metrics client.
     #[cfg(feature = "metrics")]
     pub fn metrics(&self) -> &InfluxClient {
@@ -104,3 +169,20 @@ impl InfluxDb {
         &self.config
     }
 }
+
+/// Quotes a name as an InfluxQL identifier, so that it can be used in a query regardless of the characters it contains.
+#[cfg(feature = "analytics")]
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(all(test, feature = "analytics"))]
+mod test {
+    use super::quote_identifier;
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("stardust_ledger_size"), r#""stardust_ledger_size""#);
+        assert_eq!(quote_identifier(r#"a" OR "b\"#), r#""a\" OR \"b\\""#);
+    }
+}
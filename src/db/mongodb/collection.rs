@@ -4,13 +4,13 @@
 use std::borrow::Borrow;
 
 use async_trait::async_trait;
-use futures::{Stream, StreamExt};
+use futures::{Stream, StreamExt, TryStreamExt};
 use mongodb::{
-    bson::{self, doc, Document},
+    bson::{self, doc, Bson, Document},
     error::{Error, ErrorKind},
     options::{
-        AggregateOptions, CreateIndexOptions, DropIndexOptions, FindOneOptions, FindOptions, InsertManyOptions,
-        InsertOneOptions, ReplaceOptions, UpdateModifications, UpdateOptions,
+        AggregateOptions, CreateIndexOptions, DropIndexOptions, FindOneOptions, FindOptions, IndexOptions,
+        InsertManyOptions, InsertOneOptions, ReplaceOptions, UpdateModifications, UpdateOptions,
     },
     results::{CreateIndexResult, InsertManyResult, InsertOneResult, UpdateResult},
     Cursor, IndexModel,
@@ -180,6 +180,80 @@ pub trait MongoDbCollectionExt: MongoDbCollection {
 }
 impl<T: MongoDbCollection> MongoDbCollectionExt for T {}
 
+/// The name of the field that holds the schema version of a document.
+pub const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// A collection whose documents carry a schema version.
+///
+/// Documents written before versioning was introduced have no `schema_version` field and are treated as version `0`.
+pub trait VersionedCollection: MongoDbCollection {
+    /// The schema version of newly written documents.
+    const SCHEMA_VERSION: u32;
+
+    /// Gets the update pipeline stages that upgrade a document from the given schema version to the next one. The
+    /// stage updating the `schema_version` field is added automatically.
+    fn upgrade_stages(from_version: u32) -> Vec<Document>;
+}
+
+/// An extension trait which upgrades the documents of a [`VersionedCollection`] to its current schema version.
+#[async_trait]
+pub trait VersionedCollectionExt: VersionedCollection + MongoDbCollectionExt {
+    /// Creates the index used to find the documents with an older schema version, so that checking for outdated
+    /// documents does not scan the whole collection.
+    async fn create_schema_version_index(&self) -> Result<(), Error> {
+        self.create_index(
+            IndexModel::builder()
+                .keys(doc! { SCHEMA_VERSION_FIELD: 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name(format!("{SCHEMA_VERSION_FIELD}_index"))
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Upgrades all documents with an older schema version, one version step at a time and in batches of the given
+    /// size. Returns the number of modified documents, counting a document once per upgrade step.
+    async fn upgrade_documents(&self, batch_size: usize) -> Result<usize, Error> {
+        let mut upgraded = 0;
+        for from_version in 0..Self::SCHEMA_VERSION {
+            let filter = if from_version == 0 {
+                doc! { SCHEMA_VERSION_FIELD: { "$in": [ Bson::Null, 0 ] } }
+            } else {
+                doc! { SCHEMA_VERSION_FIELD: from_version }
+            };
+            let mut pipeline = Self::upgrade_stages(from_version);
+            pipeline.push(doc! { "$set": { SCHEMA_VERSION_FIELD: from_version + 1 } });
+            loop {
+                let ids = self
+                    .find::<Document>(
+                        filter.clone(),
+                        FindOptions::builder()
+                            .projection(doc! { "_id": 1 })
+                            .limit(batch_size as i64)
+                            .build(),
+                    )
+                    .await?
+                    .try_filter_map(|doc| async move { Ok(doc.get("_id").cloned()) })
+                    .try_collect::<Vec<_>>()
+                    .await?;
+                if ids.is_empty() {
+                    break;
+                }
+                let mut query = filter.clone();
+                query.insert("_id", doc! { "$in": ids });
+                upgraded += self.update_many(query, pipeline.clone(), None).await?.modified_count as usize;
+            }
+        }
+        Ok(upgraded)
+    }
+}
+impl<T: VersionedCollection + MongoDbCollectionExt + Sync> VersionedCollectionExt for T {}
+
 pub struct InsertResult {
     _ignored: usize,
 }
@@ -0,0 +1,380 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::{Stream, TryStreamExt};
+use mongodb::{
+    bson::{doc, to_bson, to_document, Document},
+    error::Error,
+    options::{IndexOptions, InsertManyOptions},
+    IndexModel,
+};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::{
+    outputs::{AddressBalanceResult, AddressSummaryResult, BalanceResult, SpentOutputResult},
+    OutputCollection, SortOrder,
+};
+use crate::{
+    db::{
+        mongodb::{InsertIgnoreDuplicatesExt, MongoDbCollection, MongoDbCollectionExt},
+        MongoDb,
+    },
+    model::{
+        ledger::{LedgerOutput, LedgerSpent},
+        tangle::MilestoneIndex,
+        utxo::{Address, OutputId, TokenAmount},
+    },
+};
+
+/// An output owned by an address, denormalized from the outputs collection so that the outputs of an address can be
+/// found without scanning full output documents.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AddressOutputDocument {
+    #[serde(rename = "_id")]
+    output_id: OutputId,
+    address: Address,
+    kind: String,
+    amount: TokenAmount,
+    is_trivial_unlock: bool,
+    booked: MilestoneIndex,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spent: Option<MilestoneIndex>,
+}
+
+impl AddressOutputDocument {
+    fn from_output(rec: &LedgerOutput) -> Option<Self> {
+        rec.output.owning_address().map(|&address| Self {
+            output_id: rec.output_id,
+            address,
+            kind: rec.output.kind().to_string(),
+            amount: rec.output.amount(),
+            is_trivial_unlock: rec.output.is_trivial_unlock(),
+            booked: rec.booked.milestone_index,
+            spent: None,
+        })
+    }
+
+    fn from_spent(rec: &LedgerSpent) -> Option<Self> {
+        Self::from_output(&rec.output).map(|mut doc| {
+            doc.spent.replace(rec.spent_metadata.spent.milestone_index);
+            doc
+        })
+    }
+}
+
+/// The stardust address outputs collection, which maps owning addresses to their output ids.
+pub struct AddressOutputCollection {
+    db: mongodb::Database,
+    collection: mongodb::Collection<AddressOutputDocument>,
+}
+
+#[async_trait::async_trait]
+impl MongoDbCollection for AddressOutputCollection {
+    const NAME: &'static str = "stardust_address_outputs";
+    type Document = AddressOutputDocument;
+
+    fn instantiate(db: &MongoDb, collection: mongodb::Collection<Self::Document>) -> Self {
+        Self {
+            db: db.db(),
+            collection,
+        }
+    }
+
+    fn collection(&self) -> &mongodb::Collection<Self::Document> {
+        &self.collection
+    }
+
+    async fn create_indexes(&self) -> Result<(), Error> {
+        self.create_index(
+            IndexModel::builder()
+                .keys(doc! { "address": 1, "booked": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(false)
+                        .name("address_output_index".to_string())
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await?;
+
+        self.create_index(
+            IndexModel::builder()
+                .keys(doc! { "address": 1, "spent": 1, "_id": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("address_spent_output_index".to_string())
+                        .partial_filter_expression(doc! { "spent": { "$exists": true } })
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl AddressOutputCollection {
+    /// Inserts the unspent outputs that are owned by an address.
+    #[instrument(skip_all, err, level = "trace")]
+    pub async fn insert_unspent_outputs<'a, I>(&self, outputs: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = &'a LedgerOutput>,
+        I::IntoIter: Send + Sync,
+    {
+        self.insert_many_ignore_duplicates(
+            outputs.into_iter().filter_map(AddressOutputDocument::from_output),
+            InsertManyOptions::builder().ordered(false).build(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Upserts the spent outputs that are owned by an address.
+    #[instrument(skip_all, err, level = "trace")]
+    pub async fn update_spent_outputs(&self, outputs: impl IntoIterator<Item = &LedgerSpent>) -> Result<(), Error> {
+        // TODO: Replace `db.run_command` once the `BulkWrite` API lands in the Rust driver.
+        let update_docs = outputs
+            .into_iter()
+            .filter_map(AddressOutputDocument::from_spent)
+            .map(|doc| {
+                Ok(doc! {
+                    "q": { "_id": doc.output_id },
+                    "u": to_document(&doc)?,
+                    "upsert": true,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        if !update_docs.is_empty() {
+            let mut command = doc! {
+                "update": Self::NAME,
+                "updates": update_docs,
+            };
+            if let Some(ref write_concern) = self.db.write_concern() {
+                command.insert("writeConcern", to_bson(write_concern)?);
+            }
+            let selection_criteria = self.db.selection_criteria().cloned();
+            let _ = self.db.run_command(command, selection_criteria).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Fills the collection from the outputs collection, replacing the documents that already exist.
+    pub async fn backfill_from_outputs(&self) -> Result<(), Error> {
+        self.db
+            .collection::<Document>(OutputCollection::NAME)
+            .aggregate(
+                [
+                    doc! { "$match": { "details.address": { "$exists": true } } },
+                    doc! { "$project": {
+                        "address": "$details.address",
+                        "kind": "$output.kind",
+                        "amount": "$output.amount",
+                        "is_trivial_unlock": "$details.is_trivial_unlock",
+                        "booked": "$metadata.booked.milestone_index",
+                        "spent": "$metadata.spent_metadata.spent.milestone_index",
+                    } },
+                    doc! { "$merge": { "into": Self::NAME, "whenMatched": "replace" } },
+                ],
+                None,
+            )
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Sums the amounts of all outputs owned by the given [`Address`](crate::model::utxo::Address). If a `limit` is
+    /// given, at most that many outputs are summed.
+    pub async fn get_address_balance(
+        &self,
+        address: Address,
+        ledger_index: MilestoneIndex,
+        limit: Option<usize>,
+    ) -> Result<Option<BalanceResult>, Error> {
+        let mut pipeline = vec![doc! { "$match": {
+            "address": &address,
+            "booked": { "$lte": ledger_index },
+            "spent": { "$not": { "$lte": ledger_index } }
+        } }];
+        if let Some(limit) = limit {
+            pipeline.push(doc! { "$limit": limit as i64 });
+        }
+        pipeline.extend([
+            doc! { "$group": {
+                "_id": null,
+                "total_balance": { "$sum": { "$toDecimal": "$amount" } },
+                "sig_locked_balance": { "$sum": {
+                    "$cond": [ { "$eq": [ "$is_trivial_unlock", true] }, { "$toDecimal": "$amount" }, 0 ]
+                } },
+                "output_count": { "$sum": 1 },
+            } },
+            doc! { "$project": {
+                "total_balance": { "$toString": "$total_balance" },
+                "sig_locked_balance": { "$toString": "$sig_locked_balance" },
+                "output_count": 1,
+            } },
+        ]);
+        self.aggregate(pipeline, None).await?.try_next().await
+    }
+
+    /// Sums the amounts of all outputs owned by each of the given [`Address`](crate::model::utxo::Address)es.
+    /// Addresses that do not own any outputs are omitted. If a `limit` is given, at most that many outputs are summed
+    /// for each address.
+    pub async fn get_address_balances(
+        &self,
+        addresses: &[Address],
+        ledger_index: MilestoneIndex,
+        limit: Option<usize>,
+    ) -> Result<impl Stream<Item = Result<AddressBalanceResult, Error>>, Error> {
+        let mut pipeline = vec![doc! { "$match": {
+            "address": { "$in": addresses },
+            "booked": { "$lte": ledger_index },
+            "spent": { "$not": { "$lte": ledger_index } }
+        } }];
+        if let Some(limit) = limit {
+            // Keep at most `limit` outputs per address while grouping, so that the cap holds for every address.
+            pipeline.extend([
+                doc! { "$group": {
+                    "_id": "$address",
+                    "outputs": { "$firstN": {
+                        "input": { "amount": { "$toDecimal": "$amount" }, "is_trivial_unlock": "$is_trivial_unlock" },
+                        "n": limit as i64,
+                    } },
+                } },
+                doc! { "$project": {
+                    "total_balance": { "$toString": { "$sum": "$outputs.amount" } },
+                    "sig_locked_balance": { "$toString": { "$sum": { "$map": {
+                        "input": "$outputs",
+                        "in": { "$cond": [ { "$eq": [ "$$this.is_trivial_unlock", true ] }, "$$this.amount", 0 ] },
+                    } } } },
+                    "output_count": { "$size": "$outputs" },
+                } },
+            ]);
+        } else {
+            pipeline.extend([
+                doc! { "$group": {
+                    "_id": "$address",
+                    "total_balance": { "$sum": { "$toDecimal": "$amount" } },
+                    "sig_locked_balance": { "$sum": {
+                        "$cond": [ { "$eq": [ "$is_trivial_unlock", true] }, { "$toDecimal": "$amount" }, 0 ]
+                    } },
+                    "output_count": { "$sum": 1 },
+                } },
+                doc! { "$project": {
+                    "total_balance": { "$toString": "$total_balance" },
+                    "sig_locked_balance": { "$toString": "$sig_locked_balance" },
+                    "output_count": 1,
+                } },
+            ]);
+        }
+        self.aggregate(pipeline, None).await
+    }
+
+    /// Counts the unspent outputs owned by an address per output kind, along with their total balance. If a `limit` is
+    /// given, at most that many outputs are counted.
+    pub async fn get_address_summary(
+        &self,
+        address: Address,
+        ledger_index: MilestoneIndex,
+        limit: Option<usize>,
+    ) -> Result<AddressSummaryResult, Error> {
+        let mut pipeline = vec![doc! { "$match": {
+            "address": &address,
+            "booked": { "$lte": ledger_index },
+            "spent": { "$not": { "$lte": ledger_index } }
+        } }];
+        if let Some(limit) = limit {
+            pipeline.push(doc! { "$limit": limit as i64 });
+        }
+        pipeline.extend([
+            doc! { "$group": {
+                "_id": null,
+                "basic_count": { "$sum": { "$cond": [ { "$eq": [ "$kind", "basic" ] }, 1, 0 ] } },
+                "alias_count": { "$sum": { "$cond": [ { "$eq": [ "$kind", "alias" ] }, 1, 0 ] } },
+                "nft_count": { "$sum": { "$cond": [ { "$eq": [ "$kind", "nft" ] }, 1, 0 ] } },
+                "foundry_count": { "$sum": { "$cond": [ { "$eq": [ "$kind", "foundry" ] }, 1, 0 ] } },
+                "total_balance": { "$sum": { "$toDecimal": "$amount" } },
+            } },
+            doc! { "$project": {
+                "basic_count": 1,
+                "alias_count": 1,
+                "nft_count": 1,
+                "foundry_count": 1,
+                "total_balance": { "$toString": "$total_balance" },
+            } },
+        ]);
+        Ok(self
+            .aggregate(pipeline, None)
+            .await?
+            .try_next()
+            .await?
+            .unwrap_or_else(|| AddressSummaryResult {
+                total_balance: "0".to_string(),
+                ..Default::default()
+            }))
+    }
+
+    /// Gets the outputs formerly owned by an address that were spent at or before the ledger index, ordered by the
+    /// milestone index at which they were spent and then by output id. Only the outputs of the requested page are read
+    /// from the outputs collection.
+    pub async fn get_spent_outputs_by_address(
+        &self,
+        address: Address,
+        page_size: usize,
+        cursor: Option<(MilestoneIndex, Option<OutputId>)>,
+        order: SortOrder,
+        ledger_index: MilestoneIndex,
+    ) -> Result<impl Stream<Item = Result<SpentOutputResult, Error>>, Error> {
+        let (sort, cmp1, cmp2) = match order {
+            SortOrder::Newest => (-1, "$lt", "$lte"),
+            SortOrder::Oldest => (1, "$gt", "$gte"),
+        };
+
+        let mut queries = vec![doc! {
+            "address": &address,
+            "spent": { "$lte": ledger_index }
+        }];
+
+        if let Some((milestone_index, output_id)) = cursor {
+            queries.push(match output_id {
+                Some(output_id) => doc! { "$or": [
+                    { "spent": { cmp1: milestone_index } },
+                    { "spent": milestone_index, "_id": { cmp2: output_id } },
+                ] },
+                None => doc! { "spent": { cmp2: milestone_index } },
+            });
+        }
+
+        self.aggregate(
+            [
+                doc! { "$match": { "$and": queries } },
+                doc! { "$sort": { "spent": sort, "_id": sort } },
+                doc! { "$limit": page_size as i64 },
+                doc! { "$lookup": {
+                    "from": OutputCollection::NAME,
+                    "localField": "_id",
+                    "foreignField": "_id",
+                    "as": "doc",
+                } },
+                doc! { "$unwind": "$doc" },
+                doc! { "$project": {
+                    "output_id": "$_id",
+                    "output": "$doc.output",
+                    "booked": "$doc.metadata.booked",
+                    "spent_metadata": "$doc.metadata.spent_metadata",
+                } },
+            ],
+            None,
+        )
+        .await
+    }
+}
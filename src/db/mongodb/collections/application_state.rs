@@ -9,7 +9,7 @@ use crate::{
         mongodb::{MongoDbCollection, MongoDbCollectionExt},
         MongoDb,
     },
-    model::tangle::MilestoneIndexTimestamp,
+    model::tangle::{MilestoneIndex, MilestoneIndexTimestamp},
 };
 
 /// The MongoDb document representation of singleton Application State.
@@ -17,6 +17,8 @@ use crate::{
 pub struct ApplicationStateDocument {
     pub starting_index: Option<MilestoneIndexTimestamp>,
     pub last_migration: Option<MigrationVersion>,
+    pub node_confirmed_index: Option<MilestoneIndex>,
+    pub latest_ledger_update_index: Option<MilestoneIndex>,
 }
 
 /// The migration version and associated metadata.
@@ -94,4 +96,47 @@ impl ApplicationStateCollection {
         .await?;
         Ok(())
     }
+
+    /// Gets the node's confirmed milestone index at the time of the last INX connection.
+    pub async fn get_node_confirmed_index(&self) -> Result<Option<MilestoneIndex>, Error> {
+        Ok(self
+            .find_one::<ApplicationStateDocument>(doc! {}, None)
+            .await?
+            .and_then(|doc| doc.node_confirmed_index))
+    }
+
+    /// Set the node's confirmed milestone index in the singleton application state.
+    pub async fn set_node_confirmed_index(&self, node_confirmed_index: MilestoneIndex) -> Result<(), Error> {
+        self.update_one(
+            doc! {},
+            doc! {
+                "$set": { "node_confirmed_index": node_confirmed_index }
+            },
+            UpdateOptions::builder().upsert(true).build(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Gets the newest milestone index whose ledger updates have been applied.
+    pub async fn get_latest_ledger_update_index(&self) -> Result<Option<MilestoneIndex>, Error> {
+        Ok(self
+            .find_one::<ApplicationStateDocument>(doc! {}, None)
+            .await?
+            .and_then(|doc| doc.latest_ledger_update_index))
+    }
+
+    /// Advances the newest milestone index whose ledger updates have been applied in the singleton application state.
+    /// Milestones that are applied out of order, e.g. while filling gaps, do not move it back.
+    pub async fn set_latest_ledger_update_index(&self, milestone_index: MilestoneIndex) -> Result<(), Error> {
+        self.update_one(
+            doc! {},
+            doc! {
+                "$max": { "latest_ledger_update_index": milestone_index }
+            },
+            UpdateOptions::builder().upsert(true).build(),
+        )
+        .await?;
+        Ok(())
+    }
 }
@@ -3,9 +3,9 @@
 
 use futures::{Stream, TryStreamExt};
 use mongodb::{
-    bson::doc,
+    bson::{doc, Document},
     error::Error,
-    options::{IndexOptions, InsertManyOptions},
+    options::{AggregateOptions, CreateCollectionOptions, IndexOptions, InsertManyOptions},
     IndexModel,
 };
 use packable::PackableExt;
@@ -15,7 +15,10 @@ use tracing::instrument;
 use super::SortOrder;
 use crate::{
     db::{
-        mongodb::{InsertIgnoreDuplicatesExt, MongoDbCollection, MongoDbCollectionExt},
+        mongodb::{
+            InsertIgnoreDuplicatesExt, MongoDbCollection, MongoDbCollectionExt, VersionedCollection,
+            VersionedCollectionExt,
+        },
         MongoDb,
     },
     model::{
@@ -40,6 +43,9 @@ pub struct BlockDocument {
     raw: Vec<u8>,
     /// The block's metadata.
     metadata: BlockMetadata,
+    /// The schema version of the document.
+    #[serde(default)]
+    schema_version: u32,
 }
 
 impl From<BlockData> for BlockDocument {
@@ -56,6 +62,7 @@ impl From<BlockData> for BlockDocument {
             block,
             raw,
             metadata,
+            schema_version: BlockCollection::SCHEMA_VERSION,
         }
     }
 }
@@ -67,6 +74,7 @@ impl From<(BlockId, Block, Vec<u8>, BlockMetadata)> for BlockDocument {
             block,
             raw,
             metadata,
+            schema_version: BlockCollection::SCHEMA_VERSION,
         }
     }
 }
@@ -89,6 +97,18 @@ impl MongoDbCollection for BlockCollection {
         &self.collection
     }
 
+    async fn create_collection(&self, db: &MongoDb) -> Result<(), Error> {
+        let options = db.compress_blocks.then(|| {
+            // The raw blocks make up most of the collection, so the storage engine compresses it rather than the
+            // individual documents.
+            CreateCollectionOptions::builder()
+                .storage_engine(doc! { "wiredTiger": { "configString": "block_compressor=zstd" } })
+                .build()
+        });
+        db.db().create_collection(Self::NAME, options).await.ok();
+        Ok(())
+    }
+
     async fn create_indexes(&self) -> Result<(), Error> {
         self.create_index(
             IndexModel::builder()
@@ -108,6 +128,20 @@ impl MongoDbCollection for BlockCollection {
         )
         .await?;
 
+        // The unique index above only covers included transactions, so this one is needed to find all attachments.
+        self.create_index(
+            IndexModel::builder()
+                .keys(doc! { "block.payload.transaction_id": 1, "_id": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("transaction_id_blocks_index".to_string())
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await?;
+
         self.create_index(
             IndexModel::builder()
                 .keys(doc! { "metadata.referenced_by_milestone_index": -1, "metadata.white_flag_index": 1, "metadata.inclusion_state": 1 })
@@ -121,10 +155,21 @@ impl MongoDbCollection for BlockCollection {
         )
         .await?;
 
+        self.create_schema_version_index().await?;
+
         Ok(())
     }
 }
 
+impl VersionedCollection for BlockCollection {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn upgrade_stages(_from_version: u32) -> Vec<Document> {
+        // Version 1 introduced the `schema_version` field itself.
+        Vec::new()
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct IncludedBlockResult {
     #[serde(rename = "_id")]
@@ -249,6 +294,7 @@ impl BlockCollection {
     pub async fn get_referenced_blocks_in_white_flag_order_stream(
         &self,
         index: MilestoneIndex,
+        batch_size: Option<u32>,
     ) -> Result<impl Stream<Item = Result<(BlockId, Block, Vec<u8>, BlockMetadata), Error>>, Error> {
         #[derive(Debug, Deserialize)]
         struct QueryRes {
@@ -265,7 +311,7 @@ impl BlockCollection {
                     doc! { "$match": { "metadata.referenced_by_milestone_index": index } },
                     doc! { "$sort": { "metadata.white_flag_index": 1 } },
                 ],
-                None,
+                AggregateOptions::builder().batch_size(batch_size).build(),
             )
             .await?
             .map_ok(|r| {
@@ -380,6 +426,33 @@ impl BlockCollection {
         .await
     }
 
+    /// Gets all blocks that contain a transaction by [`TransactionId`], including reattachments that were not
+    /// included, ordered by block id and starting at the given cursor.
+    pub async fn get_blocks_for_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        page_size: usize,
+        cursor: Option<BlockId>,
+    ) -> Result<impl Stream<Item = Result<IncludedBlockMetadataResult, Error>>, Error> {
+        let mut filter = doc! { "block.payload.transaction_id": transaction_id };
+        if let Some(block_id) = cursor {
+            filter.insert("_id", doc! { "$gte": block_id });
+        }
+        self.aggregate(
+            [
+                doc! { "$match": filter },
+                doc! { "$sort": { "_id": 1 } },
+                doc! { "$limit": page_size as i64 },
+                doc! { "$project": {
+                    "_id": 1,
+                    "metadata": 1,
+                } },
+            ],
+            None,
+        )
+        .await
+    }
+
     /// Gets the spending transaction of an [`Output`](crate::model::utxo::Output) by [`OutputId`].
     pub async fn get_spending_transaction(&self, output_id: &OutputId) -> Result<Option<Block>, Error> {
         self.aggregate(
@@ -1,17 +1,17 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use futures::{Stream, TryStreamExt};
+use futures::{Stream, StreamExt, TryStreamExt};
 use mongodb::{
     bson::{doc, Document},
     error::Error,
-    options::{FindOptions, IndexOptions, InsertManyOptions},
+    options::{AggregateOptions, CountOptions, FindOneOptions, FindOptions, IndexOptions, InsertManyOptions},
     IndexModel,
 };
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
-use super::SortOrder;
+use super::{OutputCollection, SortOrder};
 use crate::{
     db::{
         mongodb::{InsertIgnoreDuplicatesExt, MongoDbCollection, MongoDbCollectionExt},
@@ -73,6 +73,19 @@ impl MongoDbCollection for LedgerUpdateCollection {
         )
         .await?;
 
+        self.create_index(
+            IndexModel::builder()
+                .keys(doc! { "_id.milestone_index": 1, "_id.output_id": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("ledger_update_milestone_index".to_string())
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await?;
+
         Ok(())
     }
 }
@@ -93,6 +106,21 @@ pub struct LedgerUpdateByMilestoneRecord {
     pub is_spent: bool,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct LedgerDiffRecord {
+    pub output_id: OutputId,
+    pub address: Address,
+    pub is_spent: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[allow(missing_docs)]
+pub struct LedgerUpdateSummaryResult {
+    pub update_count: usize,
+    pub net_balance_change: String,
+}
+
 fn newest() -> Document {
     doc! { "address": -1, "_id.milestone_index": -1, "_id.output_id": -1, "_id.is_spent": -1 }
 }
@@ -209,10 +237,110 @@ impl LedgerUpdateCollection {
             }))
     }
 
-    /// Streams updates to the ledger for a given milestone index (sorted by [`OutputId`]).
+    /// Checks whether there are any updates to the ledger for a given address after the given milestone, up to and
+    /// including the ledger index.
+    pub async fn has_ledger_updates_since(
+        &self,
+        address: &Address,
+        milestone_index: MilestoneIndex,
+        ledger_index: MilestoneIndex,
+    ) -> Result<bool, Error> {
+        Ok(self
+            .find_one::<Document>(
+                doc! {
+                    "address": address,
+                    "_id.milestone_index": { "$gt": milestone_index, "$lte": ledger_index },
+                },
+                FindOneOptions::builder().projection(doc! { "_id": 1 }).build(),
+            )
+            .await?
+            .is_some())
+    }
+
+    /// Counts the updates to the ledger for a given address up to and including the ledger index. If a `limit` is
+    /// given, counting stops once it is reached, which bounds the cost for addresses with a long history.
+    pub async fn count_ledger_updates_by_address(
+        &self,
+        address: &Address,
+        ledger_index: MilestoneIndex,
+        limit: Option<usize>,
+    ) -> Result<usize, Error> {
+        self.collection()
+            .count_documents(
+                doc! {
+                    "address": address,
+                    "_id.milestone_index": { "$lte": ledger_index },
+                },
+                CountOptions::builder().limit(limit.map(|limit| limit as u64)).build(),
+            )
+            .await
+            .map(|count| count as usize)
+    }
+
+    /// Summarizes the updates to the ledger for a given address by counting them and summing the amounts of the
+    /// created outputs minus the amounts of the spent outputs. If `updates` is given, only those updates are included,
+    /// otherwise the whole history of the address is summarized, which requires a lookup for every update.
+    pub async fn get_ledger_update_summary(
+        &self,
+        address: &Address,
+        updates: Option<&[(OutputId, bool)]>,
+    ) -> Result<LedgerUpdateSummaryResult, Error> {
+        let mut match_doc = doc! { "address": address };
+        if let Some(updates) = updates {
+            if updates.is_empty() {
+                return Ok(LedgerUpdateSummaryResult {
+                    update_count: 0,
+                    net_balance_change: "0".to_string(),
+                });
+            }
+            match_doc.insert(
+                "$or",
+                updates
+                    .iter()
+                    .map(|(output_id, is_spent)| doc! { "_id.output_id": output_id, "_id.is_spent": is_spent })
+                    .collect::<Vec<_>>(),
+            );
+        }
+        Ok(self
+            .aggregate(
+                [
+                    doc! { "$match": match_doc },
+                    doc! { "$lookup": {
+                        "from": OutputCollection::NAME,
+                        "localField": "_id.output_id",
+                        "foreignField": "_id",
+                        "as": "output",
+                    } },
+                    doc! { "$group": {
+                        "_id": null,
+                        "update_count": { "$sum": 1 },
+                        "net_balance_change": { "$sum": { "$multiply": [
+                            { "$cond": [ "$_id.is_spent", -1, 1 ] },
+                            { "$toDecimal": { "$arrayElemAt": [ "$output.output.amount", 0 ] } },
+                        ] } },
+                    } },
+                    doc! { "$project": {
+                        "update_count": 1,
+                        "net_balance_change": { "$toString": "$net_balance_change" },
+                    } },
+                ],
+                None,
+            )
+            .await?
+            .try_next()
+            .await?
+            .unwrap_or_else(|| LedgerUpdateSummaryResult {
+                update_count: 0,
+                net_balance_change: "0".to_string(),
+            }))
+    }
+
+    /// Streams updates to the ledger for a given milestone index (sorted by [`OutputId`]), optionally only those of
+    /// outputs of the given kind.
     pub async fn get_ledger_updates_by_milestone(
         &self,
         milestone_index: MilestoneIndex,
+        output_kind: Option<&str>,
         page_size: usize,
         cursor: Option<(OutputId, bool)>,
     ) -> Result<impl Stream<Item = Result<LedgerUpdateByMilestoneRecord, Error>>, Error> {
@@ -229,16 +357,81 @@ impl LedgerUpdateCollection {
             queries.push(doc! { "$or": cursor_queries });
         }
 
-        Ok(self
-            .find::<LedgerUpdateDocument>(
+        let stream = if let Some(kind) = output_kind {
+            // The kind is only stored with the output, so it is looked up for every update.
+            self.aggregate(
+                [
+                    doc! { "$match": { "$and": queries } },
+                    doc! { "$sort": oldest() },
+                    doc! { "$lookup": {
+                        "from": OutputCollection::NAME,
+                        "localField": "_id.output_id",
+                        "foreignField": "_id",
+                        "pipeline": [ { "$project": { "_id": 0, "kind": "$output.kind" } } ],
+                        "as": "output",
+                    } },
+                    doc! { "$match": { "output.kind": kind } },
+                    doc! { "$limit": page_size as i64 },
+                    doc! { "$unset": "output" },
+                ],
+                None,
+            )
+            .await?
+            .boxed()
+        } else {
+            self.find::<LedgerUpdateDocument>(
                 doc! { "$and": queries },
                 FindOptions::builder().limit(page_size as i64).sort(oldest()).build(),
             )
             .await?
-            .map_ok(|doc| LedgerUpdateByMilestoneRecord {
+            .boxed()
+        };
+
+        Ok(
+            stream.map_ok(|doc: LedgerUpdateDocument| LedgerUpdateByMilestoneRecord {
                 address: doc.address,
                 output_id: doc._id.output_id,
                 is_spent: doc._id.is_spent,
-            }))
+            }),
+        )
+    }
+
+    /// Gets the net change of the ledger after milestone `from`, up to and including milestone `to`. Outputs that were
+    /// both created and consumed within the range are omitted, so every record is either a newly created output that
+    /// is still unspent at `to`, or a consumed output that existed before the range.
+    pub async fn get_ledger_diff(
+        &self,
+        from: MilestoneIndex,
+        to: MilestoneIndex,
+        page_size: usize,
+        cursor: Option<OutputId>,
+    ) -> Result<impl Stream<Item = Result<LedgerDiffRecord, Error>>, Error> {
+        let mut queries = vec![doc! { "_id.milestone_index": { "$gt": from, "$lte": to } }];
+        if let Some(output_id) = cursor {
+            queries.push(doc! { "_id.output_id": { "$gte": output_id } });
+        }
+
+        self.aggregate(
+            [
+                doc! { "$match": { "$and": queries } },
+                doc! { "$group": {
+                    "_id": "$_id.output_id",
+                    "address": { "$first": "$address" },
+                    "created": { "$max": { "$eq": [ "$_id.is_spent", false ] } },
+                    "consumed": { "$max": "$_id.is_spent" },
+                } },
+                doc! { "$match": { "$expr": { "$ne": [ "$created", "$consumed" ] } } },
+                doc! { "$sort": { "_id": 1 } },
+                doc! { "$limit": page_size as i64 },
+                doc! { "$project": {
+                    "_id": 0,
+                    "output_id": "$_id",
+                    "address": 1,
+                    "is_spent": "$consumed",
+                } },
+            ],
+            AggregateOptions::builder().allow_disk_use(true).build(),
+        )
+        .await
     }
 }
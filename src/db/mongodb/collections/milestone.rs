@@ -3,9 +3,9 @@
 
 use std::ops::RangeInclusive;
 
-use futures::{Stream, TryStreamExt};
+use futures::{future::ready, Stream, TryStreamExt};
 use mongodb::{
-    bson::doc,
+    bson::{doc, Document},
     error::Error,
     options::{FindOneOptions, FindOptions, IndexOptions},
     IndexModel,
@@ -16,7 +16,7 @@ use tracing::instrument;
 use super::SortOrder;
 use crate::{
     db::{
-        mongodb::{MongoDbCollection, MongoDbCollectionExt},
+        mongodb::{MongoDbCollection, MongoDbCollectionExt, VersionedCollection, VersionedCollectionExt},
         MongoDb,
     },
     model::{
@@ -38,6 +38,9 @@ pub struct MilestoneDocument {
     at: MilestoneIndexTimestamp,
     /// The milestone's payload.
     payload: MilestonePayload,
+    /// The schema version of the document.
+    #[serde(default)]
+    schema_version: u32,
 }
 
 /// The stardust milestones collection.
@@ -45,6 +48,23 @@ pub struct MilestoneCollection {
     collection: mongodb::Collection<MilestoneDocument>,
 }
 
+/// The milestone that the ledger index is resolved against.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerIndexKind {
+    /// The newest milestone that has been fully synced. The ledger state at this index is complete.
+    Confirmed,
+    /// The newest milestone whose ledger updates have been applied, while the rest of the milestone may still be in the
+    /// process of being synced.
+    Latest,
+}
+
+impl Default for LedgerIndexKind {
+    fn default() -> Self {
+        Self::Confirmed
+    }
+}
+
 #[async_trait::async_trait]
 impl MongoDbCollection for MilestoneCollection {
     const NAME: &'static str = "stardust_milestones";
@@ -87,10 +107,21 @@ impl MongoDbCollection for MilestoneCollection {
         )
         .await?;
 
+        self.create_schema_version_index().await?;
+
         Ok(())
     }
 }
 
+impl VersionedCollection for MilestoneCollection {
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn upgrade_stages(_from_version: u32) -> Vec<Document> {
+        // Version 1 introduced the `schema_version` field itself.
+        Vec::new()
+    }
+}
+
 /// An aggregation type that represents the ranges of completed milestones and gaps.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SyncData {
@@ -144,6 +175,7 @@ impl MilestoneCollection {
                      milestone_id,
                      at,
                      payload,
+                     ..
                  }| (milestone_id, at, payload),
             )
             .try_next()
@@ -208,6 +240,7 @@ impl MilestoneCollection {
             },
             milestone_id,
             payload,
+            schema_version: Self::SCHEMA_VERSION,
         };
 
         self.insert_one(milestone_document, None).await?;
@@ -288,11 +321,79 @@ impl MilestoneCollection {
         self.get_first_milestone_sorted(BY_OLDEST).await
     }
 
-    /// Gets the current ledger index.
+    /// Gets the current ledger index, which is the newest milestone that has been fully synced.
     pub async fn get_ledger_index(&self) -> Result<Option<MilestoneIndex>, Error> {
         Ok(self.get_newest_milestone().await?.map(|ts| ts.milestone_index))
     }
 
+    /// Watches for newly inserted milestones and yields their indexes. Change streams are only available on replica
+    /// sets and sharded clusters, so this fails on standalone servers.
+    pub async fn watch_milestone_indexes(&self) -> Result<impl Stream<Item = Result<MilestoneIndex, Error>>, Error> {
+        #[derive(Deserialize)]
+        struct Res {
+            at: MilestoneIndexTimestamp,
+        }
+
+        Ok(self
+            .collection()
+            .clone_with_type::<Res>()
+            .watch([doc! { "$match": { "operationType": "insert" } }], None)
+            .await?
+            .try_filter_map(|event| ready(Ok(event.full_document.map(|doc| doc.at.milestone_index)))))
+    }
+
+    /// Gets the ranges of stored milestones and the gaps between them within the given range.
+    pub async fn get_sync_data(&self, range: RangeInclusive<MilestoneIndex>) -> Result<SyncData, Error> {
+        #[derive(Deserialize)]
+        struct Res {
+            milestone_index: MilestoneIndex,
+        }
+
+        let mut sync_data = SyncData::default();
+        let mut milestones = self
+            .find::<Res>(
+                doc! {
+                    "at.milestone_index": { "$gte": range.start(), "$lte": range.end() },
+                },
+                FindOptions::builder()
+                    .sort(doc! { "at.milestone_index": BY_OLDEST })
+                    .projection(doc! {
+                        "milestone_index": "$at.milestone_index",
+                    })
+                    .build(),
+            )
+            .await?;
+
+        let mut current: Option<RangeInclusive<MilestoneIndex>> = None;
+        while let Some(Res { milestone_index }) = milestones.try_next().await? {
+            current = Some(match current {
+                Some(completed) if *completed.end() + 1 == milestone_index => *completed.start()..=milestone_index,
+                Some(completed) => {
+                    sync_data.gaps.push(*completed.end() + 1..=milestone_index - 1);
+                    sync_data.completed.push(completed);
+                    milestone_index..=milestone_index
+                }
+                None => {
+                    if milestone_index > *range.start() {
+                        sync_data.gaps.push(*range.start()..=milestone_index - 1);
+                    }
+                    milestone_index..=milestone_index
+                }
+            });
+        }
+        match current {
+            Some(completed) => {
+                if completed.end() < range.end() {
+                    sync_data.gaps.push(*completed.end() + 1..=*range.end());
+                }
+                sync_data.completed.push(completed);
+            }
+            None => sync_data.gaps.push(range),
+        }
+
+        Ok(sync_data)
+    }
+
     /// Streams all available receipt milestone options together with their corresponding `MilestoneIndex`.
     pub async fn get_all_receipts(
         &self,
@@ -361,12 +462,22 @@ pub struct MilestoneResult {
     pub index: MilestoneIndex,
 }
 
+/// Conditions on the payload of the milestones returned by [`MilestoneCollection::get_milestones`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MilestoneFilter {
+    /// Whether the milestone must (or must not) contain a receipt.
+    pub has_receipt: Option<bool>,
+    /// The minimum number of signatures of the milestone.
+    pub min_signatures: Option<usize>,
+}
+
 impl MilestoneCollection {
     /// Get milestones matching given conditions.
     pub async fn get_milestones(
         &self,
         start_timestamp: Option<MilestoneTimestamp>,
         end_timestamp: Option<MilestoneTimestamp>,
+        filter: MilestoneFilter,
         order: SortOrder,
         page_size: usize,
         cursor: Option<MilestoneIndex>,
@@ -376,15 +487,27 @@ impl MilestoneCollection {
             SortOrder::Oldest => (doc! { "at.milestone_index": 1 }, "$lt"),
         };
 
+        let mut queries = vec![doc! {
+            "$nor": [
+                { "at.milestone_timestamp": { "$lt": start_timestamp } },
+                { "at.milestone_timestamp": { "$gt": end_timestamp } },
+                { "at.milestone_index": { cmp: cursor } }
+            ]
+        }];
+        if let Some(has_receipt) = filter.has_receipt {
+            queries.push(if has_receipt {
+                doc! { "payload.essence.options.kind": "receipt" }
+            } else {
+                doc! { "payload.essence.options.kind": { "$ne": "receipt" } }
+            });
+        }
+        if let Some(min_signatures) = filter.min_signatures {
+            queries.push(doc! { "$expr": { "$gte": [ { "$size": "$payload.signatures" }, min_signatures as i64 ] } });
+        }
+
         self.aggregate(
             [
-                doc! { "$match": {
-                    "$nor": [
-                        { "at.milestone_timestamp": { "$lt": start_timestamp } },
-                        { "at.milestone_timestamp": { "$gt": end_timestamp } },
-                        { "at.milestone_index": { cmp: cursor } }
-                    ]
-                } },
+                doc! { "$match": { "$and": queries } },
                 doc! { "$sort": sort },
                 doc! { "$limit": page_size as i64 },
                 doc! { "$project": {
@@ -1,6 +1,8 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+/// Module containing the address outputs collection.
+mod address_output;
 mod application_state;
 /// Module containing the Block document model.
 mod block;
@@ -16,24 +18,35 @@ mod outputs;
 mod protocol_update;
 /// Module containing the treasury model.
 mod treasury;
+/// Module containing the unreferenced blocks collection.
+mod unreferenced_block;
 
 use std::str::FromStr;
 
 use thiserror::Error;
 
 pub use self::{
+    address_output::AddressOutputCollection,
     application_state::{ApplicationStateCollection, MigrationVersion},
     block::BlockCollection,
     configuration_update::ConfigurationUpdateCollection,
-    ledger_update::{LedgerUpdateByAddressRecord, LedgerUpdateByMilestoneRecord, LedgerUpdateCollection},
-    milestone::{MilestoneCollection, MilestoneResult, SyncData},
+    ledger_update::{
+        LedgerDiffRecord, LedgerUpdateByAddressRecord, LedgerUpdateByMilestoneRecord, LedgerUpdateCollection,
+        LedgerUpdateSummaryResult,
+    },
+    milestone::{
+        LedgerIndexKind, MilestoneCollection, MilestoneFilter, MilestoneResult, SyncData,
+    },
     outputs::{
-        AddressStat, AliasOutputsQuery, BasicOutputsQuery, DistributionStat, FoundryOutputsQuery, IndexedId,
-        NftOutputsQuery, OutputCollection, OutputMetadataResult, OutputWithMetadataResult, OutputsResult,
-        UtxoChangesResult,
+        AddressBalanceResult, AddressStat, AddressSummaryResult, AliasOutputsQuery, BalanceResult, BasicOutputsQuery,
+        DistributionStat, FoundryOutputsQuery, IndexedId, LargestOutputResult, MaturingOutputResult,
+        NativeTokenBalanceResult, NftOutputsQuery, OutputCollection, OutputCondition, OutputMetadataResult,
+        OutputResult, OutputWithMetadataResult, OutputsResult, SpentInMilestoneQuery, SpentOutputResult,
+        UnlockConditionKind, UnlockConditionOutputResult, UtxoChangesResult,
     },
     protocol_update::ProtocolUpdateCollection,
     treasury::{TreasuryCollection, TreasuryResult},
+    unreferenced_block::UnreferencedBlockCollection,
 };
 use crate::model::utxo::{AliasOutput, BasicOutput, FoundryOutput, NftOutput, Output};
 
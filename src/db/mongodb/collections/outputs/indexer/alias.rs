@@ -4,10 +4,16 @@
 use mongodb::bson::{self, doc};
 use primitive_types::U256;
 
-use super::queries::{AppendQuery, CreatedQuery, GovernorQuery, IssuerQuery, NativeTokensQuery, SenderQuery};
+use super::queries::{
+    AppendQuery, CreatedQuery, GovernorQuery, IssuerQuery, NativeTokenAmountQuery, NativeTokensQuery, SenderQuery,
+    SpentQuery,
+};
 use crate::{
     db::mongodb::collections::outputs::indexer::queries::AddressQuery,
-    model::{tangle::MilestoneTimestamp, utxo::Address},
+    model::{
+        tangle::{MilestoneIndex, MilestoneTimestamp},
+        utxo::{Address, NativeTokenId},
+    },
 };
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -20,8 +26,11 @@ pub struct AliasOutputsQuery {
     pub has_native_tokens: Option<bool>,
     pub min_native_token_count: Option<U256>,
     pub max_native_token_count: Option<U256>,
+    pub native_token_id: Option<NativeTokenId>,
+    pub min_native_token_amount: Option<U256>,
     pub created_before: Option<MilestoneTimestamp>,
     pub created_after: Option<MilestoneTimestamp>,
+    pub spent_in_milestone: Option<MilestoneIndex>,
 }
 
 impl From<AliasOutputsQuery> for bson::Document {
@@ -37,10 +46,15 @@ impl From<AliasOutputsQuery> for bson::Document {
             min_native_token_count: query.min_native_token_count,
             max_native_token_count: query.max_native_token_count,
         });
+        queries.append_query(NativeTokenAmountQuery {
+            native_token_id: query.native_token_id,
+            min_native_token_amount: query.min_native_token_amount,
+        });
         queries.append_query(CreatedQuery {
             created_before: query.created_before,
             created_after: query.created_after,
         });
+        queries.append_query(SpentQuery(query.spent_in_milestone));
         doc! { "$and": queries }
     }
 }
@@ -51,10 +65,11 @@ mod test {
     use primitive_types::U256;
 
     use super::AliasOutputsQuery;
-    use crate::model::utxo::{Address, NativeTokenAmount};
+    use crate::model::utxo::{Address, NativeTokenAmount, NativeTokenId};
 
     #[test]
     fn test_alias_query_everything() {
+        let native_token_id = NativeTokenId::rand();
         let address = Address::rand_ed25519();
         let query = AliasOutputsQuery {
             state_controller: Some(address),
@@ -64,15 +79,18 @@ mod test {
             has_native_tokens: Some(true),
             min_native_token_count: Some(100.into()),
             max_native_token_count: Some(1000.into()),
+            native_token_id: Some(native_token_id.clone()),
+            min_native_token_amount: Some(500.into()),
             created_before: Some(10000.into()),
             created_after: Some(1000.into()),
+            spent_in_milestone: Some(500.into()),
         };
         let query_doc = doc! {
             "$and": [
                 { "output.kind": "alias" },
                 { "details.address": address },
                 { "output.governor_address_unlock_condition.address": address },
-                { "output.features": {
+                { "output.immutable_features": {
                     "$elemMatch": {
                         "kind": "issuer",
                         "address": address
@@ -95,8 +113,13 @@ mod test {
                         "amount": { "$gt": bson::to_bson(&NativeTokenAmount::from(&U256::from(1000))).unwrap() }
                     }
                 } } },
+                { "output.native_tokens": { "$elemMatch": {
+                    "token_id": bson::to_bson(&native_token_id).unwrap(),
+                    "amount": { "$gte": bson::to_bson(&NativeTokenAmount::from(&U256::from(500))).unwrap() },
+                } } },
                 { "metadata.booked.milestone_timestamp": { "$lt": 10000 } },
                 { "metadata.booked.milestone_timestamp": { "$gt": 1000 } },
+                { "metadata.spent_metadata.spent.milestone_index": 500 },
             ]
         };
         assert_eq!(query_doc, bson::Document::from(query));
@@ -108,6 +131,8 @@ mod test {
             has_native_tokens: Some(false),
             min_native_token_count: Some(100.into()),
             max_native_token_count: Some(1000.into()),
+            native_token_id: None,
+            min_native_token_amount: None,
             created_before: Some(10000.into()),
             ..Default::default()
         };
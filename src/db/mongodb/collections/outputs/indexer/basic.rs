@@ -5,18 +5,25 @@ use mongodb::bson::{self, doc};
 use primitive_types::U256;
 
 use super::queries::{
-    AddressQuery, AppendQuery, CreatedQuery, ExpirationQuery, NativeTokensQuery, SenderQuery,
-    StorageDepositReturnQuery, TagQuery, TimelockQuery,
+    AddressQuery, AliasAddressQuery, AnyOfQuery, AppendQuery, CreatedQuery, ExpirationQuery, NativeTokenAmountQuery,
+    NativeTokensQuery, OutputCondition, SenderQuery, SpentQuery, StorageDepositReturnQuery, TagQuery, TimelockQuery,
+};
+use crate::model::{
+    payload::transaction::output::Tag,
+    tangle::{MilestoneIndex, MilestoneTimestamp},
+    utxo::{Address, AliasAddress, NativeTokenId},
 };
-use crate::model::{payload::transaction::output::Tag, tangle::MilestoneTimestamp, utxo::Address};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[allow(missing_docs)]
 pub struct BasicOutputsQuery {
     pub address: Option<Address>,
+    pub alias_address: Option<AliasAddress>,
     pub has_native_tokens: Option<bool>,
     pub min_native_token_count: Option<U256>,
     pub max_native_token_count: Option<U256>,
+    pub native_token_id: Option<NativeTokenId>,
+    pub min_native_token_amount: Option<U256>,
     pub has_storage_deposit_return: Option<bool>,
     pub storage_deposit_return_address: Option<Address>,
     pub has_timelock: Option<bool>,
@@ -26,10 +33,13 @@ pub struct BasicOutputsQuery {
     pub expires_before: Option<MilestoneTimestamp>,
     pub expires_after: Option<MilestoneTimestamp>,
     pub expiration_return_address: Option<Address>,
+    /// Conditions of which the output has to match at least one.
+    pub any_of: Vec<OutputCondition>,
     pub sender: Option<Address>,
     pub tag: Option<Tag>,
     pub created_before: Option<MilestoneTimestamp>,
     pub created_after: Option<MilestoneTimestamp>,
+    pub spent_in_milestone: Option<MilestoneIndex>,
 }
 
 impl From<BasicOutputsQuery> for bson::Document {
@@ -37,11 +47,16 @@ impl From<BasicOutputsQuery> for bson::Document {
         let mut queries = Vec::new();
         queries.push(doc! { "output.kind": "basic" });
         queries.append_query(AddressQuery(query.address));
+        queries.append_query(AliasAddressQuery(query.alias_address));
         queries.append_query(NativeTokensQuery {
             has_native_tokens: query.has_native_tokens,
             min_native_token_count: query.min_native_token_count,
             max_native_token_count: query.max_native_token_count,
         });
+        queries.append_query(NativeTokenAmountQuery {
+            native_token_id: query.native_token_id,
+            min_native_token_amount: query.min_native_token_amount,
+        });
         queries.append_query(StorageDepositReturnQuery {
             has_storage_return_condition: query.has_storage_deposit_return,
             storage_return_address: query.storage_deposit_return_address,
@@ -57,12 +72,14 @@ impl From<BasicOutputsQuery> for bson::Document {
             expires_after: query.expires_after,
             expiration_return_address: query.expiration_return_address,
         });
+        queries.append_query(AnyOfQuery(query.any_of));
         queries.append_query(SenderQuery(query.sender));
         queries.append_query(TagQuery(query.tag));
         queries.append_query(CreatedQuery {
             created_before: query.created_before,
             created_after: query.created_after,
         });
+        queries.append_query(SpentQuery(query.spent_in_milestone));
         doc! { "$and": queries }
     }
 }
@@ -72,20 +89,25 @@ mod test {
     use mongodb::bson::{self, doc};
     use primitive_types::U256;
 
-    use super::BasicOutputsQuery;
+    use super::{BasicOutputsQuery, OutputCondition};
     use crate::model::{
         payload::transaction::output::Tag,
-        utxo::{Address, NativeTokenAmount},
+        utxo::{Address, AliasAddress, NativeTokenAmount, NativeTokenId},
     };
 
     #[test]
     fn test_basic_query_everything() {
+        let native_token_id = NativeTokenId::rand();
         let address = Address::rand_ed25519();
+        let alias_address = AliasAddress::rand();
         let query = BasicOutputsQuery {
             address: Some(address),
+            alias_address: Some(alias_address),
             has_native_tokens: Some(true),
             min_native_token_count: Some(100.into()),
             max_native_token_count: Some(1000.into()),
+            native_token_id: Some(native_token_id.clone()),
+            min_native_token_amount: Some(500.into()),
             has_storage_deposit_return: Some(true),
             storage_deposit_return_address: Some(address),
             has_timelock: Some(true),
@@ -95,15 +117,18 @@ mod test {
             expires_before: Some(10000.into()),
             expires_after: Some(1000.into()),
             expiration_return_address: Some(address),
+            any_of: vec![OutputCondition::Timelock, OutputCondition::Expiration],
             sender: Some(address),
             tag: Some(Tag::from("my_tag")),
             created_before: Some(10000.into()),
             created_after: Some(1000.into()),
+            spent_in_milestone: Some(500.into()),
         };
         let query_doc = doc! {
             "$and": [
                 { "output.kind": "basic" },
                 { "details.address": address },
+                { "details.address": Address::Alias(alias_address) },
                 { "output.native_tokens": { "$ne": [] } },
                 { "output.native_tokens": { "$not": {
                     "$elemMatch": {
@@ -115,6 +140,10 @@ mod test {
                         "amount": { "$gt": bson::to_bson(&NativeTokenAmount::from(&U256::from(1000))).unwrap() }
                     }
                 } } },
+                { "output.native_tokens": { "$elemMatch": {
+                    "token_id": bson::to_bson(&native_token_id).unwrap(),
+                    "amount": { "$gte": bson::to_bson(&NativeTokenAmount::from(&U256::from(500))).unwrap() },
+                } } },
                 { "output.storage_deposit_return_unlock_condition": { "$exists": true } },
                 { "output.storage_deposit_return_unlock_condition.return_address": address },
                 { "output.timelock_unlock_condition": { "$exists": true } },
@@ -124,6 +153,10 @@ mod test {
                 { "output.expiration_unlock_condition.timestamp": { "$lt": 10000 } },
                 { "output.expiration_unlock_condition.timestamp": { "$gt": 1000 } },
                 { "output.expiration_unlock_condition.return_address": address },
+                { "$or": [
+                    { "output.timelock_unlock_condition": { "$exists": true } },
+                    { "output.expiration_unlock_condition": { "$exists": true } },
+                ] },
                 { "output.features": { "$elemMatch": {
                     "kind": "sender",
                     "address": address
@@ -134,6 +167,7 @@ mod test {
                 } } },
                 { "metadata.booked.milestone_timestamp": { "$lt": 10000 } },
                 { "metadata.booked.milestone_timestamp": { "$gt": 1000 } },
+                { "metadata.spent_metadata.spent.milestone_index": 500 },
             ]
         };
         assert_eq!(query_doc, bson::Document::from(query));
@@ -144,9 +178,12 @@ mod test {
         let address = Address::rand_ed25519();
         let query = BasicOutputsQuery {
             address: Some(address),
+            alias_address: None,
             has_native_tokens: Some(false),
             min_native_token_count: Some(100.into()),
             max_native_token_count: Some(1000.into()),
+            native_token_id: None,
+            min_native_token_amount: None,
             has_storage_deposit_return: Some(false),
             storage_deposit_return_address: Some(address),
             has_timelock: Some(false),
@@ -156,10 +193,12 @@ mod test {
             expires_before: Some(10000.into()),
             expires_after: Some(1000.into()),
             expiration_return_address: Some(address),
+            any_of: Vec::new(),
             sender: None,
             tag: Some(Tag::from("my_tag")),
             created_before: Some(10000.into()),
             created_after: Some(1000.into()),
+            spent_in_milestone: None,
         };
         let query_doc = doc! {
             "$and": [
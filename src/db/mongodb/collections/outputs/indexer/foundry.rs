@@ -4,34 +4,47 @@
 use mongodb::bson::{self, doc};
 use primitive_types::U256;
 
-use super::queries::{AddressQuery, AppendQuery, CreatedQuery, NativeTokensQuery};
-use crate::model::{tangle::MilestoneTimestamp, utxo::Address};
+use super::queries::{
+    AliasAddressQuery, AppendQuery, CreatedQuery, NativeTokenAmountQuery, NativeTokensQuery, SpentQuery,
+};
+use crate::model::{
+    tangle::{MilestoneIndex, MilestoneTimestamp},
+    utxo::{AliasAddress, NativeTokenId},
+};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[allow(missing_docs)]
 pub struct FoundryOutputsQuery {
-    pub alias_address: Option<Address>,
+    pub alias_address: Option<AliasAddress>,
     pub has_native_tokens: Option<bool>,
     pub min_native_token_count: Option<U256>,
     pub max_native_token_count: Option<U256>,
+    pub native_token_id: Option<NativeTokenId>,
+    pub min_native_token_amount: Option<U256>,
     pub created_before: Option<MilestoneTimestamp>,
     pub created_after: Option<MilestoneTimestamp>,
+    pub spent_in_milestone: Option<MilestoneIndex>,
 }
 
 impl From<FoundryOutputsQuery> for bson::Document {
     fn from(query: FoundryOutputsQuery) -> Self {
         let mut queries = Vec::new();
         queries.push(doc! { "output.kind": "foundry" });
-        queries.append_query(AddressQuery(query.alias_address));
+        queries.append_query(AliasAddressQuery(query.alias_address));
         queries.append_query(NativeTokensQuery {
             has_native_tokens: query.has_native_tokens,
             min_native_token_count: query.min_native_token_count,
             max_native_token_count: query.max_native_token_count,
         });
+        queries.append_query(NativeTokenAmountQuery {
+            native_token_id: query.native_token_id,
+            min_native_token_amount: query.min_native_token_amount,
+        });
         queries.append_query(CreatedQuery {
             created_before: query.created_before,
             created_after: query.created_after,
         });
+        queries.append_query(SpentQuery(query.spent_in_milestone));
         doc! { "$and": queries }
     }
 }
@@ -42,23 +55,27 @@ mod test {
     use primitive_types::U256;
 
     use super::FoundryOutputsQuery;
-    use crate::model::utxo::{Address, NativeTokenAmount};
+    use crate::model::utxo::{Address, AliasAddress, NativeTokenAmount, NativeTokenId};
 
     #[test]
     fn test_foundry_query_everything() {
-        let address = Address::rand_ed25519();
+        let native_token_id = NativeTokenId::rand();
+        let address = AliasAddress::rand();
         let query = FoundryOutputsQuery {
             alias_address: Some(address),
             has_native_tokens: Some(true),
             min_native_token_count: Some(100.into()),
             max_native_token_count: Some(1000.into()),
+            native_token_id: Some(native_token_id.clone()),
+            min_native_token_amount: Some(500.into()),
             created_before: Some(10000.into()),
             created_after: Some(1000.into()),
+            spent_in_milestone: Some(500.into()),
         };
         let query_doc = doc! {
             "$and": [
                 { "output.kind": "foundry" },
-                { "details.address": address },
+                { "details.address": Address::Alias(address) },
                 { "output.native_tokens": { "$ne": [] } },
                 { "output.native_tokens": { "$not": {
                     "$elemMatch": {
@@ -70,8 +87,13 @@ mod test {
                         "amount": { "$gt": bson::to_bson(&NativeTokenAmount::from(&U256::from(1000))).unwrap() }
                     }
                 } } },
+                { "output.native_tokens": { "$elemMatch": {
+                    "token_id": bson::to_bson(&native_token_id).unwrap(),
+                    "amount": { "$gte": bson::to_bson(&NativeTokenAmount::from(&U256::from(500))).unwrap() },
+                } } },
                 { "metadata.booked.milestone_timestamp": { "$lt": 10000 } },
                 { "metadata.booked.milestone_timestamp": { "$gt": 1000 } },
+                { "metadata.spent_metadata.spent.milestone_index": 500 },
             ]
         };
         assert_eq!(query_doc, bson::Document::from(query));
@@ -84,8 +106,11 @@ mod test {
             has_native_tokens: Some(false),
             min_native_token_count: Some(100.into()),
             max_native_token_count: Some(1000.into()),
+            native_token_id: None,
+            min_native_token_amount: None,
             created_before: Some(10000.into()),
             created_after: Some(1000.into()),
+            spent_in_milestone: None,
         };
         let query_doc = doc! {
             "$and": [
@@ -19,6 +19,7 @@ use serde::{Deserialize, Serialize};
 
 pub use self::{
     alias::AliasOutputsQuery, basic::BasicOutputsQuery, foundry::FoundryOutputsQuery, nft::NftOutputsQuery,
+    queries::OutputCondition,
 };
 use super::{OutputCollection, OutputDocument};
 use crate::{
@@ -26,15 +27,33 @@ use crate::{
     model::{
         metadata::OutputMetadata,
         tangle::MilestoneIndex,
-        utxo::{AliasId, AliasOutput, FoundryId, FoundryOutput, NftId, NftOutput, OutputId},
+        utxo::{AliasId, AliasOutput, Feature, FoundryId, FoundryOutput, NftId, NftOutput, Output, OutputId},
     },
 };
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug)]
 #[allow(missing_docs)]
 pub struct OutputResult {
     pub output_id: OutputId,
     pub booked_index: MilestoneIndex,
+    /// The alias, foundry or NFT id of the output, if it has one.
+    pub indexed_id: Option<IndexedId>,
+    /// Whether the output was spent at the ledger index.
+    pub is_spent: bool,
+    /// The immutable features of the output, if it is an NFT output.
+    pub nft_immutable_features: Option<Box<[Feature]>>,
+}
+
+#[derive(Deserialize)]
+struct OutputResultDocument {
+    output_id: OutputId,
+    booked_index: MilestoneIndex,
+    #[serde(default)]
+    indexed_id: Option<IndexedId>,
+    kind: String,
+    is_spent: bool,
+    #[serde(default)]
+    immutable_features: Option<Box<[Feature]>>,
 }
 
 #[derive(Clone, Debug)]
@@ -61,6 +80,24 @@ impl IndexedId {
             IndexedId::Nft(_) => NftOutput::KIND,
         }
     }
+
+    /// Converts the indexed ID to its hex representation.
+    pub fn to_hex(&self) -> String {
+        match self {
+            IndexedId::Alias(id) => iota_types::block::output::AliasId::from(*id).to_string(),
+            IndexedId::Foundry(id) => iota_types::block::output::FoundryId::from(*id).to_string(),
+            IndexedId::Nft(id) => iota_types::block::output::NftId::from(*id).to_string(),
+        }
+    }
+
+    /// Alias and NFT IDs have the same representation, so the kind of the output is needed to tell them apart when
+    /// reading them back from the database.
+    fn with_output_kind(self, kind: &str) -> Self {
+        match self {
+            IndexedId::Alias(id) if kind == NftOutput::KIND => IndexedId::Nft(NftId(id.0)),
+            id => id,
+        }
+    }
 }
 
 impl From<IndexedId> for Bson {
@@ -77,6 +114,61 @@ impl From<IndexedId> for Bson {
 #[allow(missing_docs)]
 pub struct IndexedOutputResult {
     pub output_id: OutputId,
+    /// The immutable features of the output, if it is an NFT output.
+    pub nft_immutable_features: Option<Box<[Feature]>>,
+}
+
+/// An indexed outputs query that can be restricted to outputs spent in a given milestone.
+pub trait SpentInMilestoneQuery {
+    /// The milestone in which the requested outputs were spent, if any.
+    fn spent_in_milestone(&self) -> Option<MilestoneIndex>;
+}
+
+macro_rules! impl_spent_in_milestone_query {
+    ($($query:ty),*) => {
+        $(
+            impl SpentInMilestoneQuery for $query {
+                fn spent_in_milestone(&self) -> Option<MilestoneIndex> {
+                    self.spent_in_milestone
+                }
+            }
+        )*
+    };
+}
+
+impl_spent_in_milestone_query!(AliasOutputsQuery, BasicOutputsQuery, FoundryOutputsQuery, NftOutputsQuery);
+
+/// Builds the `$match` stage for an indexed outputs query.
+fn indexed_outputs_match<Q>(
+    query: Q,
+    cursor_query: Option<bson::Document>,
+    include_spent: bool,
+    ledger_index: MilestoneIndex,
+) -> bson::Document
+where
+    Q: SpentInMilestoneQuery,
+    bson::Document: From<Q>,
+{
+    let mut additional_queries = vec![doc! { "metadata.booked.milestone_index": { "$lte": ledger_index } }];
+    if !include_spent {
+        additional_queries.push(doc! {
+            "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
+        });
+    }
+    if query.spent_in_milestone().is_some() {
+        // Outputs spent after the ledger index are still unspent at the ledger index.
+        additional_queries.push(doc! {
+            "metadata.spent_metadata.spent.milestone_index": { "$lte": ledger_index }
+        });
+    }
+    let query_doc = bson::Document::from(query);
+    additional_queries.extend(cursor_query);
+    doc! { "$match": {
+        "$and": [
+            query_doc,
+            { "$and": additional_queries }
+        ]
+    } }
 }
 
 impl OutputCollection {
@@ -116,6 +208,10 @@ impl OutputCollection {
         }
         Ok(res.map(|doc| IndexedOutputResult {
             output_id: doc.output_id,
+            nft_immutable_features: match doc.output {
+                Output::Nft(nft) => Some(nft.immutable_features),
+                _ => None,
+            },
         }))
     }
 
@@ -130,6 +226,7 @@ impl OutputCollection {
         ledger_index: MilestoneIndex,
     ) -> Result<OutputsResult, Error>
     where
+        Q: SpentInMilestoneQuery,
         bson::Document: From<Q>,
     {
         let (sort, cmp1, cmp2) = match order {
@@ -137,42 +234,78 @@ impl OutputCollection {
             SortOrder::Oldest => (doc! { "metadata.booked.milestone_index": 1, "_id": 1 }, "$gt", "$gte"),
         };
 
-        let query_doc = bson::Document::from(query);
-        let mut additional_queries = vec![doc! { "metadata.booked.milestone_index": { "$lte": ledger_index } }];
-        if !include_spent {
-            additional_queries.push(doc! {
-                "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
-            });
-        }
-        if let Some((start_ms, start_output_id)) = cursor {
-            additional_queries.push(doc! { "$or": [
+        let cursor_query = cursor.map(|(start_ms, start_output_id)| {
+            doc! { "$or": [
                 doc! { "metadata.booked.milestone_index": { cmp1: start_ms } },
                 doc! {
                     "metadata.booked.milestone_index": start_ms,
                     "_id": { cmp2: start_output_id }
                 },
-            ] });
-        }
-        let match_doc = doc! { "$match": {
-            "$and": [
-                query_doc,
-                { "$and": additional_queries }
-            ]
-        } };
+            ] }
+        });
+        self.find_indexed_outputs(
+            vec![
+                indexed_outputs_match(query, cursor_query, include_spent, ledger_index),
+                doc! { "$sort": sort },
+                doc! { "$limit": page_size as i64 },
+            ],
+            ledger_index,
+        )
+        .await
+    }
+
+    /// Gets a random sample of indexed outputs that match the provided query.
+    ///
+    /// The sample is taken using the `$sample` aggregation stage, so repeated calls with the same query will return
+    /// different outputs and the result cannot be paginated.
+    pub async fn sample_indexed_outputs<Q>(
+        &self,
+        query: Q,
+        sample_size: usize,
+        include_spent: bool,
+        ledger_index: MilestoneIndex,
+    ) -> Result<OutputsResult, Error>
+    where
+        Q: SpentInMilestoneQuery,
+        bson::Document: From<Q>,
+    {
+        self.find_indexed_outputs(
+            vec![
+                indexed_outputs_match(query, None, include_spent, ledger_index),
+                doc! { "$sample": { "size": sample_size as i64 } },
+            ],
+            ledger_index,
+        )
+        .await
+    }
+
+    async fn find_indexed_outputs(
+        &self,
+        mut pipeline: Vec<bson::Document>,
+        ledger_index: MilestoneIndex,
+    ) -> Result<OutputsResult, Error> {
+        pipeline.push(doc! { "$replaceWith": {
+            "output_id": "$_id",
+            "booked_index": "$metadata.booked.milestone_index",
+            "indexed_id": "$details.indexed_id",
+            "kind": "$output.kind",
+            "immutable_features": "$output.immutable_features",
+            // Unspent outputs have a `null` spent milestone index, which would otherwise compare as lower.
+            "is_spent": { "$and": [
+                { "$gt": [ "$metadata.spent_metadata.spent.milestone_index", null ] },
+                { "$lte": [ "$metadata.spent_metadata.spent.milestone_index", ledger_index ] },
+            ] },
+        } });
         let outputs = self
-            .aggregate(
-                [
-                    match_doc,
-                    doc! { "$sort": sort },
-                    doc! { "$limit": page_size as i64 },
-                    doc! { "$replaceWith": {
-                        "output_id": "$_id",
-                        "booked_index": "$metadata.booked.milestone_index"
-                    } },
-                ],
-                None,
-            )
+            .aggregate(pipeline, None)
             .await?
+            .map_ok(|doc: OutputResultDocument| OutputResult {
+                output_id: doc.output_id,
+                booked_index: doc.booked_index,
+                indexed_id: doc.indexed_id.map(|id| id.with_output_kind(&doc.kind)),
+                is_spent: doc.is_spent,
+                nft_immutable_features: doc.immutable_features.filter(|_| doc.kind == NftOutput::KIND),
+            })
             .try_collect::<Vec<_>>()
             .await?;
         Ok(OutputsResult { outputs })
@@ -310,6 +443,22 @@ impl OutputCollection {
         )
         .await?;
 
+        self.create_index(
+            IndexModel::builder()
+                .keys(doc! { "output.immutable_features": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("output_immutable_feature_index".to_string())
+                        .partial_filter_expression(doc! {
+                            "output.immutable_features": { "$exists": true },
+                        })
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await?;
+
         self.create_index(
             IndexModel::builder()
                 .keys(doc! { "output.native_tokens": 1 })
@@ -380,3 +529,31 @@ impl OutputCollection {
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "rand"))]
+mod test {
+    use mongodb::bson::doc;
+
+    use super::{indexed_outputs_match, BasicOutputsQuery};
+
+    #[test]
+    fn test_spent_in_milestone_match() {
+        let query = BasicOutputsQuery {
+            spent_in_milestone: Some(500.into()),
+            ..Default::default()
+        };
+        let match_doc = doc! { "$match": {
+            "$and": [
+                { "$and": [
+                    { "output.kind": "basic" },
+                    { "metadata.spent_metadata.spent.milestone_index": 500 },
+                ] },
+                { "$and": [
+                    { "metadata.booked.milestone_index": { "$lte": 1000 } },
+                    { "metadata.spent_metadata.spent.milestone_index": { "$lte": 1000 } },
+                ] }
+            ]
+        } };
+        assert_eq!(match_doc, indexed_outputs_match(query, None, true, 1000.into()));
+    }
+}
@@ -5,20 +5,28 @@ use mongodb::bson::{self, doc};
 use primitive_types::U256;
 
 use super::queries::{
-    AddressQuery, AppendQuery, CreatedQuery, ExpirationQuery, IssuerQuery, NativeTokensQuery, SenderQuery,
-    StorageDepositReturnQuery, TagQuery, TimelockQuery,
+    AddressQuery, AliasAddressQuery, AnyOfQuery, AppendQuery, CreatedQuery, ExpirationQuery, IssuerQuery,
+    NativeTokenAmountQuery, NativeTokensQuery, OutputCondition, SenderQuery, SpentQuery, StorageDepositReturnQuery,
+    TagQuery, TimelockQuery,
+};
+use crate::model::{
+    payload::transaction::output::Tag,
+    tangle::{MilestoneIndex, MilestoneTimestamp},
+    utxo::{Address, AliasAddress, NativeTokenId},
 };
-use crate::model::{payload::transaction::output::Tag, tangle::MilestoneTimestamp, utxo::Address};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[allow(missing_docs)]
 pub struct NftOutputsQuery {
     pub address: Option<Address>,
+    pub alias_address: Option<AliasAddress>,
     pub issuer: Option<Address>,
     pub sender: Option<Address>,
     pub has_native_tokens: Option<bool>,
     pub min_native_token_count: Option<U256>,
     pub max_native_token_count: Option<U256>,
+    pub native_token_id: Option<NativeTokenId>,
+    pub min_native_token_amount: Option<U256>,
     pub has_storage_deposit_return: Option<bool>,
     pub storage_deposit_return_address: Option<Address>,
     pub has_timelock: Option<bool>,
@@ -28,9 +36,12 @@ pub struct NftOutputsQuery {
     pub expires_before: Option<MilestoneTimestamp>,
     pub expires_after: Option<MilestoneTimestamp>,
     pub expiration_return_address: Option<Address>,
+    /// Conditions of which the output has to match at least one.
+    pub any_of: Vec<OutputCondition>,
     pub tag: Option<Tag>,
     pub created_before: Option<MilestoneTimestamp>,
     pub created_after: Option<MilestoneTimestamp>,
+    pub spent_in_milestone: Option<MilestoneIndex>,
 }
 
 impl From<NftOutputsQuery> for bson::Document {
@@ -38,6 +49,7 @@ impl From<NftOutputsQuery> for bson::Document {
         let mut queries = Vec::new();
         queries.push(doc! { "output.kind": "nft" });
         queries.append_query(AddressQuery(query.address));
+        queries.append_query(AliasAddressQuery(query.alias_address));
         queries.append_query(IssuerQuery(query.issuer));
         queries.append_query(SenderQuery(query.sender));
         queries.append_query(NativeTokensQuery {
@@ -45,6 +57,10 @@ impl From<NftOutputsQuery> for bson::Document {
             min_native_token_count: query.min_native_token_count,
             max_native_token_count: query.max_native_token_count,
         });
+        queries.append_query(NativeTokenAmountQuery {
+            native_token_id: query.native_token_id,
+            min_native_token_amount: query.min_native_token_amount,
+        });
         queries.append_query(StorageDepositReturnQuery {
             has_storage_return_condition: query.has_storage_deposit_return,
             storage_return_address: query.storage_deposit_return_address,
@@ -60,11 +76,13 @@ impl From<NftOutputsQuery> for bson::Document {
             expires_after: query.expires_after,
             expiration_return_address: query.expiration_return_address,
         });
+        queries.append_query(AnyOfQuery(query.any_of));
         queries.append_query(TagQuery(query.tag));
         queries.append_query(CreatedQuery {
             created_before: query.created_before,
             created_after: query.created_after,
         });
+        queries.append_query(SpentQuery(query.spent_in_milestone));
         doc! { "$and": queries }
     }
 }
@@ -74,22 +92,27 @@ mod test {
     use mongodb::bson::{self, doc};
     use primitive_types::U256;
 
-    use super::NftOutputsQuery;
+    use super::{NftOutputsQuery, OutputCondition};
     use crate::model::{
         payload::transaction::output::Tag,
-        utxo::{Address, NativeTokenAmount},
+        utxo::{Address, AliasAddress, NativeTokenAmount, NativeTokenId},
     };
 
     #[test]
     fn test_nft_query_everything() {
+        let native_token_id = NativeTokenId::rand();
         let address = Address::rand_ed25519();
+        let alias_address = AliasAddress::rand();
         let query = NftOutputsQuery {
             address: Some(address),
+            alias_address: Some(alias_address),
             issuer: Some(address),
             sender: Some(address),
             has_native_tokens: Some(true),
             min_native_token_count: Some(100.into()),
             max_native_token_count: Some(1000.into()),
+            native_token_id: Some(native_token_id.clone()),
+            min_native_token_amount: Some(500.into()),
             has_storage_deposit_return: Some(true),
             storage_deposit_return_address: Some(address),
             has_timelock: Some(true),
@@ -99,15 +122,18 @@ mod test {
             expires_before: Some(10000.into()),
             expires_after: Some(1000.into()),
             expiration_return_address: Some(address),
+            any_of: vec![OutputCondition::Timelock, OutputCondition::Expiration],
             tag: Some(Tag::from("my_tag")),
             created_before: Some(10000.into()),
             created_after: Some(1000.into()),
+            spent_in_milestone: Some(500.into()),
         };
         let query_doc = doc! {
             "$and": [
                 { "output.kind": "nft" },
                 { "details.address": address },
-                { "output.features": { "$elemMatch": {
+                { "details.address": Address::Alias(alias_address) },
+                { "output.immutable_features": { "$elemMatch": {
                     "kind": "issuer",
                     "address": address
                 } } },
@@ -126,6 +152,10 @@ mod test {
                         "amount": { "$gt": bson::to_bson(&NativeTokenAmount::from(&U256::from(1000))).unwrap() }
                     }
                 } } },
+                { "output.native_tokens": { "$elemMatch": {
+                    "token_id": bson::to_bson(&native_token_id).unwrap(),
+                    "amount": { "$gte": bson::to_bson(&NativeTokenAmount::from(&U256::from(500))).unwrap() },
+                } } },
                 { "output.storage_deposit_return_unlock_condition": { "$exists": true } },
                 { "output.storage_deposit_return_unlock_condition.return_address": address },
                 { "output.timelock_unlock_condition": { "$exists": true } },
@@ -135,12 +165,17 @@ mod test {
                 { "output.expiration_unlock_condition.timestamp": { "$lt": 10000 } },
                 { "output.expiration_unlock_condition.timestamp": { "$gt": 1000 } },
                 { "output.expiration_unlock_condition.return_address": address },
+                { "$or": [
+                    { "output.timelock_unlock_condition": { "$exists": true } },
+                    { "output.expiration_unlock_condition": { "$exists": true } },
+                ] },
                 { "output.features": { "$elemMatch": {
                     "kind": "tag",
                     "data": Tag::from("my_tag"),
                 } } },
                 { "metadata.booked.milestone_timestamp": { "$lt": 10000 } },
                 { "metadata.booked.milestone_timestamp": { "$gt": 1000 } },
+                { "metadata.spent_metadata.spent.milestone_index": 500 },
             ]
         };
         assert_eq!(query_doc, bson::Document::from(query));
@@ -151,11 +186,14 @@ mod test {
         let address = Address::rand_ed25519();
         let query = NftOutputsQuery {
             address: Some(address),
+            alias_address: None,
             issuer: None,
             sender: None,
             has_native_tokens: Some(false),
             min_native_token_count: Some(100.into()),
             max_native_token_count: Some(1000.into()),
+            native_token_id: None,
+            min_native_token_amount: None,
             has_storage_deposit_return: Some(false),
             storage_deposit_return_address: Some(address),
             has_timelock: Some(false),
@@ -165,9 +203,11 @@ mod test {
             expires_before: Some(10000.into()),
             expires_after: Some(1000.into()),
             expiration_return_address: Some(address),
+            any_of: Vec::new(),
             tag: Some(Tag::from("my_tag")),
             created_before: Some(10000.into()),
             created_after: Some(1000.into()),
+            spent_in_milestone: None,
         };
         let query_doc = doc! {
             "$and": [
@@ -6,8 +6,8 @@ use primitive_types::U256;
 
 use crate::model::{
     payload::transaction::output::Tag,
-    tangle::MilestoneTimestamp,
-    utxo::{Address, NativeTokenAmount},
+    tangle::{MilestoneIndex, MilestoneTimestamp},
+    utxo::{Address, AliasAddress, NativeTokenAmount, NativeTokenId},
 };
 
 /// Defines how a query is appended to a list of `$and` queries.
@@ -25,14 +25,14 @@ impl<Q: AppendToQuery> AppendQuery<Q> for Vec<Document> {
     }
 }
 
-/// Queries for a feature of type `issuer`.
+/// Queries for an immutable feature of type `issuer`.
 pub(super) struct IssuerQuery(pub(super) Option<Address>);
 
 impl AppendToQuery for IssuerQuery {
     fn append_to(self, queries: &mut Vec<Document>) {
         if let Some(address) = self.0 {
             queries.push(doc! {
-                "output.features": {
+                "output.immutable_features": {
                     "$elemMatch": {
                         "kind": "issuer",
                         "address": address
@@ -131,6 +131,29 @@ impl AppendToQuery for NativeTokensQuery {
     }
 }
 
+/// Queries for outputs holding a specific native token, optionally with a minimum amount.
+pub(super) struct NativeTokenAmountQuery {
+    pub(super) native_token_id: Option<NativeTokenId>,
+    pub(super) min_native_token_amount: Option<U256>,
+}
+
+impl AppendToQuery for NativeTokenAmountQuery {
+    fn append_to(self, queries: &mut Vec<Document>) {
+        if let Some(native_token_id) = self.native_token_id {
+            let mut native_token = doc! { "token_id": bson::to_bson(&native_token_id).unwrap() };
+            if let Some(min_native_token_amount) = self.min_native_token_amount {
+                native_token.insert(
+                    "amount",
+                    doc! { "$gte": bson::to_bson(&NativeTokenAmount::from(&min_native_token_amount)).unwrap() },
+                );
+            }
+            queries.push(doc! {
+                "output.native_tokens": { "$elemMatch": native_token }
+            });
+        }
+    }
+}
+
 /// Queries for an unlock condition of type `address`.
 pub(super) struct AddressQuery(pub(super) Option<Address>);
 
@@ -144,6 +167,20 @@ impl AppendToQuery for AddressQuery {
     }
 }
 
+/// Queries for outputs owned by an alias address. Unlike [`AddressQuery`], this can never match outputs owned by an
+/// Ed25519 or Nft address.
+pub(super) struct AliasAddressQuery(pub(super) Option<AliasAddress>);
+
+impl AppendToQuery for AliasAddressQuery {
+    fn append_to(self, queries: &mut Vec<Document>) {
+        if let Some(address) = self.0 {
+            queries.push(doc! {
+                "details.address": Address::Alias(address)
+            });
+        }
+    }
+}
+
 /// Queries for an unlock condition of type `governor_address`.
 pub(super) struct GovernorQuery(pub(super) Option<Address>);
 
@@ -238,6 +275,45 @@ impl AppendToQuery for ExpirationQuery {
     }
 }
 
+/// A condition on an output that can be combined with others in an [`AnyOfQuery`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OutputCondition {
+    /// The output holds native tokens.
+    NativeTokens,
+    /// The output has an unlock condition of type `storage_deposit_return`.
+    StorageDepositReturn,
+    /// The output has an unlock condition of type `timelock`.
+    Timelock,
+    /// The output has an unlock condition of type `expiration`.
+    Expiration,
+}
+
+impl From<OutputCondition> for Document {
+    fn from(condition: OutputCondition) -> Self {
+        match condition {
+            OutputCondition::NativeTokens => doc! { "output.native_tokens": { "$ne": [] } },
+            OutputCondition::StorageDepositReturn => {
+                doc! { "output.storage_deposit_return_unlock_condition": { "$exists": true } }
+            }
+            OutputCondition::Timelock => doc! { "output.timelock_unlock_condition": { "$exists": true } },
+            OutputCondition::Expiration => doc! { "output.expiration_unlock_condition": { "$exists": true } },
+        }
+    }
+}
+
+/// Queries for outputs that match at least one of the given conditions.
+pub(super) struct AnyOfQuery(pub(super) Vec<OutputCondition>);
+
+impl AppendToQuery for AnyOfQuery {
+    fn append_to(self, queries: &mut Vec<Document>) {
+        if !self.0.is_empty() {
+            queries.push(doc! {
+                "$or": self.0.into_iter().map(Document::from).collect::<Vec<_>>()
+            });
+        }
+    }
+}
+
 /// Queries for created (booked) time.
 pub(super) struct CreatedQuery {
     pub(super) created_before: Option<MilestoneTimestamp>,
@@ -258,3 +334,19 @@ impl AppendToQuery for CreatedQuery {
         }
     }
 }
+
+/// Queries for outputs spent in a given milestone.
+///
+/// Only outputs that are still stored in the database can be matched, so spent outputs that have been pruned will not
+/// be returned.
+pub(super) struct SpentQuery(pub(super) Option<MilestoneIndex>);
+
+impl AppendToQuery for SpentQuery {
+    fn append_to(self, queries: &mut Vec<Document>) {
+        if let Some(spent_in_milestone) = self.0 {
+            queries.push(doc! {
+                "metadata.spent_metadata.spent.milestone_index": spent_in_milestone
+            });
+        }
+    }
+}
@@ -7,7 +7,7 @@ use std::borrow::Borrow;
 
 use futures::{Stream, TryStreamExt};
 use mongodb::{
-    bson::{doc, to_bson, to_document},
+    bson::{doc, to_bson, to_document, Document},
     error::Error,
     options::{IndexOptions, InsertManyOptions},
     IndexModel,
@@ -16,18 +16,24 @@ use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
 pub use self::indexer::{
-    AliasOutputsQuery, BasicOutputsQuery, FoundryOutputsQuery, IndexedId, NftOutputsQuery, OutputsResult,
+    AliasOutputsQuery, BasicOutputsQuery, FoundryOutputsQuery, IndexedId, NftOutputsQuery, OutputCondition,
+    OutputResult, OutputsResult, SpentInMilestoneQuery,
 };
+use super::SortOrder;
 use crate::{
     db::{
-        mongodb::{InsertIgnoreDuplicatesExt, MongoDbCollection, MongoDbCollectionExt},
+        mongodb::{
+            InsertIgnoreDuplicatesExt, MongoDbCollection, MongoDbCollectionExt, VersionedCollection,
+            VersionedCollectionExt,
+        },
         MongoDb,
     },
     model::{
         ledger::{LedgerOutput, LedgerSpent, RentStructureBytes},
         metadata::{OutputMetadata, SpentMetadata},
+        payload::TransactionId,
         tangle::{MilestoneIndex, MilestoneIndexTimestamp, MilestoneTimestamp},
-        utxo::{Address, AliasId, NftId, Output, OutputId},
+        utxo::{Address, AliasId, NativeTokenAmount, NativeTokenId, NftId, Output, OutputId, OutputIndex, TokenAmount},
         BlockId,
     },
 };
@@ -40,6 +46,8 @@ pub struct OutputDocument {
     output: Output,
     metadata: OutputMetadata,
     details: OutputDetails,
+    #[serde(default)]
+    schema_version: u32,
 }
 
 /// The stardust outputs collection.
@@ -79,18 +87,65 @@ impl MongoDbCollection for OutputCollection {
         )
         .await?;
 
+        // Outputs listed by unlock condition are sorted by id, so a partial index per condition covers both the filter
+        // and the sort. The condition field is part of the key only to keep the key patterns distinct.
+        for kind in UnlockConditionKind::ALL {
+            let field = kind.field();
+            self.create_index(
+                IndexModel::builder()
+                    .keys(doc! { "_id": 1, field: 1 })
+                    .options(
+                        IndexOptions::builder()
+                            .name(format!("{}_index", field.replace('.', "_")))
+                            .partial_filter_expression(doc! { field: { "$exists": true } })
+                            .build(),
+                    )
+                    .build(),
+                None,
+            )
+            .await?;
+        }
+
+        // Serves the largest outputs in the order they are listed.
+        self.create_index(
+            IndexModel::builder()
+                .keys(doc! { "details.amount": -1, "_id": 1 })
+                .options(IndexOptions::builder().name("output_amount_index".to_string()).build())
+                .build(),
+            None,
+        )
+        .await?;
+
+        self.create_schema_version_index().await?;
+
         self.create_indexer_indexes().await?;
 
         Ok(())
     }
 }
 
+impl VersionedCollection for OutputCollection {
+    const SCHEMA_VERSION: u32 = 2;
+
+    fn upgrade_stages(from_version: u32) -> Vec<Document> {
+        match from_version {
+            // Version 2 added the numeric amount to the details.
+            1 => vec![doc! { "$set": { "details.amount": { "$toLong": "$output.amount" } } }],
+            // Version 1 introduced the `schema_version` field itself.
+            _ => Vec::new(),
+        }
+    }
+}
+
 /// Precalculated info and other output details.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct OutputDetails {
     #[serde(skip_serializing_if = "Option::is_none")]
     address: Option<Address>,
     is_trivial_unlock: bool,
+    /// The amount of the output as a number, because `output.amount` is stored as a string which does not sort by
+    /// value.
+    amount: u64,
     rent_structure: RentStructureBytes,
     #[serde(skip_serializing_if = "Option::is_none")]
     indexed_id: Option<IndexedId>,
@@ -112,6 +167,7 @@ impl From<&LedgerOutput> for OutputDocument {
             details: OutputDetails {
                 address,
                 is_trivial_unlock,
+                amount: rec.amount().0,
                 rent_structure: rec.rent_structure,
                 indexed_id: match &rec.output {
                     Output::Alias(output) => Some(
@@ -134,6 +190,7 @@ impl From<&LedgerOutput> for OutputDocument {
                     _ => None,
                 },
             },
+            schema_version: OutputCollection::SCHEMA_VERSION,
         }
     }
 }
@@ -167,6 +224,57 @@ pub struct OutputWithMetadataResult {
 pub struct BalanceResult {
     pub total_balance: String,
     pub sig_locked_balance: String,
+    /// The number of outputs the balance was summed over.
+    #[serde(default)]
+    pub output_count: usize,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[allow(missing_docs)]
+pub struct AddressBalanceResult {
+    #[serde(rename = "_id")]
+    pub address: Address,
+    pub total_balance: String,
+    pub sig_locked_balance: String,
+    /// The number of outputs the balance was summed over.
+    pub output_count: usize,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[allow(missing_docs)]
+pub struct NativeTokenBalanceResult {
+    #[serde(rename = "_id")]
+    pub token_id: NativeTokenId,
+    /// The amounts of the token held in each output, which have to be summed by the caller because the database
+    /// cannot sum 256-bit integers.
+    pub amounts: Vec<NativeTokenAmount>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[allow(missing_docs)]
+pub struct MaturingOutputResult {
+    pub output_id: OutputId,
+    /// The time at which the timelock of the output expires.
+    pub unlockable_at: MilestoneTimestamp,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[allow(missing_docs)]
+pub struct AddressSummaryResult {
+    pub basic_count: usize,
+    pub alias_count: usize,
+    pub nft_count: usize,
+    pub foundry_count: usize,
+    pub total_balance: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[allow(missing_docs)]
+pub struct SpentOutputResult {
+    pub output_id: OutputId,
+    pub output: Output,
+    pub booked: MilestoneIndexTimestamp,
+    pub spent_metadata: SpentMetadata,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -176,6 +284,56 @@ pub struct UtxoChangesResult {
     pub consumed_outputs: Vec<OutputId>,
 }
 
+/// The kinds of unlock conditions that an output can carry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UnlockConditionKind {
+    /// The `address` unlock condition.
+    Address,
+    /// The `storage_deposit_return` unlock condition.
+    StorageDepositReturn,
+    /// The `timelock` unlock condition.
+    Timelock,
+    /// The `expiration` unlock condition.
+    Expiration,
+    /// The `state_controller_address` unlock condition of alias outputs.
+    StateControllerAddress,
+    /// The `governor_address` unlock condition of alias outputs.
+    GovernorAddress,
+    /// The `immutable_alias_address` unlock condition of foundry outputs.
+    ImmutableAliasAddress,
+}
+
+impl UnlockConditionKind {
+    const ALL: [Self; 7] = [
+        Self::Address,
+        Self::StorageDepositReturn,
+        Self::Timelock,
+        Self::Expiration,
+        Self::StateControllerAddress,
+        Self::GovernorAddress,
+        Self::ImmutableAliasAddress,
+    ];
+
+    /// The field of the output document that holds the unlock condition.
+    fn field(&self) -> &'static str {
+        match self {
+            Self::Address => "output.address_unlock_condition",
+            Self::StorageDepositReturn => "output.storage_deposit_return_unlock_condition",
+            Self::Timelock => "output.timelock_unlock_condition",
+            Self::Expiration => "output.expiration_unlock_condition",
+            Self::StateControllerAddress => "output.state_controller_address_unlock_condition",
+            Self::GovernorAddress => "output.governor_address_unlock_condition",
+            Self::ImmutableAliasAddress => "output.immutable_alias_address_unlock_condition",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[allow(missing_docs)]
+pub struct UnlockConditionOutputResult {
+    pub output_id: OutputId,
+}
+
 /// Implements the queries for the core API.
 impl OutputCollection {
     /// Upserts [`Outputs`](crate::model::utxo::Output) with their
@@ -296,6 +454,154 @@ impl OutputCollection {
         .await
     }
 
+    /// Get the [`Output`]s with their [`OutputMetadata`] created by a transaction, ordered by output index and
+    /// starting at the given index.
+    pub async fn get_outputs_by_transaction_id(
+        &self,
+        transaction_id: TransactionId,
+        start_index: OutputIndex,
+        page_size: usize,
+        ledger_index: MilestoneIndex,
+    ) -> Result<impl Stream<Item = Result<OutputWithMetadataResult, Error>>, Error> {
+        // Output ids are ordered by transaction id first, so this is a range query on the `_id` index.
+        let start = OutputId::from((transaction_id, start_index));
+        let end = OutputId::from((transaction_id, OutputIndex::MAX));
+        self.aggregate(
+            [
+                doc! { "$match": {
+                    "_id": { "$gte": start, "$lte": end },
+                    "metadata.booked.milestone_index": { "$lte": ledger_index }
+                } },
+                doc! { "$sort": { "_id": 1 } },
+                doc! { "$limit": page_size as i64 },
+                doc! { "$project": {
+                    "output": "$output",
+                    "metadata": {
+                        "output_id": "$_id",
+                        "block_id": "$metadata.block_id",
+                        "booked": "$metadata.booked",
+                        "spent_metadata": "$metadata.spent_metadata",
+                    },
+                } },
+            ],
+            None,
+        )
+        .await
+    }
+
+    /// Get the [`Output`]s with their [`OutputMetadata`] that were created at the given milestone, ordered by output
+    /// id and starting at the given output id.
+    pub async fn get_outputs_created_at(
+        &self,
+        index: MilestoneIndex,
+        start_output_id: Option<OutputId>,
+        page_size: usize,
+    ) -> Result<impl Stream<Item = Result<OutputWithMetadataResult, Error>>, Error> {
+        self.get_outputs_at("metadata.booked.milestone_index", index, start_output_id, page_size)
+            .await
+    }
+
+    /// Get the [`Output`]s with their [`OutputMetadata`] that were consumed at the given milestone, ordered by output
+    /// id and starting at the given output id.
+    pub async fn get_outputs_consumed_at(
+        &self,
+        index: MilestoneIndex,
+        start_output_id: Option<OutputId>,
+        page_size: usize,
+    ) -> Result<impl Stream<Item = Result<OutputWithMetadataResult, Error>>, Error> {
+        self.get_outputs_at(
+            "metadata.spent_metadata.spent.milestone_index",
+            index,
+            start_output_id,
+            page_size,
+        )
+        .await
+    }
+
+    async fn get_outputs_at(
+        &self,
+        milestone_index_field: &str,
+        index: MilestoneIndex,
+        start_output_id: Option<OutputId>,
+        page_size: usize,
+    ) -> Result<impl Stream<Item = Result<OutputWithMetadataResult, Error>>, Error> {
+        let mut query = doc! { milestone_index_field: index };
+        if let Some(start_output_id) = start_output_id {
+            query.insert("_id", doc! { "$gte": start_output_id });
+        }
+        self.aggregate(
+            [
+                doc! { "$match": query },
+                doc! { "$sort": { "_id": 1 } },
+                doc! { "$limit": page_size as i64 },
+                doc! { "$project": {
+                    "output": "$output",
+                    "metadata": {
+                        "output_id": "$_id",
+                        "block_id": "$metadata.block_id",
+                        "booked": "$metadata.booked",
+                        "spent_metadata": "$metadata.spent_metadata",
+                    },
+                } },
+            ],
+            None,
+        )
+        .await
+    }
+
+    /// Get the [`OutputMetadata`] for a set of [`OutputId`]s, without the outputs themselves. Outputs that do not exist
+    /// (at the given ledger index) are not returned.
+    pub async fn get_outputs_metadata(
+        &self,
+        output_ids: &[OutputId],
+        ledger_index: MilestoneIndex,
+    ) -> Result<impl Stream<Item = Result<OutputMetadataResult, Error>>, Error> {
+        self.aggregate(
+            [
+                doc! { "$match": {
+                    "_id": { "$in": output_ids },
+                    "metadata.booked.milestone_index": { "$lte": ledger_index }
+                } },
+                doc! { "$project": {
+                    "output_id": "$_id",
+                    "block_id": "$metadata.block_id",
+                    "booked": "$metadata.booked",
+                    "spent_metadata": "$metadata.spent_metadata",
+                } },
+            ],
+            None,
+        )
+        .await
+    }
+
+    /// Get the [`Output`]s with their [`OutputMetadata`] for a set of [`OutputId`]s. Outputs that do not exist (at the
+    /// given ledger index) are not returned.
+    pub async fn get_outputs_with_metadata(
+        &self,
+        output_ids: &[OutputId],
+        ledger_index: MilestoneIndex,
+    ) -> Result<impl Stream<Item = Result<OutputWithMetadataResult, Error>>, Error> {
+        self.aggregate(
+            [
+                doc! { "$match": {
+                    "_id": { "$in": output_ids },
+                    "metadata.booked.milestone_index": { "$lte": ledger_index }
+                } },
+                doc! { "$project": {
+                    "output": "$output",
+                    "metadata": {
+                        "output_id": "$_id",
+                        "block_id": "$metadata.block_id",
+                        "booked": "$metadata.booked",
+                        "spent_metadata": "$metadata.spent_metadata",
+                    },
+                } },
+            ],
+            None,
+        )
+        .await
+    }
+
     /// Stream all [`LedgerOutput`]s that were unspent at a given ledger index.
     pub async fn get_unspent_output_stream(
         &self,
@@ -416,38 +722,287 @@ impl OutputCollection {
         .await
     }
 
-    /// Sums the amounts of all outputs owned by the given [`Address`](crate::model::utxo::Address).
+    /// Sums the amounts of all outputs owned by the given [`Address`](crate::model::utxo::Address). If a `limit` is
+    /// given, at most that many outputs are summed.
     pub async fn get_address_balance(
         &self,
         address: Address,
         ledger_index: MilestoneIndex,
+        limit: Option<usize>,
     ) -> Result<Option<BalanceResult>, Error> {
-        self
-            .aggregate(
-                [
-                    // Look at all (at ledger index o'clock) unspent output documents for the given address.
-                    doc! { "$match": {
-                        "details.address": &address,
-                        "metadata.booked.milestone_index": { "$lte": ledger_index },
-                        "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
-                    } },
-                    doc! { "$group": {
-                        "_id": null,
-                        "total_balance": { "$sum": { "$toDecimal": "$output.amount" } },
-                        "sig_locked_balance": { "$sum": { 
-                            "$cond": [ { "$eq": [ "$details.is_trivial_unlock", true] }, { "$toDecimal": "$output.amount" }, 0 ]
-                        } },
+        // Look at all (at ledger index o'clock) unspent output documents for the given address.
+        let mut pipeline = vec![doc! { "$match": {
+            "details.address": &address,
+            "metadata.booked.milestone_index": { "$lte": ledger_index },
+            "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
+        } }];
+        if let Some(limit) = limit {
+            pipeline.push(doc! { "$limit": limit as i64 });
+        }
+        pipeline.extend([
+            doc! { "$group": {
+                "_id": null,
+                "total_balance": { "$sum": { "$toDecimal": "$output.amount" } },
+                "sig_locked_balance": { "$sum": {
+                    "$cond": [ { "$eq": [ "$details.is_trivial_unlock", true] }, { "$toDecimal": "$output.amount" }, 0 ]
+                } },
+                "output_count": { "$sum": 1 },
+            } },
+            doc! { "$project": {
+                "total_balance": { "$toString": "$total_balance" },
+                "sig_locked_balance": { "$toString": "$sig_locked_balance" },
+                "output_count": 1,
+            } },
+        ]);
+        self.aggregate(pipeline, None).await?.try_next().await
+    }
+
+    /// Gets the amounts of native tokens held in the unspent outputs of the given
+    /// [`Address`](crate::model::utxo::Address), grouped by token id. If a `limit` is given, at most that many outputs
+    /// are read.
+    pub async fn get_address_native_token_balances(
+        &self,
+        address: Address,
+        ledger_index: MilestoneIndex,
+        limit: Option<usize>,
+    ) -> Result<impl Stream<Item = Result<NativeTokenBalanceResult, Error>>, Error> {
+        let mut pipeline = vec![doc! { "$match": {
+            "details.address": &address,
+            "output.native_tokens.0": { "$exists": true },
+            "metadata.booked.milestone_index": { "$lte": ledger_index },
+            "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
+        } }];
+        if let Some(limit) = limit {
+            pipeline.push(doc! { "$limit": limit as i64 });
+        }
+        pipeline.extend([
+            doc! { "$unwind": "$output.native_tokens" },
+            doc! { "$group": {
+                "_id": "$output.native_tokens.token_id",
+                "amounts": { "$push": "$output.native_tokens.amount" },
+            } },
+        ]);
+        self.aggregate(pipeline, None).await
+    }
+
+    /// Sums the amounts of all outputs owned by each of the given [`Address`](crate::model::utxo::Address)es.
+    /// Addresses that do not own any outputs are omitted. If a `limit` is given, at most that many outputs are summed
+    /// for each address.
+    pub async fn get_address_balances(
+        &self,
+        addresses: &[Address],
+        ledger_index: MilestoneIndex,
+        limit: Option<usize>,
+    ) -> Result<impl Stream<Item = Result<AddressBalanceResult, Error>>, Error> {
+        let mut pipeline = vec![doc! { "$match": {
+            "details.address": { "$in": addresses },
+            "metadata.booked.milestone_index": { "$lte": ledger_index },
+            "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
+        } }];
+        if let Some(limit) = limit {
+            // Keep at most `limit` outputs per address while grouping, so that the cap holds for every address.
+            pipeline.extend([
+                doc! { "$group": {
+                    "_id": "$details.address",
+                    "outputs": { "$firstN": {
+                        "input": {
+                            "amount": { "$toDecimal": "$output.amount" },
+                            "is_trivial_unlock": "$details.is_trivial_unlock",
+                        },
+                        "n": limit as i64,
                     } },
-                    doc! { "$project": {
-                        "total_balance": { "$toString": "$total_balance" },
-                        "sig_locked_balance": { "$toString": "$sig_locked_balance" },
+                } },
+                doc! { "$project": {
+                    "total_balance": { "$toString": { "$sum": "$outputs.amount" } },
+                    "sig_locked_balance": { "$toString": { "$sum": { "$map": {
+                        "input": "$outputs",
+                        "in": { "$cond": [ { "$eq": [ "$$this.is_trivial_unlock", true ] }, "$$this.amount", 0 ] },
+                    } } } },
+                    "output_count": { "$size": "$outputs" },
+                } },
+            ]);
+        } else {
+            pipeline.extend([
+                doc! { "$group": {
+                    "_id": "$details.address",
+                    "total_balance": { "$sum": { "$toDecimal": "$output.amount" } },
+                    "sig_locked_balance": { "$sum": {
+                        "$cond": [ { "$eq": [ "$details.is_trivial_unlock", true] }, { "$toDecimal": "$output.amount" }, 0 ]
                     } },
-                ],
-                None,
-            )
+                    "output_count": { "$sum": 1 },
+                } },
+                doc! { "$project": {
+                    "total_balance": { "$toString": "$total_balance" },
+                    "sig_locked_balance": { "$toString": "$sig_locked_balance" },
+                    "output_count": 1,
+                } },
+            ]);
+        }
+        self.aggregate(pipeline, None).await
+    }
+
+    /// Gets the unspent outputs owned by an address whose timelock expires within the given time range, ordered by
+    /// the expiry and output id ascending and starting after the given cursor.
+    pub async fn get_maturing_outputs(
+        &self,
+        address: Address,
+        ledger_index: MilestoneIndex,
+        unlockable_after: MilestoneTimestamp,
+        unlockable_before: Option<MilestoneTimestamp>,
+        limit: usize,
+        cursor: Option<(MilestoneTimestamp, OutputId)>,
+    ) -> Result<impl Stream<Item = Result<MaturingOutputResult, Error>>, Error> {
+        let mut timestamp = doc! { "$gte": unlockable_after };
+        if let Some(unlockable_before) = unlockable_before {
+            timestamp.insert("$lt", unlockable_before);
+        }
+        let mut pipeline = vec![doc! { "$match": {
+            "details.address": &address,
+            "output.timelock_unlock_condition.timestamp": timestamp,
+            "metadata.booked.milestone_index": { "$lte": ledger_index },
+            "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
+        } }];
+        if let Some((timestamp, output_id)) = cursor {
+            pipeline.push(doc! { "$match": { "$or": [
+                { "output.timelock_unlock_condition.timestamp": { "$gt": timestamp } },
+                { "output.timelock_unlock_condition.timestamp": timestamp, "_id": { "$gte": output_id } },
+            ] } });
+        }
+        pipeline.extend([
+            doc! { "$sort": { "output.timelock_unlock_condition.timestamp": 1, "_id": 1 } },
+            doc! { "$limit": limit as i64 },
+            doc! { "$project": {
+                "_id": 0,
+                "output_id": "$_id",
+                "unlockable_at": "$output.timelock_unlock_condition.timestamp",
+            } },
+        ]);
+        self.aggregate(pipeline, None).await
+    }
+
+    /// Gets the ids of the unspent outputs that carry the given kind of unlock condition, ordered by output id.
+    pub async fn get_outputs_by_unlock_condition(
+        &self,
+        kind: UnlockConditionKind,
+        ledger_index: MilestoneIndex,
+        limit: usize,
+        cursor: Option<OutputId>,
+    ) -> Result<impl Stream<Item = Result<UnlockConditionOutputResult, Error>>, Error> {
+        let mut filter = doc! {
+            kind.field(): { "$exists": true },
+            "metadata.booked.milestone_index": { "$lte": ledger_index },
+            "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
+        };
+        if let Some(output_id) = cursor {
+            filter.insert("_id", doc! { "$gte": output_id });
+        }
+        self.aggregate(
+            [
+                doc! { "$match": filter },
+                doc! { "$sort": { "_id": 1 } },
+                doc! { "$limit": limit as i64 },
+                doc! { "$project": {
+                    "_id": 0,
+                    "output_id": "$_id",
+                } },
+            ],
+            None,
+        )
+        .await
+    }
+
+    /// Counts the unspent outputs owned by an address per output kind, along with their total balance. If a `limit` is
+    /// given, at most that many outputs are counted.
+    pub async fn get_address_summary(
+        &self,
+        address: Address,
+        ledger_index: MilestoneIndex,
+        limit: Option<usize>,
+    ) -> Result<AddressSummaryResult, Error> {
+        let mut pipeline = vec![doc! { "$match": {
+            "details.address": &address,
+            "metadata.booked.milestone_index": { "$lte": ledger_index },
+            "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
+        } }];
+        if let Some(limit) = limit {
+            pipeline.push(doc! { "$limit": limit as i64 });
+        }
+        pipeline.extend([
+            doc! { "$group": {
+                "_id": null,
+                "basic_count": { "$sum": { "$cond": [ { "$eq": [ "$output.kind", "basic" ] }, 1, 0 ] } },
+                "alias_count": { "$sum": { "$cond": [ { "$eq": [ "$output.kind", "alias" ] }, 1, 0 ] } },
+                "nft_count": { "$sum": { "$cond": [ { "$eq": [ "$output.kind", "nft" ] }, 1, 0 ] } },
+                "foundry_count": { "$sum": { "$cond": [ { "$eq": [ "$output.kind", "foundry" ] }, 1, 0 ] } },
+                "total_balance": { "$sum": { "$toDecimal": "$output.amount" } },
+            } },
+            doc! { "$project": {
+                "basic_count": 1,
+                "alias_count": 1,
+                "nft_count": 1,
+                "foundry_count": 1,
+                "total_balance": { "$toString": "$total_balance" },
+            } },
+        ]);
+        Ok(self
+            .aggregate(pipeline, None)
             .await?
             .try_next()
-            .await
+            .await?
+            .unwrap_or_else(|| AddressSummaryResult {
+                total_balance: "0".to_string(),
+                ..Default::default()
+            }))
+    }
+
+    /// Gets the outputs formerly owned by an address that were spent at or before the ledger index, ordered by the
+    /// milestone index at which they were spent and then by output id.
+    pub async fn get_spent_outputs_by_address(
+        &self,
+        address: Address,
+        page_size: usize,
+        cursor: Option<(MilestoneIndex, Option<OutputId>)>,
+        order: SortOrder,
+        ledger_index: MilestoneIndex,
+    ) -> Result<impl Stream<Item = Result<SpentOutputResult, Error>>, Error> {
+        let (sort, cmp1, cmp2) = match order {
+            SortOrder::Newest => (-1, "$lt", "$lte"),
+            SortOrder::Oldest => (1, "$gt", "$gte"),
+        };
+
+        let mut queries = vec![doc! {
+            "details.address": &address,
+            "metadata.spent_metadata.spent.milestone_index": { "$lte": ledger_index }
+        }];
+
+        if let Some((milestone_index, output_id)) = cursor {
+            queries.push(match output_id {
+                Some(output_id) => doc! { "$or": [
+                    { "metadata.spent_metadata.spent.milestone_index": { cmp1: milestone_index } },
+                    {
+                        "metadata.spent_metadata.spent.milestone_index": milestone_index,
+                        "_id": { cmp2: output_id }
+                    },
+                ] },
+                None => doc! { "metadata.spent_metadata.spent.milestone_index": { cmp2: milestone_index } },
+            });
+        }
+
+        self.aggregate(
+            [
+                doc! { "$match": { "$and": queries } },
+                doc! { "$sort": { "metadata.spent_metadata.spent.milestone_index": sort, "_id": sort } },
+                doc! { "$limit": page_size as i64 },
+                doc! { "$project": {
+                    "output_id": "$_id",
+                    "output": "$output",
+                    "booked": "$metadata.booked",
+                    "spent_metadata": "$metadata.spent_metadata",
+                } },
+            ],
+            None,
+        )
+        .await
     }
 
     /// Returns the changes to the UTXO ledger (as consumed and created output ids) that were applied at the given
@@ -494,19 +1049,14 @@ impl OutputCollection {
     /// Get the address activity in a date
     pub async fn get_address_activity_count_in_range(
         &self,
-        start_date: time::Date,
-        end_date: time::Date,
+        start_timestamp: MilestoneTimestamp,
+        end_timestamp: MilestoneTimestamp,
     ) -> Result<usize, Error> {
         #[derive(Deserialize)]
         struct Res {
             count: usize,
         }
 
-        let (start_timestamp, end_timestamp) = (
-            MilestoneTimestamp::from(start_date.midnight().assume_utc()),
-            MilestoneTimestamp::from(end_date.midnight().assume_utc()),
-        );
-
         Ok(self
             .aggregate::<Res>(
                 [
@@ -550,6 +1100,14 @@ pub struct AddressStat {
     pub balance: String,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct LargestOutputResult {
+    pub output_id: OutputId,
+    pub amount: TokenAmount,
+    pub address: Option<Address>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TokenDistribution {
     pub distribution: Vec<DistributionStat>,
@@ -567,19 +1125,24 @@ pub struct DistributionStat {
 }
 
 impl OutputCollection {
-    /// Create richest address statistics.
+    /// Create richest address statistics, leaving out the excluded addresses.
     pub async fn get_richest_addresses(
         &self,
         ledger_index: MilestoneIndex,
         top: usize,
+        excluded: &[Address],
     ) -> Result<RichestAddresses, Error> {
+        let mut filter = doc! {
+            "metadata.booked.milestone_index": { "$lte": ledger_index },
+            "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
+        };
+        if !excluded.is_empty() {
+            filter.insert("details.address", doc! { "$nin": excluded });
+        }
         let top = self
             .aggregate(
                 [
-                    doc! { "$match": {
-                        "metadata.booked.milestone_index": { "$lte": ledger_index },
-                        "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
-                    } },
+                    doc! { "$match": filter },
                     doc! { "$group" : {
                         "_id": "$details.address",
                         "balance": { "$sum": { "$toDecimal": "$output.amount" } },
@@ -600,6 +1163,41 @@ impl OutputCollection {
         Ok(RichestAddresses { top })
     }
 
+    /// Get the unspent outputs with the highest amounts, ordered by amount descending and output id ascending and
+    /// starting after the given cursor.
+    pub async fn get_largest_outputs(
+        &self,
+        ledger_index: MilestoneIndex,
+        limit: usize,
+        cursor: Option<(TokenAmount, OutputId)>,
+    ) -> Result<impl Stream<Item = Result<LargestOutputResult, Error>>, Error> {
+        let mut filter = doc! {
+            "metadata.booked.milestone_index": { "$lte": ledger_index },
+            "metadata.spent_metadata.spent.milestone_index": { "$not": { "$lte": ledger_index } }
+        };
+        if let Some((amount, output_id)) = cursor {
+            filter.insert(
+                "$or",
+                vec![
+                    doc! { "details.amount": { "$lt": amount.0 as i64 } },
+                    doc! { "details.amount": amount.0 as i64, "_id": { "$gte": output_id } },
+                ],
+            );
+        }
+        let pipeline = [
+            doc! { "$match": filter },
+            doc! { "$sort": { "details.amount": -1, "_id": 1 } },
+            doc! { "$limit": limit as i64 },
+            doc! { "$project": {
+                "_id": 0,
+                "output_id": "$_id",
+                "amount": "$output.amount",
+                "address": "$details.address",
+            } },
+        ];
+        self.aggregate(pipeline, None).await
+    }
+
     /// Create token distribution statistics.
     pub async fn get_token_distribution(&self, ledger_index: MilestoneIndex) -> Result<TokenDistribution, Error> {
         let distribution = self
@@ -0,0 +1,163 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::TryStreamExt;
+use mongodb::{
+    bson::doc,
+    error::Error,
+    options::{FindOneOptions, InsertManyOptions},
+};
+use packable::PackableExt;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    db::{
+        mongodb::{collections::BlockCollection, InsertIgnoreDuplicatesExt, MongoDbCollection, MongoDbCollectionExt},
+        MongoDb,
+    },
+    model::{Block, BlockId},
+};
+
+/// A block that the node received but that has not been referenced by a synced milestone.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnreferencedBlockDocument {
+    #[serde(rename = "_id")]
+    block_id: BlockId,
+    /// The block.
+    block: Block,
+    /// The raw bytes of the block.
+    #[serde(with = "serde_bytes")]
+    raw: Vec<u8>,
+}
+
+impl From<(BlockId, Block, Vec<u8>)> for UnreferencedBlockDocument {
+    fn from((block_id, block, raw): (BlockId, Block, Vec<u8>)) -> Self {
+        Self { block_id, block, raw }
+    }
+}
+
+/// The stardust unreferenced blocks collection. Blocks are removed from it once a milestone that references them is
+/// synced, so that it only holds blocks that are pending or were orphaned.
+pub struct UnreferencedBlockCollection {
+    collection: mongodb::Collection<UnreferencedBlockDocument>,
+}
+
+impl MongoDbCollection for UnreferencedBlockCollection {
+    const NAME: &'static str = "stardust_unreferenced_blocks";
+    type Document = UnreferencedBlockDocument;
+
+    fn instantiate(_db: &MongoDb, collection: mongodb::Collection<Self::Document>) -> Self {
+        Self { collection }
+    }
+
+    fn collection(&self) -> &mongodb::Collection<Self::Document> {
+        &self.collection
+    }
+}
+
+#[derive(Deserialize)]
+struct RawResult {
+    #[serde(with = "serde_bytes")]
+    raw: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct BlockIdResult {
+    #[serde(rename = "_id")]
+    block_id: BlockId,
+}
+
+impl UnreferencedBlockCollection {
+    /// Inserts blocks that have not been referenced yet.
+    ///
+    /// A milestone cone removes its blocks from this collection after storing them in the blocks collection. If one of
+    /// the blocks is inserted here after that happened, it is removed again right away, so that no referenced block is
+    /// left behind.
+    #[instrument(skip_all, err, level = "trace")]
+    pub async fn insert_blocks<I, B>(&self, blocks: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = B>,
+        UnreferencedBlockDocument: From<B>,
+    {
+        let blocks = blocks
+            .into_iter()
+            .map(UnreferencedBlockDocument::from)
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return Ok(());
+        }
+        let block_ids = blocks.iter().map(|doc| doc.block_id).collect::<Vec<_>>();
+        self.insert_many_ignore_duplicates(blocks, InsertManyOptions::builder().ordered(false).build())
+            .await?;
+
+        let referenced = self
+            .aggregate::<BlockIdResult>(
+                [
+                    doc! { "$match": { "_id": { "$in": &block_ids } } },
+                    doc! { "$lookup": {
+                        "from": BlockCollection::NAME,
+                        "localField": "_id",
+                        "foreignField": "_id",
+                        "as": "referenced",
+                    } },
+                    doc! { "$match": { "referenced": { "$ne": [] } } },
+                    doc! { "$project": { "_id": 1 } },
+                ],
+                None,
+            )
+            .await?
+            .map_ok(|BlockIdResult { block_id }| block_id)
+            .try_collect::<Vec<_>>()
+            .await?;
+        self.remove_blocks(&referenced).await
+    }
+
+    /// Removes blocks that have been referenced by a milestone.
+    #[instrument(skip_all, err, level = "trace")]
+    pub async fn remove_blocks(&self, block_ids: impl IntoIterator<Item = &BlockId>) -> Result<(), Error> {
+        let block_ids = block_ids.into_iter().collect::<Vec<_>>();
+        if !block_ids.is_empty() {
+            self.collection
+                .delete_many(doc! { "_id": { "$in": block_ids } }, None)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get an unreferenced [`Block`] by its [`BlockId`].
+    pub async fn get_block(&self, block_id: &BlockId) -> Result<Option<Block>, Error> {
+        Ok(self
+            .get_block_raw(block_id)
+            .await?
+            .map(|raw| iota_types::block::Block::unpack_unverified(raw).unwrap().into()))
+    }
+
+    /// Get the raw bytes of an unreferenced [`Block`] by its [`BlockId`].
+    pub async fn get_block_raw(&self, block_id: &BlockId) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self
+            .aggregate(
+                [
+                    doc! { "$match": { "_id": block_id } },
+                    doc! { "$project": { "raw": 1 } },
+                ],
+                None,
+            )
+            .await?
+            .try_next()
+            .await?
+            .map(|RawResult { raw }| raw))
+    }
+
+    /// Whether an unreferenced [`Block`] with the given [`BlockId`] is stored.
+    pub async fn contains_block(&self, block_id: &BlockId) -> Result<bool, Error> {
+        Ok(self
+            .find_one::<BlockIdResult>(
+                doc! { "_id": block_id },
+                FindOneOptions::builder().projection(doc! { "_id": 1 }).build(),
+            )
+            .await?
+            .is_some())
+    }
+}
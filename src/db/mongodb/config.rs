@@ -3,9 +3,11 @@
 
 //! Holds the `MongoDb` config and its defaults.
 
+use std::path::PathBuf;
+
 use mongodb::{
     error::Error,
-    options::{ConnectionString, HostInfo},
+    options::{AuthMechanism, ConnectionString, HostInfo},
 };
 
 /// The default connection string of the database.
@@ -21,6 +23,39 @@ pub struct MongoDbConfig {
     pub conn_str: String,
     /// The name of the database to connect to.
     pub database_name: String,
+    /// The CA file that the TLS certificate of the database is verified against. Setting it enables TLS.
+    pub tls_ca_file: Option<PathBuf>,
+    /// The file holding the client certificate and its private key, which are presented to the database. Setting it
+    /// enables TLS.
+    pub tls_cert_key_file: Option<PathBuf>,
+    /// The mechanism used to authenticate with the database.
+    pub auth_mechanism: Option<MongoDbAuthMechanism>,
+    /// The database that holds the credentials of the user.
+    pub auth_source: Option<String>,
+    /// Whether the blocks collection is created with zstd compression. Only takes effect when the collection is
+    /// created, so an existing database keeps its compression.
+    pub compress_blocks: bool,
+}
+
+/// The mechanisms that can be used to authenticate with the database.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum MongoDbAuthMechanism {
+    /// SCRAM with SHA-1, using the user and password of the connection string.
+    ScramSha1,
+    /// SCRAM with SHA-256, using the user and password of the connection string.
+    ScramSha256,
+    /// X.509, using the client certificate presented over TLS.
+    X509,
+}
+
+impl From<MongoDbAuthMechanism> for AuthMechanism {
+    fn from(value: MongoDbAuthMechanism) -> Self {
+        match value {
+            MongoDbAuthMechanism::ScramSha1 => AuthMechanism::ScramSha1,
+            MongoDbAuthMechanism::ScramSha256 => AuthMechanism::ScramSha256,
+            MongoDbAuthMechanism::X509 => AuthMechanism::MongoDbX509,
+        }
+    }
 }
 
 impl MongoDbConfig {
@@ -40,6 +75,11 @@ impl Default for MongoDbConfig {
         Self {
             conn_str: DEFAULT_CONN_STR.to_string(),
             database_name: DEFAULT_DATABASE_NAME.to_string(),
+            tls_ca_file: None,
+            tls_cert_key_file: None,
+            auth_mechanism: None,
+            auth_source: None,
+            compress_blocks: false,
         }
     }
 }
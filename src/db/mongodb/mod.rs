@@ -14,37 +14,102 @@ use config::MongoDbConfig;
 use mongodb::{
     bson::{doc, Document},
     error::Error,
-    options::ClientOptions,
+    options::{ClientOptions, Credential, DatabaseOptions, ReadPreference, SelectionCriteria, Tls, TlsOptions},
     Client,
 };
 
-pub use self::collection::{InsertIgnoreDuplicatesExt, MongoDbCollection, MongoDbCollectionExt};
+pub use self::collection::{
+    InsertIgnoreDuplicatesExt, MongoDbCollection, MongoDbCollectionExt, VersionedCollection, VersionedCollectionExt,
+    SCHEMA_VERSION_FIELD,
+};
 
 /// A handle to the underlying `MongoDB` database.
 #[derive(Clone, Debug)]
 pub struct MongoDb {
     pub(crate) database_name: String,
     pub(crate) client: mongodb::Client,
+    /// The servers that reads are routed to. Uses the primary if not set.
+    pub(crate) selection_criteria: Option<SelectionCriteria>,
+    /// Whether the blocks collection is created with zstd compression.
+    pub(crate) compress_blocks: bool,
+}
+
+/// Fails with a message naming the file if it cannot be read, rather than with the less specific error of the driver
+/// once it tries to connect.
+fn check_readable(description: &str, path: &std::path::Path) -> Result<(), Error> {
+    std::fs::File::open(path).map(drop).map_err(|e| {
+        mongodb::error::ErrorKind::from(std::io::Error::new(
+            e.kind(),
+            format!("cannot read the MongoDb {description} `{}`: {e}", path.display()),
+        ))
+        .into()
+    })
 }
 
 impl MongoDb {
     /// Constructs a [`MongoDb`] by connecting to a MongoDB instance.
+    ///
+    /// The TLS and authentication options of the config take precedence over those of the connection string.
     pub async fn connect(config: &MongoDbConfig) -> Result<Self, Error> {
         let mut client_options = ClientOptions::parse(&config.conn_str).await?;
 
         client_options.app_name = Some(crate::CHRONICLE_APP_NAME.to_string());
 
+        if config.tls_ca_file.is_some() || config.tls_cert_key_file.is_some() {
+            let mut tls_options = match client_options.tls.take() {
+                Some(Tls::Enabled(tls_options)) => tls_options,
+                _ => TlsOptions::default(),
+            };
+            if let Some(path) = &config.tls_ca_file {
+                check_readable("TLS CA file", path)?;
+                tls_options.ca_file_path = Some(path.clone());
+            }
+            if let Some(path) = &config.tls_cert_key_file {
+                check_readable("TLS certificate key file", path)?;
+                tls_options.cert_key_file_path = Some(path.clone());
+            }
+            client_options.tls = Some(Tls::Enabled(tls_options));
+        }
+
+        if config.auth_mechanism.is_some() || config.auth_source.is_some() {
+            let credential = client_options.credential.get_or_insert_with(Credential::default);
+            if let Some(mechanism) = config.auth_mechanism {
+                credential.mechanism = Some(mechanism.into());
+            }
+            if let Some(source) = &config.auth_source {
+                credential.source = Some(source.clone());
+            }
+        }
+
         let client = Client::with_options(client_options)?;
 
         Ok(Self {
             database_name: config.database_name.clone(),
             client,
+            selection_criteria: None,
+            compress_blocks: config.compress_blocks,
         })
     }
 
+    /// Returns a handle to the same database that routes reads according to the given read preference.
+    ///
+    /// Reads from secondaries of a replica set may lag behind the primary, so they can observe a slightly older
+    /// ledger index than the one that was last written.
+    pub fn with_read_preference(&self, read_preference: ReadPreference) -> Self {
+        Self {
+            selection_criteria: Some(SelectionCriteria::ReadPreference(read_preference)),
+            ..self.clone()
+        }
+    }
+
     /// Returns the current database.
     pub fn db(&self) -> mongodb::Database {
-        self.client.database(&self.database_name)
+        self.client.database_with_options(
+            &self.database_name,
+            DatabaseOptions::builder()
+                .selection_criteria(self.selection_criteria.clone())
+                .build(),
+        )
     }
 
     /// Creates a collection if it does not exist.
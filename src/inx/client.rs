@@ -3,9 +3,10 @@
 
 use futures::stream::{Stream, StreamExt};
 use inx::{client::InxClient, proto};
+use tracing::warn;
 
 use super::{
-    block::BlockWithMetadataMessage,
+    block::{BlockMessage, BlockWithMetadataMessage},
     ledger::UnspentOutputMessage,
     milestone::{MilestoneAndProtocolParametersMessage, MilestoneMessage},
     node::NodeConfigurationMessage,
@@ -13,10 +14,34 @@ use super::{
     InxError, LedgerUpdateMessage, MilestoneRangeRequest, NodeStatusMessage, RawProtocolParametersMessage,
 };
 
+/// How outputs of a kind that is unknown to Chronicle are treated when they are received from the node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum UnknownOutputPolicy {
+    /// Stop with an error, so that no part of the ledger goes missing unnoticed.
+    Fail,
+    /// Log a warning and leave the output out of the ledger.
+    Skip,
+}
+
+impl UnknownOutputPolicy {
+    /// Filters a converted message according to this policy. Returns `None` if the message contained an output of an
+    /// unknown kind that should be skipped.
+    fn filter<T>(self, msg: Result<T, InxError>) -> Option<Result<T, InxError>> {
+        match msg {
+            Err(InxError::UnknownOutputKind(kind)) if self == UnknownOutputPolicy::Skip => {
+                warn!("Skipping output of unknown kind {kind}.");
+                None
+            }
+            msg => Some(msg),
+        }
+    }
+}
+
 /// An INX client connection.
 #[derive(Clone, Debug)]
 pub struct Inx {
     inx: InxClient<inx::tonic::transport::Channel>,
+    unknown_outputs: UnknownOutputPolicy,
 }
 
 fn unpack_proto_msg<Proto, T>(msg: Result<Proto, tonic::Status>) -> Result<T, InxError>
@@ -32,9 +57,22 @@ impl Inx {
     pub async fn connect(address: String) -> Result<Self, InxError> {
         Ok(Self {
             inx: InxClient::connect(address).await?,
+            unknown_outputs: UnknownOutputPolicy::Fail,
         })
     }
 
+    /// Sets how outputs of unknown kinds are treated.
+    pub fn with_unknown_output_policy(mut self, policy: UnknownOutputPolicy) -> Self {
+        self.unknown_outputs = policy;
+        self
+    }
+
+    /// Filters a converted message according to the [`UnknownOutputPolicy`] of this connection. Returns `None` if the
+    /// message contained an output of an unknown kind that should be skipped.
+    pub(crate) fn filter_unknown_output<T>(&self, msg: Result<T, InxError>) -> Option<Result<T, InxError>> {
+        self.unknown_outputs.filter(msg)
+    }
+
     /// Convenience wrapper that listen to ledger updates as a stream of
     /// [`MilestoneAndProtocolParametersMessages`](MilestoneAndProtocolParametersMessage).
     pub async fn listen_to_confirmed_milestones(
@@ -76,12 +114,13 @@ impl Inx {
     pub async fn read_unspent_outputs(
         &mut self,
     ) -> Result<impl Stream<Item = Result<UnspentOutputMessage, InxError>>, InxError> {
+        let this = self.clone();
         Ok(self
             .inx
             .read_unspent_outputs(proto::NoParams {})
             .await?
             .into_inner()
-            .map(unpack_proto_msg))
+            .filter_map(move |msg| futures::future::ready(this.filter_unknown_output(unpack_proto_msg(msg)))))
     }
 
     /// Convenience wrapper that reads the protocol parameters for a given milestone into a
@@ -112,6 +151,17 @@ impl Inx {
             .map(unpack_proto_msg))
     }
 
+    /// Convenience wrapper that listens to the blocks the node receives as a stream of
+    /// [`BlockMessages`](BlockMessage), whether or not they are referenced by a milestone.
+    pub async fn listen_to_blocks(&mut self) -> Result<impl Stream<Item = Result<BlockMessage, InxError>>, InxError> {
+        Ok(self
+            .inx
+            .listen_to_blocks(proto::NoParams {})
+            .await?
+            .into_inner()
+            .map(unpack_proto_msg))
+    }
+
     /// Convenience wrapper that reads the information for a given milestone.
     pub async fn read_milestone(&mut self, request: MilestoneRequest) -> Result<MilestoneMessage, InxError> {
         MilestoneMessage::try_from(
@@ -122,3 +172,30 @@ impl Inx {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fail_on_unknown_output() {
+        let policy = UnknownOutputPolicy::Fail;
+        assert!(matches!(policy.filter(Ok(1)), Some(Ok(1))));
+        assert!(matches!(
+            policy.filter::<()>(Err(InxError::UnknownOutputKind(42))),
+            Some(Err(InxError::UnknownOutputKind(42)))
+        ));
+    }
+
+    #[test]
+    fn skip_unknown_output() {
+        let policy = UnknownOutputPolicy::Skip;
+        assert!(matches!(policy.filter(Ok(1)), Some(Ok(1))));
+        assert!(policy.filter::<()>(Err(InxError::UnknownOutputKind(42))).is_none());
+        // Other errors are never skipped.
+        assert!(matches!(
+            policy.filter::<()>(Err(InxError::MissingField("output"))),
+            Some(Err(InxError::MissingField("output")))
+        ));
+    }
+}
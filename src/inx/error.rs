@@ -13,6 +13,8 @@ pub enum InxError {
     InvalidRawBytes(String),
     #[error("missing field: {0}")]
     MissingField(&'static str),
+    #[error("unknown output kind: {0}")]
+    UnknownOutputKind(u8),
     #[error("gRPC status code: {0}")]
     StatusCode(#[from] tonic::Status),
     #[error(transparent)]
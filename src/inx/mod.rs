@@ -16,7 +16,7 @@ mod request;
 
 pub use self::{
     block::{BlockMessage, BlockMetadataMessage, BlockWithMetadataMessage},
-    client::Inx,
+    client::{Inx, UnknownOutputPolicy},
     error::InxError,
     ledger::{LedgerUpdateMessage, MarkerMessage, UnspentOutputMessage},
     milestone::MilestoneAndProtocolParametersMessage,
@@ -25,6 +25,7 @@ pub use self::{
     raw::RawMessage,
     request::MilestoneRangeRequest,
 };
+pub(crate) use self::raw::unpack_error;
 
 /// Tries to access the field of a protobug messages and returns an appropriate error if the field is not present.
 #[macro_export]
@@ -1,10 +1,10 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::marker::PhantomData;
+use std::{fmt::Debug, marker::PhantomData};
 
 use inx::proto;
-use packable::{Packable, PackableExt};
+use packable::{error::UnpackError, Packable, PackableExt};
 
 use super::InxError;
 
@@ -31,13 +31,25 @@ impl<T: Packable> RawMessage<T> {
     }
 
     /// Unpack the raw data into a type `T` without performing syntactic or semantic validation. This is useful if the
-    /// type is guaranteed to be well-formed, for example when it was transmitted via the INX interface.
-    pub fn inner_unverified(self) -> Result<T, InxError> {
-        let unpacked = T::unpack_unverified(self.data).map_err(|e| InxError::InvalidRawBytes(format!("{e:?}")))?;
+    /// type is guaranteed to be well-formed, for example when it was transmitted via the INX interface. Outputs of a
+    /// kind that is unknown to Chronicle are reported as [`InxError::UnknownOutputKind`].
+    pub fn inner_unverified(self) -> Result<T, InxError>
+    where
+        T: Packable<UnpackError = iota_types::block::Error>,
+    {
+        let unpacked = T::unpack_unverified(self.data).map_err(unpack_error)?;
         Ok(unpacked)
     }
 }
 
+/// Converts an error from unpacking raw bytes, telling outputs of an unknown kind apart from malformed data.
+pub(crate) fn unpack_error<E: Debug>(e: UnpackError<iota_types::block::Error, E>) -> InxError {
+    match e {
+        UnpackError::Packable(iota_types::block::Error::InvalidOutputKind(kind)) => InxError::UnknownOutputKind(kind),
+        e => InxError::InvalidRawBytes(format!("{e:?}")),
+    }
+}
+
 impl<T: Packable> From<Vec<u8>> for RawMessage<T> {
     fn from(value: Vec<u8>) -> Self {
         Self {
@@ -103,6 +115,12 @@ mod test {
         assert_eq!(output, raw.inner(&protocol_parameters).unwrap());
     }
 
+    #[test]
+    fn raw_output_of_unknown_kind() {
+        let raw = RawMessage::<iota_types::block::output::Output>::from(vec![42]);
+        assert!(matches!(raw.inner_unverified(), Err(InxError::UnknownOutputKind(42))));
+    }
+
     #[test]
     fn raw_milestone() {
         // The `RawMilestone` field in the protobuf definitions contains a `Payload`.
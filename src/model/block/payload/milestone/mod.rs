@@ -293,12 +293,12 @@ impl From<MilestoneOption> for iota::option::dto::MilestoneOptionDto {
 pub struct MigratedFundsEntry {
     /// The tail transaction hash of the bundle in which these funds were migrated.
     #[serde(with = "bytify")]
-    tail_transaction_hash: [u8; Self::TAIL_TRANSACTION_HASH_LENGTH],
+    pub tail_transaction_hash: [u8; Self::TAIL_TRANSACTION_HASH_LENGTH],
     /// The target address.
-    address: Address,
+    pub address: Address,
     /// The amount of tokens that have been migrated.
     #[serde(with = "stringify")]
-    amount: u64,
+    pub amount: u64,
 }
 
 impl MigratedFundsEntry {
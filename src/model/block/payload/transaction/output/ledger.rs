@@ -92,15 +92,17 @@ mod inx {
     use packable::PackableExt;
 
     use super::*;
-    use crate::{inx::InxError, maybe_missing};
+    use crate::{
+        inx::{unpack_error, InxError},
+        maybe_missing,
+    };
 
     impl TryFrom<::inx::proto::LedgerOutput> for LedgerOutput {
         type Error = InxError;
 
         fn try_from(value: ::inx::proto::LedgerOutput) -> Result<Self, Self::Error> {
             let data = maybe_missing!(value.output).data;
-            let bee_output = iota_types::block::output::Output::unpack_unverified(data)
-                .map_err(|e| InxError::InvalidRawBytes(format!("{e:?}")))?;
+            let bee_output = iota_types::block::output::Output::unpack_unverified(data).map_err(unpack_error)?;
 
             Ok(Self {
                 rent_structure: RentStructureBytes::compute(&bee_output),
@@ -129,6 +131,8 @@ mod inx {
                         milestone_index: value.milestone_index_spent.into(),
                         milestone_timestamp: value.milestone_timestamp_spent.into(),
                     },
+                    // The node does not report it, so it is taken from the spending transaction instead.
+                    input_index: None,
                 },
             })
         }
@@ -14,6 +14,10 @@ use crate::model::{
 pub struct SpentMetadata {
     pub transaction_id: TransactionId,
     pub spent: MilestoneIndexTimestamp,
+    /// The position of the output among the inputs of the spending transaction. Outputs that were spent before it
+    /// was recorded do not have it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_index: Option<u16>,
 }
 
 /// Block metadata.
@@ -23,3 +27,33 @@ pub struct OutputMetadata {
     pub booked: MilestoneIndexTimestamp,
     pub spent_metadata: Option<SpentMetadata>,
 }
+
+#[cfg(all(test, feature = "rand"))]
+mod test {
+    use mongodb::bson::{from_bson, to_bson};
+
+    use super::*;
+
+    #[test]
+    fn test_spent_metadata_without_input_index() {
+        let spent_metadata = SpentMetadata {
+            transaction_id: TransactionId::rand(),
+            spent: MilestoneIndexTimestamp {
+                milestone_index: 1.into(),
+                milestone_timestamp: 12345.into(),
+            },
+            input_index: None,
+        };
+        let bson = to_bson(&spent_metadata).unwrap();
+        // Documents written before the input index was recorded do not have the field.
+        assert!(!bson.as_document().unwrap().contains_key("input_index"));
+        assert_eq!(spent_metadata, from_bson::<SpentMetadata>(bson).unwrap());
+
+        let spent_metadata = SpentMetadata {
+            input_index: Some(3),
+            ..spent_metadata
+        };
+        let bson = to_bson(&spent_metadata).unwrap();
+        assert_eq!(spent_metadata, from_bson::<SpentMetadata>(bson).unwrap());
+    }
+}
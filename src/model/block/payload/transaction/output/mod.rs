@@ -29,7 +29,7 @@ pub use self::{
     basic::BasicOutput,
     feature::Feature,
     foundry::{FoundryId, FoundryOutput},
-    native_token::{NativeToken, NativeTokenAmount, TokenScheme},
+    native_token::{NativeToken, NativeTokenAmount, NativeTokenId, TokenScheme},
     nft::{NftId, NftOutput},
     treasury::TreasuryOutput,
 };
@@ -14,8 +14,10 @@ use crate::model::{tangle::MilestoneTimestamp, utxo::Address};
 /// After or at the unix time, only Return Address can unlock it.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExpirationUnlockCondition {
-    return_address: Address,
-    timestamp: MilestoneTimestamp,
+    /// The address that can unlock the output after the expiration time.
+    pub return_address: Address,
+    /// The unix time at which the output expires.
+    pub timestamp: MilestoneTimestamp,
 }
 
 impl<T: Borrow<iota::ExpirationUnlockCondition>> From<T> for ExpirationUnlockCondition {
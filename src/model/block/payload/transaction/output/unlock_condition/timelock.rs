@@ -13,7 +13,8 @@ use crate::model::tangle::MilestoneTimestamp;
 /// Defines a unix timestamp until which the output can not be unlocked.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TimelockUnlockCondition {
-    timestamp: MilestoneTimestamp,
+    /// The unix time until which the output can not be unlocked.
+    pub timestamp: MilestoneTimestamp,
 }
 
 impl<T: Borrow<iota::TimelockUnlockCondition>> From<T> for TimelockUnlockCondition {
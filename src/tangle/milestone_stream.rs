@@ -22,6 +22,7 @@ use crate::model::{
 #[allow(missing_docs)]
 pub struct Milestone<'a, I: InputSource> {
     pub(super) source: &'a I,
+    pub(super) cone_batch_size: Option<u32>,
     pub milestone_id: MilestoneId,
     pub at: MilestoneIndexTimestamp,
     pub payload: MilestonePayload,
@@ -33,7 +34,9 @@ pub struct Milestone<'a, I: InputSource> {
 impl<'a, I: InputSource> Milestone<'a, I> {
     /// Returns the blocks of a milestone in white-flag order.
     pub async fn cone_stream(&self) -> Result<BoxStream<Result<BlockData, I::Error>>, I::Error> {
-        self.source.cone_stream(self.at.milestone_index).await
+        self.source
+            .cone_stream(self.at.milestone_index, self.cone_batch_size)
+            .await
     }
 
     /// Returns the ledger update store.
@@ -20,12 +20,14 @@ use crate::model::tangle::MilestoneIndex;
 /// Provides access to the tangle.
 pub struct Tangle<I: InputSource> {
     source: I,
+    cone_batch_size: Option<u32>,
 }
 
 impl<I: InputSource + Clone> Clone for Tangle<I> {
     fn clone(&self) -> Self {
         Self {
             source: self.source.clone(),
+            cone_batch_size: self.cone_batch_size,
         }
     }
 }
@@ -33,7 +35,19 @@ impl<I: InputSource + Copy> Copy for Tangle<I> {}
 
 impl<I: InputSource> From<I> for Tangle<I> {
     fn from(source: I) -> Self {
-        Self { source }
+        Self {
+            source,
+            cone_batch_size: None,
+        }
+    }
+}
+
+impl<I: InputSource> Tangle<I> {
+    /// Sets the number of blocks fetched per round-trip when streaming milestone cones. `None` uses the input
+    /// source's default.
+    pub fn with_cone_batch_size(mut self, cone_batch_size: Option<u32>) -> Self {
+        self.cone_batch_size = cone_batch_size;
+        self
     }
 }
 
@@ -49,10 +63,12 @@ impl<I: InputSource + Sync> Tangle<I> {
                 .and_then(|data| {
                     #[allow(clippy::borrow_deref_ref)]
                     let source = &self.source;
+                    let cone_batch_size = self.cone_batch_size;
                     async move {
                         Ok(Milestone {
                             ledger_updates: source.ledger_updates(data.at.milestone_index).await?,
                             source,
+                            cone_batch_size,
                             milestone_id: data.milestone_id,
                             at: data.at,
                             payload: data.payload,
@@ -70,20 +70,26 @@ impl InputSource for Inx {
     async fn cone_stream(
         &self,
         index: MilestoneIndex,
+        // The node pushes the cone over a single gRPC stream, so there are no pages to size.
+        _batch_size: Option<u32>,
     ) -> Result<BoxStream<Result<BlockData, Self::Error>>, Self::Error> {
         let mut inx = self.clone();
+        let this = self.clone();
         Ok(Box::pin(
             inx.read_milestone_cone(index.0.into())
                 .await?
-                .map_err(Self::Error::from)
-                .and_then(|msg| async move {
+                .map(|msg| -> Result<_, InxError> {
+                    let msg = msg?;
                     Ok(BlockData {
                         block_id: msg.metadata.block_id,
                         block: msg.block.clone().inner_unverified()?.into(),
                         raw: msg.block.data(),
                         metadata: msg.metadata.into(),
                     })
-                }),
+                })
+                // A block whose transaction creates an output of an unknown kind can not be decoded at all.
+                .filter_map(move |block| futures::future::ready(this.filter_unknown_output(block)))
+                .map_err(Self::Error::from),
         ))
     }
 
@@ -104,12 +110,14 @@ impl InputSource for Inx {
         let consumed = stream
             .by_ref()
             .take(consumed_count)
+            .filter_map(|update| futures::future::ready(self.filter_unknown_output(update)))
             .map(|update| update?.consumed().ok_or(Self::Error::UnexpectedMessage))
             .try_collect()
             .await?;
 
         let created = stream
             .take(created_count)
+            .filter_map(|update| futures::future::ready(self.filter_unknown_output(update)))
             .map(|update| update?.created().ok_or(Self::Error::UnexpectedMessage))
             .try_collect()
             .await?;
@@ -38,6 +38,7 @@ impl InputSource for BTreeMap<MilestoneIndex, InMemoryData> {
     async fn cone_stream(
         &self,
         index: MilestoneIndex,
+        _batch_size: Option<u32>,
     ) -> Result<BoxStream<Result<BlockData, Self::Error>>, Self::Error> {
         let cone = &self
             .get(&index)
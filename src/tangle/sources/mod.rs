@@ -54,9 +54,13 @@ pub trait InputSource: Send + Sync {
     ) -> Result<BoxStream<Result<MilestoneData, Self::Error>>, Self::Error>;
 
     /// Retrieves a stream of blocks and their metadata in white-flag order given a milestone index.
+    ///
+    /// The `batch_size` is the number of blocks fetched per round-trip, if the source supports paging. `None` uses the
+    /// source's default.
     async fn cone_stream(
         &self,
         index: MilestoneIndex,
+        batch_size: Option<u32>,
     ) -> Result<BoxStream<Result<BlockData, Self::Error>>, Self::Error>;
 
     /// Retrieves the updates to the ledger for a given milestone.
@@ -92,10 +92,11 @@ impl InputSource for MongoDb {
     async fn cone_stream(
         &self,
         index: MilestoneIndex,
+        batch_size: Option<u32>,
     ) -> Result<BoxStream<Result<BlockData, Self::Error>>, Self::Error> {
         Ok(Box::pin(
             self.collection::<BlockCollection>()
-                .get_referenced_blocks_in_white_flag_order_stream(index)
+                .get_referenced_blocks_in_white_flag_order_stream(index, batch_size)
                 .await?
                 .map_err(|e| e.into())
                 .map_ok(|(block_id, block, raw, metadata)| BlockData {
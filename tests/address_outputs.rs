@@ -0,0 +1,169 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+#[cfg(feature = "rand")]
+mod test_rand {
+    use std::collections::HashMap;
+
+    use chronicle::{
+        db::{
+            mongodb::collections::{AddressOutputCollection, OutputCollection, SortOrder},
+            MongoDbCollection,
+        },
+        model::{
+            ledger::{LedgerOutput, LedgerSpent, RentStructureBytes},
+            metadata::SpentMetadata,
+            payload::TransactionId,
+            tangle::MilestoneIndexTimestamp,
+            utxo::{Address, BasicOutput, Output, OutputId, TokenAmount},
+            BlockId,
+        },
+    };
+    use futures::TryStreamExt;
+
+    use super::common::{setup_collection, setup_database, teardown};
+
+    #[tokio::test]
+    async fn test_address_output_balances() {
+        let db = setup_database("test-address-output-balances").await.unwrap();
+        let output_collection = setup_collection::<OutputCollection>(&db).await.unwrap();
+        let address_output_collection = setup_collection::<AddressOutputCollection>(&db).await.unwrap();
+
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+
+        let (first, second) = (Address::rand_ed25519(), Address::rand_ed25519());
+        let ledger_output = |address, amount, milestone_index: u32| {
+            let mut output = BasicOutput::rand(&protocol_params);
+            output.address_unlock_condition.address = address;
+            output.amount = TokenAmount(amount);
+            LedgerOutput {
+                output_id: OutputId::rand(),
+                rent_structure: RentStructureBytes {
+                    num_key_bytes: 0,
+                    num_data_bytes: 100,
+                },
+                output: Output::Basic(output),
+                block_id: BlockId::rand(),
+                booked: MilestoneIndexTimestamp {
+                    milestone_index: milestone_index.into(),
+                    milestone_timestamp: (12345 + milestone_index).into(),
+                },
+            }
+        };
+
+        let unspent = [
+            ledger_output(first, 100, 1),
+            ledger_output(first, 200, 3),
+            ledger_output(second, 400, 1),
+        ];
+        let spent = [LedgerSpent {
+            output: ledger_output(first, 800, 1),
+            spent_metadata: SpentMetadata {
+                transaction_id: TransactionId::rand(),
+                spent: MilestoneIndexTimestamp {
+                    milestone_index: 2.into(),
+                    milestone_timestamp: 12347.into(),
+                },
+                input_index: None,
+            },
+        }];
+        let outputs = unspent.iter().chain(spent.iter().map(|s| &s.output));
+
+        output_collection.insert_unspent_outputs(outputs.clone()).await.unwrap();
+        output_collection.update_spent_outputs(&spent).await.unwrap();
+        address_output_collection.insert_unspent_outputs(outputs).await.unwrap();
+        address_output_collection.update_spent_outputs(&spent).await.unwrap();
+
+        let check_queries = || async {
+            for (ledger_index, expected) in [(1, "900"), (2, "100"), (3, "300")] {
+                let indexed = address_output_collection
+                    .get_address_balance(first, ledger_index.into(), None)
+                    .await
+                    .unwrap()
+                    .unwrap();
+                let scanned = output_collection
+                    .get_address_balance(first, ledger_index.into(), None)
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(indexed.total_balance, expected);
+                assert_eq!(indexed.total_balance, scanned.total_balance);
+                assert_eq!(indexed.sig_locked_balance, scanned.sig_locked_balance);
+            }
+
+            let balances = address_output_collection
+                .get_address_balances(&[first, second], 3.into(), None)
+                .await
+                .unwrap()
+                .map_ok(|res| (res.address, res.total_balance))
+                .try_collect::<HashMap<_, _>>()
+                .await
+                .unwrap();
+            assert_eq!(
+                balances,
+                HashMap::from([(first, "300".to_string()), (second, "400".to_string())])
+            );
+
+            let summary = address_output_collection
+                .get_address_summary(first, 3.into(), None)
+                .await
+                .unwrap();
+            assert_eq!(summary.basic_count, 2);
+            assert_eq!(summary.total_balance, "300");
+
+            let indexed = address_output_collection
+                .get_spent_outputs_by_address(first, 10, None, SortOrder::Newest, 3.into())
+                .await
+                .unwrap()
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+            let scanned = output_collection
+                .get_spent_outputs_by_address(first, 10, None, SortOrder::Newest, 3.into())
+                .await
+                .unwrap()
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+            assert_eq!(indexed.len(), 1);
+            assert_eq!(indexed, scanned);
+        };
+        check_queries().await;
+
+        // Only the given number of outputs are summed.
+        let limited = output_collection
+            .get_address_balance(first, 3.into(), Some(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(limited.output_count, 1);
+
+        // The limit applies to every address of a multi-address query.
+        let limited = output_collection
+            .get_address_balances(&[first, second], 3.into(), Some(1))
+            .await
+            .unwrap()
+            .map_ok(|res| (res.address, res.output_count))
+            .try_collect::<HashMap<_, _>>()
+            .await
+            .unwrap();
+        assert_eq!(limited, HashMap::from([(first, 1), (second, 1)]));
+        let limited = address_output_collection
+            .get_address_balances(&[first, second], 3.into(), Some(1))
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert!(limited.iter().all(|res| res.output_count == 1));
+
+        // Rebuilding the index from the outputs collection yields the same balances.
+        address_output_collection.collection().drop(None).await.unwrap();
+        address_output_collection.backfill_from_outputs().await.unwrap();
+        check_queries().await;
+
+        teardown(db).await;
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+#[cfg(feature = "api")]
+mod test_api_only {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        process::{Child, Command, Stdio},
+        time::{Duration, Instant},
+    };
+
+    use super::common::{setup_database, teardown};
+
+    /// Kills the Chronicle process when the test ends, even if it fails.
+    struct ChronicleProcess(Child);
+
+    impl Drop for ChronicleProcess {
+        fn drop(&mut self) {
+            self.0.kill().ok();
+            self.0.wait().ok();
+        }
+    }
+
+    fn get(port: u16, path: &str) -> std::io::Result<String> {
+        let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"
+        )?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    }
+
+    #[tokio::test]
+    async fn test_api_without_influxdb() {
+        let db = setup_database("test-api-without-influxdb").await.unwrap();
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+        let mut command = Command::new(env!("CARGO_BIN_EXE_inx-chronicle"));
+        command
+            .args(["--mongodb-database-name", db.name(), "--api-port", &port.to_string()])
+            // Nothing listens on this port, so any attempt to reach InfluxDb fails.
+            .args(["--influxdb-url", "http://127.0.0.1:1"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        #[cfg(feature = "inx")]
+        command.arg("--disable-inx");
+        #[cfg(feature = "analytics")]
+        command.arg("--disable-analytics");
+        #[cfg(feature = "metrics")]
+        command.arg("--disable-metrics");
+        let mut chronicle = ChronicleProcess(command.spawn().unwrap());
+
+        let start = Instant::now();
+        let response = loop {
+            if let Some(status) = chronicle.0.try_wait().unwrap() {
+                panic!("Chronicle exited with {status}");
+            }
+            match get(port, "/routes") {
+                Ok(response) => break response,
+                Err(_) if start.elapsed() < Duration::from_secs(30) => {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                Err(e) => panic!("the API did not start: {e}"),
+            }
+        };
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+
+        drop(chronicle);
+        teardown(db).await;
+    }
+}
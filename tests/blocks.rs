@@ -8,7 +8,10 @@ mod test_rand {
     use std::{collections::HashSet, fs::File, io::BufReader};
 
     use chronicle::{
-        db::{mongodb::collections::BlockCollection, MongoDbCollectionExt},
+        db::{
+            mongodb::collections::{BlockCollection, UnreferencedBlockCollection},
+            MongoDbCollectionExt,
+        },
         model::{
             metadata::{BlockMetadata, ConflictReason, LedgerInclusionState},
             payload::Payload,
@@ -293,4 +296,75 @@ mod test_rand {
 
         teardown(db).await;
     }
+
+    #[tokio::test]
+    async fn test_unreferenced_blocks() {
+        let db = setup_database("test-unreferenced-blocks").await.unwrap();
+        let unreferenced_collection = setup_collection::<UnreferencedBlockCollection>(&db).await.unwrap();
+
+        let blocks = std::iter::repeat_with(|| {
+            (
+                BlockId::rand(),
+                Block::rand_no_payload(),
+                iota_types::block::rand::bytes::rand_bytes(100),
+            )
+        })
+        .take(3)
+        .collect::<Vec<_>>();
+
+        unreferenced_collection.insert_blocks(blocks.clone()).await.unwrap();
+        // Blocks that are received again are ignored.
+        unreferenced_collection
+            .insert_blocks(blocks[..1].to_vec())
+            .await
+            .unwrap();
+
+        for (block_id, _, raw) in &blocks {
+            assert!(unreferenced_collection.contains_block(block_id).await.unwrap());
+            assert_eq!(
+                unreferenced_collection.get_block_raw(block_id).await.unwrap().as_ref(),
+                Some(raw)
+            );
+        }
+
+        unreferenced_collection
+            .remove_blocks(blocks[..2].iter().map(|(block_id, _, _)| block_id))
+            .await
+            .unwrap();
+
+        assert!(!unreferenced_collection.contains_block(&blocks[0].0).await.unwrap());
+        assert!(!unreferenced_collection.contains_block(&blocks[1].0).await.unwrap());
+        assert!(unreferenced_collection.contains_block(&blocks[2].0).await.unwrap());
+
+        // A block that arrives after its milestone cone was stored is not kept as unreferenced.
+        let block_collection = setup_collection::<BlockCollection>(&db).await.unwrap();
+        let (block_id, block, raw) = blocks[0].clone();
+        let parents = block.parents.clone();
+        block_collection
+            .insert_blocks_with_metadata([(
+                block_id,
+                block,
+                raw,
+                BlockMetadata {
+                    parents,
+                    is_solid: true,
+                    should_promote: false,
+                    should_reattach: false,
+                    referenced_by_milestone_index: 1.into(),
+                    milestone_index: 0.into(),
+                    inclusion_state: LedgerInclusionState::NoTransaction,
+                    conflict_reason: ConflictReason::None,
+                    white_flag_index: 0,
+                },
+            )])
+            .await
+            .unwrap();
+        unreferenced_collection
+            .insert_blocks(blocks[..1].to_vec())
+            .await
+            .unwrap();
+        assert!(!unreferenced_collection.contains_block(&blocks[0].0).await.unwrap());
+
+        teardown(db).await;
+    }
 }
@@ -10,7 +10,8 @@ mod test_rand {
     use chronicle::{
         db::{
             mongodb::collections::{
-                LedgerUpdateByAddressRecord, LedgerUpdateByMilestoneRecord, LedgerUpdateCollection, SortOrder,
+                LedgerDiffRecord, LedgerUpdateByAddressRecord, LedgerUpdateByMilestoneRecord, LedgerUpdateCollection,
+                OutputCollection, SortOrder,
             },
             MongoDbCollectionExt,
         },
@@ -116,6 +117,21 @@ mod test_rand {
         }
         assert!(outputs.is_empty());
 
+        assert_eq!(
+            update_collection
+                .count_ledger_updates_by_address(&address, 0.into(), None)
+                .await
+                .unwrap(),
+            50
+        );
+        assert_eq!(
+            update_collection
+                .count_ledger_updates_by_address(&address, 0.into(), Some(10))
+                .await
+                .unwrap(),
+            10
+        );
+
         teardown(db).await;
     }
 
@@ -158,7 +174,7 @@ mod test_rand {
         assert_eq!(update_collection.count().await.unwrap(), 100);
 
         let mut s = update_collection
-            .get_ledger_updates_by_milestone(0.into(), 100, None)
+            .get_ledger_updates_by_milestone(0.into(), None, 100, None)
             .await
             .unwrap();
 
@@ -171,6 +187,27 @@ mod test_rand {
         }
         assert_eq!(outputs.len(), 95);
 
+        // The kind filter looks up the outputs themselves.
+        setup_collection::<OutputCollection>(&db)
+            .await
+            .unwrap()
+            .insert_unspent_outputs(ledger_outputs.iter())
+            .await
+            .unwrap();
+        for (kind, count) in [("basic", 5), ("nft", 0)] {
+            assert_eq!(
+                update_collection
+                    .get_ledger_updates_by_milestone(0.into(), Some(kind), 100, None)
+                    .await
+                    .unwrap()
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .unwrap()
+                    .len(),
+                count
+            );
+        }
+
         teardown(db).await;
     }
 
@@ -218,6 +255,7 @@ mod test_rand {
                                 milestone_index: 1.into(),
                                 milestone_timestamp: 20000.into(),
                             },
+                            input_index: None,
                         },
                     })
                 } else {
@@ -235,4 +273,98 @@ mod test_rand {
 
         teardown(db).await;
     }
+
+    #[tokio::test]
+    async fn test_ledger_diff() {
+        let db = setup_database("test-ledger-diff").await.unwrap();
+        let update_collection = setup_collection::<LedgerUpdateCollection>(&db).await.unwrap();
+
+        let ctx = iota_types::block::protocol::protocol_parameters();
+
+        // Half of the outputs exist before the range, the other half are created within it.
+        let outputs = std::iter::repeat_with(|| (BlockId::rand(), Output::rand_basic(&ctx), OutputId::rand()))
+            .take(20)
+            .enumerate()
+            .map(|(i, (block_id, output, output_id))| LedgerOutput {
+                block_id,
+                booked: MilestoneIndexTimestamp {
+                    milestone_index: (i as u32 / 10).into(),
+                    milestone_timestamp: (10000 + i as u32 / 10).into(),
+                },
+                output,
+                output_id,
+                rent_structure: RentStructureBytes {
+                    num_key_bytes: 0,
+                    num_data_bytes: 100,
+                },
+            })
+            .collect::<Vec<_>>();
+
+        update_collection
+            .insert_unspent_ledger_updates(outputs.iter())
+            .await
+            .unwrap();
+
+        // Every other output of both halves is consumed within the range.
+        let spent_outputs = outputs
+            .iter()
+            .step_by(2)
+            .map(|output| LedgerSpent {
+                output: output.clone(),
+                spent_metadata: SpentMetadata {
+                    transaction_id: OutputId::rand().transaction_id,
+                    spent: MilestoneIndexTimestamp {
+                        milestone_index: 2.into(),
+                        milestone_timestamp: 10002.into(),
+                    },
+                    input_index: None,
+                },
+            })
+            .collect::<Vec<_>>();
+
+        update_collection
+            .insert_spent_ledger_updates(spent_outputs.iter())
+            .await
+            .unwrap();
+
+        let diff = update_collection
+            .get_ledger_diff(0.into(), 2.into(), 100, None)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        // Outputs that were created and consumed within the range are omitted.
+        let mut expected = outputs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, output)| match (i < 10, i % 2 == 0) {
+                (true, true) => Some((output.output_id, true)),
+                (false, false) => Some((output.output_id, false)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        assert_eq!(diff.len(), 10);
+        for LedgerDiffRecord {
+            output_id, is_spent, ..
+        } in diff
+        {
+            assert_eq!(expected.remove(&output_id), Some(is_spent));
+        }
+        assert!(expected.is_empty());
+
+        // Only the consumption of the outputs is within this range.
+        let diff = update_collection
+            .get_ledger_diff(1.into(), 2.into(), 100, None)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(diff.len(), 10);
+        assert!(diff.iter().all(|record| record.is_spent));
+
+        teardown(db).await;
+    }
 }
@@ -6,9 +6,20 @@ mod common;
 #[cfg(feature = "rand")]
 mod test_rand {
     use chronicle::{
-        db::mongodb::collections::MilestoneCollection,
-        model::payload::{MilestoneId, MilestonePayload},
+        db::{
+            mongodb::{
+                collections::{MilestoneCollection, MilestoneFilter, SortOrder},
+                VersionedCollection, VersionedCollectionExt,
+            },
+            MongoDbCollection, MongoDbCollectionExt,
+        },
+        model::{
+            payload::{MilestoneId, MilestoneOption, MilestonePayload},
+            tangle::MilestoneIndex,
+        },
     };
+    use futures::TryStreamExt;
+    use mongodb::bson::doc;
 
     use super::common::{setup_collection, setup_database, teardown};
 
@@ -56,6 +67,150 @@ mod test_rand {
             Some(&milestone)
         );
 
+        let newest = milestone_collection.get_newest_milestone().await.unwrap().unwrap();
+        assert_eq!(newest.milestone_index, milestone.essence.index);
+        assert_eq!(newest.milestone_timestamp, milestone.essence.timestamp);
+
+        teardown(db).await;
+    }
+
+    #[tokio::test]
+    async fn test_milestones_filter() {
+        let db = setup_database("test-milestones-filter").await.unwrap();
+        let milestone_collection = setup_collection::<MilestoneCollection>(&db).await.unwrap();
+
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+        let mut with_receipt = MilestonePayload::rand(&protocol_params);
+        with_receipt.essence.index = 1.into();
+        with_receipt.essence.options = Box::new([MilestoneOption::rand_receipt(&protocol_params)]);
+        let mut without_receipt = MilestonePayload::rand(&protocol_params);
+        without_receipt.essence.index = 2.into();
+        without_receipt.essence.options = Box::new([]);
+        without_receipt.signatures = with_receipt
+            .signatures
+            .iter()
+            .chain(&with_receipt.signatures)
+            .copied()
+            .collect();
+
+        for milestone in [&with_receipt, &without_receipt] {
+            milestone_collection
+                .insert_milestone(
+                    MilestoneId::rand(),
+                    milestone.essence.index,
+                    milestone.essence.timestamp,
+                    milestone.clone(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let indexes = |filter| {
+            let milestone_collection = &milestone_collection;
+            async move {
+                milestone_collection
+                    .get_milestones(None, None, filter, SortOrder::Oldest, 10, None)
+                    .await
+                    .unwrap()
+                    .map_ok(|res| res.index.0)
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .unwrap()
+            }
+        };
+
+        assert_eq!(indexes(MilestoneFilter::default()).await, vec![1, 2]);
+        assert_eq!(
+            indexes(MilestoneFilter {
+                has_receipt: Some(true),
+                ..Default::default()
+            })
+            .await,
+            vec![1]
+        );
+        assert_eq!(
+            indexes(MilestoneFilter {
+                has_receipt: Some(false),
+                ..Default::default()
+            })
+            .await,
+            vec![2]
+        );
+        assert_eq!(
+            indexes(MilestoneFilter {
+                min_signatures: Some(with_receipt.signatures.len() + 1),
+                ..Default::default()
+            })
+            .await,
+            vec![2]
+        );
+
+        teardown(db).await;
+    }
+
+    #[tokio::test]
+    async fn test_milestone_sync_data() {
+        let db = setup_database("test-milestone-sync-data").await.unwrap();
+        let milestone_collection = setup_collection::<MilestoneCollection>(&db).await.unwrap();
+
+        for index in [2, 3, 4, 7, 8] {
+            let milestone = MilestonePayload::rand(&iota_types::block::protocol::protocol_parameters());
+            milestone_collection
+                .insert_milestone(MilestoneId::rand(), index.into(), index.into(), milestone)
+                .await
+                .unwrap();
+        }
+
+        let sync_data = milestone_collection.get_sync_data(1.into()..=10.into()).await.unwrap();
+
+        assert_eq!(
+            sync_data.completed,
+            vec![
+                MilestoneIndex(2)..=MilestoneIndex(4),
+                MilestoneIndex(7)..=MilestoneIndex(8)
+            ]
+        );
+        assert_eq!(
+            sync_data.gaps,
+            vec![
+                MilestoneIndex(1)..=MilestoneIndex(1),
+                MilestoneIndex(5)..=MilestoneIndex(6),
+                MilestoneIndex(9)..=MilestoneIndex(10)
+            ]
+        );
+
+        teardown(db).await;
+    }
+
+    #[tokio::test]
+    async fn test_milestone_schema_upgrade() {
+        let db = setup_database("test-milestone-schema-upgrade").await.unwrap();
+        let milestone_collection = setup_collection::<MilestoneCollection>(&db).await.unwrap();
+
+        for index in 1..=5 {
+            let milestone = MilestonePayload::rand(&iota_types::block::protocol::protocol_parameters());
+            milestone_collection
+                .insert_milestone(MilestoneId::rand(), index.into(), index.into(), milestone)
+                .await
+                .unwrap();
+        }
+        // Simulate documents written before schema versioning was introduced.
+        milestone_collection
+            .update_many(doc! {}, doc! { "$unset": { "schema_version": "" } }, None)
+            .await
+            .unwrap();
+
+        assert_eq!(milestone_collection.upgrade_documents(2).await.unwrap(), 5);
+        assert_eq!(
+            milestone_collection
+                .collection()
+                .count_documents(doc! { "schema_version": MilestoneCollection::SCHEMA_VERSION }, None)
+                .await
+                .unwrap(),
+            5
+        );
+        assert_eq!(milestone_collection.upgrade_documents(2).await.unwrap(), 0);
+
         teardown(db).await;
     }
 }
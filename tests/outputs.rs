@@ -6,16 +6,17 @@ mod common;
 #[cfg(feature = "rand")]
 mod test_rand {
     use chronicle::{
-        db::mongodb::collections::{OutputCollection, OutputMetadataResult, OutputWithMetadataResult},
+        db::mongodb::collections::{OutputCollection, OutputMetadataResult, OutputWithMetadataResult, SortOrder},
         model::{
             ledger::{LedgerOutput, LedgerSpent, RentStructureBytes},
             metadata::SpentMetadata,
             payload::TransactionId,
             tangle::MilestoneIndexTimestamp,
-            utxo::{Output, OutputId},
+            utxo::{Address, BasicOutput, Output, OutputId},
             BlockId,
         },
     };
+    use futures::TryStreamExt;
 
     use super::common::{setup_collection, setup_database, teardown};
 
@@ -106,6 +107,7 @@ mod test_rand {
                         milestone_index: 1.into(),
                         milestone_timestamp: 23456.into(),
                     },
+                    input_index: None,
                 },
             })
             .collect::<Vec<_>>();
@@ -169,4 +171,274 @@ mod test_rand {
 
         teardown(db).await;
     }
+
+    #[tokio::test]
+    async fn test_outputs_by_transaction_id() {
+        let db = setup_database("test-outputs-by-transaction-id").await.unwrap();
+        let output_collection = setup_collection::<OutputCollection>(&db).await.unwrap();
+
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+
+        let transaction_id = TransactionId::rand();
+        let outputs = (0..5)
+            .map(|index| (transaction_id, index).into())
+            .chain([OutputId::rand()])
+            .map(|output_id| LedgerOutput {
+                output_id,
+                rent_structure: RentStructureBytes {
+                    num_key_bytes: 0,
+                    num_data_bytes: 100,
+                },
+                output: Output::rand(&protocol_params),
+                block_id: BlockId::rand(),
+                booked: MilestoneIndexTimestamp {
+                    milestone_index: 1.into(),
+                    milestone_timestamp: 12345.into(),
+                },
+            })
+            .collect::<Vec<_>>();
+
+        output_collection.insert_unspent_outputs(&outputs).await.unwrap();
+
+        let page = |start_index, page_size| {
+            let output_collection = &output_collection;
+            async move {
+                output_collection
+                    .get_outputs_by_transaction_id(transaction_id, start_index, page_size, 1.into())
+                    .await
+                    .unwrap()
+                    .map_ok(|res| res.metadata.output_id.index)
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .unwrap()
+            }
+        };
+
+        assert_eq!(page(0, 10).await, vec![0, 1, 2, 3, 4]);
+        assert_eq!(page(0, 2).await, vec![0, 1]);
+        assert_eq!(page(3, 10).await, vec![3, 4]);
+
+        teardown(db).await;
+    }
+
+    #[tokio::test]
+    async fn test_outputs_with_metadata_by_ids() {
+        let db = setup_database("test-outputs-with-metadata-by-ids").await.unwrap();
+        let output_collection = setup_collection::<OutputCollection>(&db).await.unwrap();
+
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+
+        let outputs = std::iter::repeat_with(|| LedgerOutput {
+            output_id: OutputId::rand(),
+            rent_structure: RentStructureBytes {
+                num_key_bytes: 0,
+                num_data_bytes: 100,
+            },
+            output: Output::rand(&protocol_params),
+            block_id: BlockId::rand(),
+            booked: MilestoneIndexTimestamp {
+                milestone_index: 1.into(),
+                milestone_timestamp: 12345.into(),
+            },
+        })
+        .take(5)
+        .collect::<Vec<_>>();
+
+        output_collection.insert_unspent_outputs(&outputs).await.unwrap();
+
+        let requested = [outputs[0].output_id, outputs[3].output_id, OutputId::rand()];
+        let mut found = output_collection
+            .get_outputs_with_metadata(&requested, 1.into())
+            .await
+            .unwrap()
+            .map_ok(|res| res.metadata.output_id)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        found.sort_by_key(|output_id| output_id.to_hex());
+        let mut expected = vec![outputs[0].output_id, outputs[3].output_id];
+        expected.sort_by_key(|output_id| output_id.to_hex());
+        assert_eq!(found, expected);
+
+        let mut found = output_collection
+            .get_outputs_metadata(&requested, 1.into())
+            .await
+            .unwrap()
+            .map_ok(|res| res.output_id)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        found.sort_by_key(|output_id| output_id.to_hex());
+        assert_eq!(found, expected);
+
+        let found = output_collection
+            .get_outputs_metadata(&requested, 0.into())
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+
+        teardown(db).await;
+    }
+
+    #[tokio::test]
+    async fn test_spent_outputs_by_address() {
+        let db = setup_database("test-spent-outputs-by-address").await.unwrap();
+        let output_collection = setup_collection::<OutputCollection>(&db).await.unwrap();
+
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+
+        let address = Address::rand_ed25519();
+        let ledger_output = |address| {
+            let mut output = BasicOutput::rand(&protocol_params);
+            output.address_unlock_condition.address = address;
+            LedgerOutput {
+                output_id: OutputId::rand(),
+                rent_structure: RentStructureBytes {
+                    num_key_bytes: 0,
+                    num_data_bytes: 100,
+                },
+                output: Output::Basic(output),
+                block_id: BlockId::rand(),
+                booked: MilestoneIndexTimestamp {
+                    milestone_index: 1.into(),
+                    milestone_timestamp: 12345.into(),
+                },
+            }
+        };
+        let spent_at = |output, milestone_index: u32| LedgerSpent {
+            output,
+            spent_metadata: SpentMetadata {
+                transaction_id: TransactionId::rand(),
+                spent: MilestoneIndexTimestamp {
+                    milestone_index: milestone_index.into(),
+                    milestone_timestamp: (12345 + milestone_index).into(),
+                },
+                input_index: None,
+            },
+        };
+
+        let spent = [2, 2, 3, 5, 9]
+            .into_iter()
+            .map(|milestone_index| spent_at(ledger_output(address), milestone_index))
+            .chain([spent_at(ledger_output(Address::rand_ed25519()), 2)])
+            .collect::<Vec<_>>();
+        output_collection
+            .insert_unspent_outputs(spent.iter().map(|s| &s.output).chain([&ledger_output(address)]))
+            .await
+            .unwrap();
+        output_collection.update_spent_outputs(&spent).await.unwrap();
+
+        let page = |cursor, page_size, order| {
+            let output_collection = &output_collection;
+            async move {
+                output_collection
+                    .get_spent_outputs_by_address(address, page_size, cursor, order, 5.into())
+                    .await
+                    .unwrap()
+                    .map_ok(|res| (res.spent_metadata.spent.milestone_index.0, res.output_id))
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let oldest = page(None, 10, SortOrder::Oldest).await;
+        assert_eq!(
+            oldest.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+            vec![2, 2, 3, 5]
+        );
+        let mut newest = page(None, 10, SortOrder::Newest).await;
+        newest.reverse();
+        assert_eq!(newest, oldest);
+
+        assert_eq!(
+            page(Some((2.into(), Some(oldest[1].1))), 2, SortOrder::Oldest).await,
+            oldest[1..3]
+        );
+        assert_eq!(page(Some((3.into(), None)), 10, SortOrder::Oldest).await, oldest[2..]);
+
+        teardown(db).await;
+    }
+
+    #[tokio::test]
+    async fn test_outputs_by_milestone() {
+        let db = setup_database("test-outputs-by-milestone").await.unwrap();
+        let output_collection = setup_collection::<OutputCollection>(&db).await.unwrap();
+
+        let protocol_params = iota_types::block::protocol::protocol_parameters();
+
+        let outputs = (1..=4u32)
+            .map(|milestone_index| LedgerOutput {
+                output_id: OutputId::rand(),
+                rent_structure: RentStructureBytes {
+                    num_key_bytes: 0,
+                    num_data_bytes: 100,
+                },
+                output: Output::rand(&protocol_params),
+                block_id: BlockId::rand(),
+                booked: MilestoneIndexTimestamp {
+                    milestone_index: (milestone_index % 2).into(),
+                    milestone_timestamp: 12345.into(),
+                },
+            })
+            .collect::<Vec<_>>();
+        let spent = outputs
+            .iter()
+            .take(3)
+            .map(|output| LedgerSpent {
+                output: output.clone(),
+                spent_metadata: SpentMetadata {
+                    transaction_id: TransactionId::rand(),
+                    spent: MilestoneIndexTimestamp {
+                        milestone_index: 2.into(),
+                        milestone_timestamp: 23456.into(),
+                    },
+                    input_index: None,
+                },
+            })
+            .collect::<Vec<_>>();
+
+        output_collection.insert_unspent_outputs(&outputs).await.unwrap();
+        output_collection.update_spent_outputs(&spent).await.unwrap();
+
+        let sorted = |mut output_ids: Vec<OutputId>| {
+            output_ids.sort_by_key(|output_id| output_id.to_hex());
+            output_ids
+        };
+
+        let created = output_collection
+            .get_outputs_created_at(1.into(), None, 10)
+            .await
+            .unwrap()
+            .map_ok(|res| res.metadata.output_id)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(created, sorted(vec![outputs[0].output_id, outputs[2].output_id]));
+
+        let consumed = output_collection
+            .get_outputs_consumed_at(2.into(), None, 10)
+            .await
+            .unwrap()
+            .map_ok(|res| res.metadata.output_id)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(consumed, sorted(spent.iter().map(|s| s.output.output_id).collect()));
+
+        let rest = output_collection
+            .get_outputs_consumed_at(2.into(), Some(consumed[1]), 10)
+            .await
+            .unwrap()
+            .map_ok(|res| res.metadata.output_id)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(rest, consumed[1..]);
+
+        teardown(db).await;
+    }
 }